@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, AnchorDeserialize};
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+
+use multisig_module::{AuditLog, Delegation, Multisig, Transaction, VaultMessage};
+
+use crate::{audit_log_address, delegation_address, transaction_address, ClientError, FetchError, Result};
+
+/// Source of raw account data, implemented over whatever RPC client the
+/// caller uses. `Ok(None)` means the account does not exist.
+pub trait AccountFetcher {
+    fn fetch_account_data(&self, address: &Pubkey) -> std::result::Result<Option<Vec<u8>>, FetchError>;
+}
+
+/// Snapshot of account data, for offline use and tests
+impl AccountFetcher for HashMap<Pubkey, Vec<u8>> {
+    fn fetch_account_data(&self, address: &Pubkey) -> std::result::Result<Option<Vec<u8>>, FetchError> {
+        Ok(self.get(address).cloned())
+    }
+}
+
+/// Decodes an Anchor account, checking its discriminator
+pub fn decode_account<T: AccountDeserialize>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(|source| ClientError::Decode {
+        address: *address,
+        source,
+    })
+}
+
+pub fn fetch_account<T: AccountDeserialize>(fetcher: &impl AccountFetcher, address: &Pubkey) -> Result<T> {
+    let data = fetcher
+        .fetch_account_data(address)?
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    decode_account(address, &data)
+}
+
+pub fn fetch_multisig(fetcher: &impl AccountFetcher, multisig: &Pubkey) -> Result<Multisig> {
+    fetch_account(fetcher, multisig)
+}
+
+pub fn fetch_transaction(fetcher: &impl AccountFetcher, multisig: &Pubkey, transaction_id: u64) -> Result<Transaction> {
+    fetch_account(fetcher, &transaction_address(multisig, transaction_id).0)
+}
+
+pub fn fetch_audit_log(fetcher: &impl AccountFetcher, multisig: &Pubkey) -> Result<AuditLog> {
+    fetch_account(fetcher, &audit_log_address(multisig).0)
+}
+
+/// Returns `None` when the owner has no delegate registered
+pub fn fetch_delegation(fetcher: &impl AccountFetcher, multisig: &Pubkey, owner: &Pubkey) -> Result<Option<Delegation>> {
+    let address = delegation_address(multisig, owner).0;
+    match fetcher.fetch_account_data(&address)? {
+        Some(data) => decode_account(&address, &data).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Pending proposals, i.e. transactions that exist and are not executed yet
+pub fn fetch_pending_transactions(fetcher: &impl AccountFetcher, multisig: &Pubkey) -> Result<Vec<Transaction>> {
+    let state = fetch_multisig(fetcher, multisig)?;
+    let mut pending = Vec::new();
+    for transaction_id in 0..state.transaction_count {
+        let address = transaction_address(multisig, transaction_id).0;
+        if let Some(data) = fetcher.fetch_account_data(&address)? {
+            let transaction: Transaction = decode_account(&address, &data)?;
+            if !transaction.executed {
+                pending.push(transaction);
+            }
+        }
+    }
+    Ok(pending)
+}
+
+pub fn decode_vault_message(transaction: &Transaction) -> Result<VaultMessage> {
+    if !transaction.vault_message {
+        return Err(ClientError::NotVaultTransaction);
+    }
+    VaultMessage::try_from_slice(&transaction.instruction_data).map_err(ClientError::InvalidVaultMessage)
+}
+
+pub fn decode_lookup_table(address: &Pubkey, data: &[u8]) -> Result<Vec<Pubkey>> {
+    let table = AddressLookupTable::deserialize(data).map_err(|_| ClientError::InvalidLookupTable(*address))?;
+    Ok(table.addresses.to_vec())
+}
+
+/// Loads the addresses of every lookup table a vault message references
+pub fn fetch_lookup_tables(fetcher: &impl AccountFetcher, message: &VaultMessage) -> Result<HashMap<Pubkey, Vec<Pubkey>>> {
+    let mut tables = HashMap::new();
+    for lookup in &message.lookups {
+        if tables.contains_key(&lookup.table) {
+            continue;
+        }
+        let data = fetcher
+            .fetch_account_data(&lookup.table)?
+            .ok_or(ClientError::AccountNotFound(lookup.table))?;
+        tables.insert(lookup.table, decode_lookup_table(&lookup.table, &data)?);
+    }
+    Ok(tables)
+}
+
+/// Expands the message's key list in the order the program resolves it:
+/// static keys, then every lookup's writable addresses, then the readonly ones.
+pub fn resolve_vault_message_keys(
+    message: &VaultMessage,
+    tables: &HashMap<Pubkey, Vec<Pubkey>>,
+) -> Result<Vec<Pubkey>> {
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+
+    for lookup in &message.lookups {
+        let addresses = tables
+            .get(&lookup.table)
+            .ok_or(ClientError::AccountNotFound(lookup.table))?;
+        let load = |index: u8| {
+            addresses
+                .get(index as usize)
+                .copied()
+                .ok_or(ClientError::LookupIndexOutOfBounds { table: lookup.table, index })
+        };
+        for &index in &lookup.writable_indexes {
+            writable.push(load(index)?);
+        }
+        for &index in &lookup.readonly_indexes {
+            readonly.push(load(index)?);
+        }
+    }
+
+    let mut keys = message.account_keys.clone();
+    keys.extend(writable);
+    keys.extend(readonly);
+    Ok(keys)
+}
@@ -0,0 +1,1337 @@
+//! Human-readable descriptions of the instructions a proposal would run.
+//!
+//! A [`DecoderRegistry`] maps program ids to [`InstructionDecoder`]s. The
+//! default registry knows the System program, SPL Token, Token-2022 and the
+//! workspace `token_module` and `governance_module` programs. Amounts are
+//! printed with a symbol and decimals when the [`DecodeContext`] knows the
+//! mint, e.g. "transfer 5 USDC from A to B".
+
+use std::collections::HashMap;
+
+use anchor_lang::{prelude::Pubkey, system_program, AnchorDeserialize, Discriminator};
+
+use governance_module::{instruction as governance_ix, ProposalType, VoteType};
+use token_module::{instruction as token_ix, TokenMetadata};
+
+use crate::{PackagedInstruction, ProposalPackage};
+
+const LAMPORTS_DECIMALS: u8 = 9;
+
+/// Display information for a token mint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintInfo {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// What the caller knows about the addresses an instruction touches
+#[derive(Clone, Debug, Default)]
+pub struct DecodeContext {
+    pub mints: HashMap<Pubkey, MintInfo>,
+    /// Mint of each known token account, for instructions that do not pass
+    /// the mint, e.g. SPL Token `Transfer`
+    pub token_accounts: HashMap<Pubkey, Pubkey>,
+    /// Names shown instead of addresses
+    pub labels: HashMap<Pubkey, String>,
+}
+
+impl DecodeContext {
+    pub fn with_mint(mut self, mint: Pubkey, symbol: impl Into<String>, decimals: u8) -> Self {
+        self.mints.insert(mint, MintInfo { symbol: symbol.into(), decimals });
+        self
+    }
+
+    pub fn with_token_metadata(self, metadata: &TokenMetadata) -> Self {
+        self.with_mint(metadata.mint, metadata.symbol.clone(), metadata.decimals)
+    }
+
+    pub fn with_token_account(mut self, token_account: Pubkey, mint: Pubkey) -> Self {
+        self.token_accounts.insert(token_account, mint);
+        self
+    }
+
+    pub fn with_label(mut self, address: Pubkey, label: impl Into<String>) -> Self {
+        self.labels.insert(address, label.into());
+        self
+    }
+
+    pub fn address(&self, address: &Pubkey) -> String {
+        self.labels.get(address).cloned().unwrap_or_else(|| address.to_string())
+    }
+
+    /// Formats a token amount. `decimals` takes precedence over the decimals
+    /// recorded for the mint.
+    pub fn amount(&self, amount: u64, mint: Option<&Pubkey>, decimals: Option<u8>) -> String {
+        let info = mint.and_then(|mint| self.mints.get(mint));
+        let value = match decimals.or(info.map(|info| info.decimals)) {
+            Some(decimals) => format_units(amount, decimals),
+            None => format!("{amount} base units"),
+        };
+        match (info, mint) {
+            (Some(info), _) => format!("{value} {}", info.symbol),
+            (None, Some(mint)) => format!("{value} of {}", self.address(mint)),
+            (None, None) => value,
+        }
+    }
+
+    /// Amount of a token account whose mint is looked up in `token_accounts`
+    pub fn account_amount(&self, amount: u64, token_account: &Pubkey, decimals: Option<u8>) -> String {
+        self.amount(amount, self.token_accounts.get(token_account), decimals)
+    }
+
+    pub fn sol(&self, lamports: u64) -> String {
+        format!("{} SOL", format_units(lamports, LAMPORTS_DECIMALS))
+    }
+}
+
+/// Formats `amount` base units with `decimals` places, dropping trailing zeros
+pub fn format_units(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let digits = format!("{amount:0>width$}", width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    match fraction.trim_end_matches('0') {
+        "" => whole.to_string(),
+        fraction => format!("{whole}.{fraction}"),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    pub program_id: Pubkey,
+    pub program: String,
+    /// Instruction name in snake case, e.g. `transfer_checked`
+    pub instruction: String,
+    /// Decoded arguments and named accounts, in instruction order
+    pub fields: Vec<(String, String)>,
+    pub description: String,
+}
+
+impl DecodedInstruction {
+    pub fn new(
+        instruction: &PackagedInstruction,
+        program: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            program_id: instruction.program_id,
+            program: program.into(),
+            instruction: name.into(),
+            fields: Vec::new(),
+            description: description.into(),
+        }
+    }
+
+    pub fn field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+
+    /// Fallback for programs or instructions no decoder understands
+    pub fn unknown(instruction: &PackagedInstruction) -> Self {
+        Self::new(
+            instruction,
+            instruction.program_id.to_string(),
+            "unknown",
+            format!(
+                "unknown instruction for program {} ({} accounts, {} bytes of data)",
+                instruction.program_id,
+                instruction.accounts.len(),
+                instruction.data.len()
+            ),
+        )
+    }
+}
+
+impl std::fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+/// Decodes the instructions of one program. Returning `None` falls back to
+/// [`DecodedInstruction::unknown`].
+pub trait InstructionDecoder: Send + Sync {
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction>;
+}
+
+impl<F> InstructionDecoder for F
+where
+    F: Fn(&PackagedInstruction, &DecodeContext) -> Option<DecodedInstruction> + Send + Sync,
+{
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction> {
+        self(instruction, context)
+    }
+}
+
+pub struct DecoderRegistry {
+    decoders: HashMap<Pubkey, Box<dyn InstructionDecoder>>,
+}
+
+impl Default for DecoderRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(system_program::ID, SystemDecoder);
+        registry.register(anchor_spl::token::ID, SplTokenDecoder);
+        registry.register(anchor_spl::token_2022::ID, SplTokenDecoder);
+        registry.register(token_module::ID, TokenModuleDecoder);
+        registry.register(governance_module::ID, GovernanceDecoder);
+        registry
+    }
+}
+
+impl DecoderRegistry {
+    /// An empty registry; use `default()` for the built-in decoders
+    pub fn new() -> Self {
+        Self { decoders: HashMap::new() }
+    }
+
+    /// Registers `decoder` for `program_id`, replacing any previous one
+    pub fn register(&mut self, program_id: Pubkey, decoder: impl InstructionDecoder + 'static) -> &mut Self {
+        self.decoders.insert(program_id, Box::new(decoder));
+        self
+    }
+
+    pub fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> DecodedInstruction {
+        self.decoders
+            .get(&instruction.program_id)
+            .and_then(|decoder| decoder.decode(instruction, context))
+            .unwrap_or_else(|| DecodedInstruction::unknown(instruction))
+    }
+
+    /// Describes every instruction of a vault message package. Packages for
+    /// other payloads carry no instructions and yield an empty list.
+    pub fn describe(&self, package: &ProposalPackage, context: &DecodeContext) -> Vec<DecodedInstruction> {
+        package
+            .instructions
+            .iter()
+            .map(|instruction| self.decode(instruction, context))
+            .collect()
+    }
+}
+
+/// Little-endian reader over instruction data
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        if self.0.len() < N {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        head.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn pubkey(&mut self) -> Option<Pubkey> {
+        self.take().map(Pubkey::new_from_array)
+    }
+
+    /// SPL `COption<Pubkey>`: a one byte tag followed by the key when set
+    fn optional_pubkey(&mut self) -> Option<Option<Pubkey>> {
+        match self.u8()? {
+            0 => Some(None),
+            1 => self.pubkey().map(Some),
+            _ => None,
+        }
+    }
+}
+
+fn account(instruction: &PackagedInstruction, index: usize) -> Option<Pubkey> {
+    instruction.accounts.get(index).map(|meta| meta.pubkey)
+}
+
+fn anchor_args<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    data.strip_prefix(T::DISCRIMINATOR)
+        .and_then(|args| T::try_from_slice(args).ok())
+}
+
+// System program
+
+pub struct SystemDecoder;
+
+impl InstructionDecoder for SystemDecoder {
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction> {
+        const PROGRAM: &str = "System";
+        let mut data = Reader(&instruction.data);
+        let decoded = match data.u32()? {
+            0 => {
+                let (lamports, space, owner) = (data.u64()?, data.u64()?, data.pubkey()?);
+                let (funder, new_account) = (account(instruction, 0)?, account(instruction, 1)?);
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "create_account",
+                    format!(
+                        "create account {} with {} and {space} bytes, owned by {}",
+                        context.address(&new_account),
+                        context.sol(lamports),
+                        context.address(&owner)
+                    ),
+                )
+                .field("funder", context.address(&funder))
+                .field("new_account", context.address(&new_account))
+                .field("lamports", context.sol(lamports))
+                .field("space", space.to_string())
+                .field("owner", context.address(&owner))
+            }
+            1 => {
+                let owner = data.pubkey()?;
+                let target = account(instruction, 0)?;
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "assign",
+                    format!("assign {} to program {}", context.address(&target), context.address(&owner)),
+                )
+                .field("account", context.address(&target))
+                .field("owner", context.address(&owner))
+            }
+            2 => {
+                let lamports = data.u64()?;
+                let (from, to) = (account(instruction, 0)?, account(instruction, 1)?);
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "transfer",
+                    format!(
+                        "transfer {} from {} to {}",
+                        context.sol(lamports),
+                        context.address(&from),
+                        context.address(&to)
+                    ),
+                )
+                .field("from", context.address(&from))
+                .field("to", context.address(&to))
+                .field("lamports", context.sol(lamports))
+            }
+            8 => {
+                let space = data.u64()?;
+                let target = account(instruction, 0)?;
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "allocate",
+                    format!("allocate {space} bytes for {}", context.address(&target)),
+                )
+                .field("account", context.address(&target))
+                .field("space", space.to_string())
+            }
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
+
+// SPL Token and Token-2022, which share the instruction layout used here
+
+pub struct SplTokenDecoder;
+
+fn authority_type_name(authority_type: u8) -> String {
+    match authority_type {
+        0 => "mint".to_string(),
+        1 => "freeze".to_string(),
+        2 => "owner".to_string(),
+        3 => "close".to_string(),
+        other => format!("type {other}"),
+    }
+}
+
+impl InstructionDecoder for SplTokenDecoder {
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction> {
+        let program = match instruction.program_id == anchor_spl::token_2022::ID {
+            true => "Token-2022",
+            false => "SPL Token",
+        };
+        let address = |index: usize| account(instruction, index).map(|key| context.address(&key));
+        let mut data = Reader(&instruction.data);
+
+        let decoded = match data.u8()? {
+            3 => {
+                let amount = data.u64()?;
+                let source = account(instruction, 0)?;
+                let value = context.account_amount(amount, &source, None);
+                DecodedInstruction::new(
+                    instruction,
+                    program,
+                    "transfer",
+                    format!("transfer {value} from {} to {}", address(0)?, address(1)?),
+                )
+                .field("source", address(0)?)
+                .field("destination", address(1)?)
+                .field("authority", address(2)?)
+                .field("amount", value)
+            }
+            12 => {
+                let (amount, decimals) = (data.u64()?, data.u8()?);
+                let value = context.amount(amount, Some(&account(instruction, 1)?), Some(decimals));
+                DecodedInstruction::new(
+                    instruction,
+                    program,
+                    "transfer_checked",
+                    format!("transfer {value} from {} to {}", address(0)?, address(2)?),
+                )
+                .field("source", address(0)?)
+                .field("mint", address(1)?)
+                .field("destination", address(2)?)
+                .field("authority", address(3)?)
+                .field("amount", value)
+            }
+            4 => {
+                let amount = data.u64()?;
+                let value = context.account_amount(amount, &account(instruction, 0)?, None);
+                DecodedInstruction::new(
+                    instruction,
+                    program,
+                    "approve",
+                    format!("approve {} to spend {value} from {}", address(1)?, address(0)?),
+                )
+                .field("source", address(0)?)
+                .field("delegate", address(1)?)
+                .field("owner", address(2)?)
+                .field("amount", value)
+            }
+            13 => {
+                let (amount, decimals) = (data.u64()?, data.u8()?);
+                let value = context.amount(amount, Some(&account(instruction, 1)?), Some(decimals));
+                DecodedInstruction::new(
+                    instruction,
+                    program,
+                    "approve_checked",
+                    format!("approve {} to spend {value} from {}", address(2)?, address(0)?),
+                )
+                .field("source", address(0)?)
+                .field("mint", address(1)?)
+                .field("delegate", address(2)?)
+                .field("owner", address(3)?)
+                .field("amount", value)
+            }
+            5 => DecodedInstruction::new(
+                instruction,
+                program,
+                "revoke",
+                format!("revoke the delegate of {}", address(0)?),
+            )
+            .field("source", address(0)?)
+            .field("owner", address(1)?),
+            6 => {
+                let authority_type = authority_type_name(data.u8()?);
+                let new_authority = data.optional_pubkey()?;
+                let description = match &new_authority {
+                    Some(new_authority) => format!(
+                        "set the {authority_type} authority of {} to {}",
+                        address(0)?,
+                        context.address(new_authority)
+                    ),
+                    None => format!("remove the {authority_type} authority of {}", address(0)?),
+                };
+                DecodedInstruction::new(instruction, program, "set_authority", description)
+                    .field("account", address(0)?)
+                    .field("current_authority", address(1)?)
+                    .field("authority_type", authority_type)
+                    .field(
+                        "new_authority",
+                        new_authority.map_or("none".to_string(), |key| context.address(&key)),
+                    )
+            }
+            tag @ (7 | 14) => {
+                let amount = data.u64()?;
+                let decimals = match tag {
+                    14 => Some(data.u8()?),
+                    _ => None,
+                };
+                let value = context.amount(amount, Some(&account(instruction, 0)?), decimals);
+                let name = match tag {
+                    14 => "mint_to_checked",
+                    _ => "mint_to",
+                };
+                DecodedInstruction::new(instruction, program, name, format!("mint {value} to {}", address(1)?))
+                    .field("mint", address(0)?)
+                    .field("destination", address(1)?)
+                    .field("authority", address(2)?)
+                    .field("amount", value)
+            }
+            tag @ (8 | 15) => {
+                let amount = data.u64()?;
+                let decimals = match tag {
+                    15 => Some(data.u8()?),
+                    _ => None,
+                };
+                let value = context.amount(amount, Some(&account(instruction, 1)?), decimals);
+                let name = match tag {
+                    15 => "burn_checked",
+                    _ => "burn",
+                };
+                DecodedInstruction::new(instruction, program, name, format!("burn {value} from {}", address(0)?))
+                    .field("account", address(0)?)
+                    .field("mint", address(1)?)
+                    .field("authority", address(2)?)
+                    .field("amount", value)
+            }
+            9 => DecodedInstruction::new(
+                instruction,
+                program,
+                "close_account",
+                format!("close token account {} and send its rent to {}", address(0)?, address(1)?),
+            )
+            .field("account", address(0)?)
+            .field("destination", address(1)?)
+            .field("owner", address(2)?),
+            tag @ (10 | 11) => {
+                let (name, verb) = match tag {
+                    10 => ("freeze_account", "freeze"),
+                    _ => ("thaw_account", "thaw"),
+                };
+                DecodedInstruction::new(instruction, program, name, format!("{verb} token account {}", address(0)?))
+                    .field("account", address(0)?)
+                    .field("mint", address(1)?)
+                    .field("authority", address(2)?)
+            }
+            17 => DecodedInstruction::new(
+                instruction,
+                program,
+                "sync_native",
+                format!("sync the SOL balance of {}", address(0)?),
+            )
+            .field("account", address(0)?),
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
+
+// Workspace token_module program
+
+pub struct TokenModuleDecoder;
+
+impl InstructionDecoder for TokenModuleDecoder {
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction> {
+        const PROGRAM: &str = "Token Module";
+        let data = &instruction.data;
+        let address = |index: usize| account(instruction, index).map(|key| context.address(&key));
+        let mint_amount = |amount: u64| account(instruction, 1).map(|mint| context.amount(amount, Some(&mint), None));
+
+        if anchor_args::<token_ix::InitializeTokenAuthority>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "initialize_token_authority",
+                    format!("initialize the token authority with admin {}", address(0)?),
+                )
+                .field("admin", address(0)?),
+            );
+        }
+        if anchor_args::<token_ix::MigrateMintAuthority>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "migrate_mint_authority",
+                    format!("move {} to its own mint authority {}", address(0)?, address(2)?),
+                )
+                .field("mint", address(0)?)
+                .field("mint_authority", address(2)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::CreateToken>(data) {
+            // An omitted optional account is passed as the program id
+            let token_admin = account(instruction, 7)
+                .filter(|designated| *designated != token_module::ID)
+                .map_or(address(0)?, |designated| context.address(&designated));
+            let max_supply = args
+                .max_supply
+                .map_or("unlimited".to_string(), |max_supply| format_units(max_supply, args.decimals));
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "create_token",
+                    format!(
+                        "create token {} ({}) at {} with {} decimals and max supply {max_supply}",
+                        args.name,
+                        args.symbol,
+                        address(1)?,
+                        args.decimals
+                    ),
+                )
+                .field("payer", address(0)?)
+                .field("admin", token_admin)
+                .field("mint", address(1)?)
+                .field("name", args.name)
+                .field("symbol", args.symbol)
+                .field("decimals", args.decimals.to_string())
+                .field("uri", args.uri.unwrap_or_default())
+                .field("max_supply", max_supply),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::TransferSol>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "transfer_sol",
+                    format!("transfer {} from {} to {}", context.sol(args.amount), address(0)?, address(1)?),
+                )
+                .field("from", address(0)?)
+                .field("to", address(1)?)
+                .field("amount", context.sol(args.amount)),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::CreateSolEscrow>(data) {
+            let value = context.sol(args.amount);
+            let recipient = args
+                .recipient
+                .map_or("any recipient".to_string(), |recipient| context.address(&recipient));
+            let mut decoded = DecodedInstruction::new(
+                instruction,
+                PROGRAM,
+                "create_sol_escrow",
+                format!("escrow {value} from {} for {recipient}", address(0)?),
+            )
+            .field("sender", address(0)?)
+            .field("escrow", address(1)?)
+            .field("amount", value)
+            .field("recipient", recipient);
+            if let Some(release_after) = args.release_after {
+                decoded = decoded.field("release_after", release_after.to_string());
+            }
+            if let Some(refund_after) = args.refund_after {
+                decoded = decoded.field("refund_after", refund_after.to_string());
+            }
+            return Some(decoded);
+        }
+        if anchor_args::<token_ix::ReleaseSolEscrow>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "release_sol_escrow",
+                    format!("release SOL escrow {} to {}", address(1)?, address(0)?),
+                )
+                .field("recipient", address(0)?)
+                .field("escrow", address(1)?),
+            );
+        }
+        if anchor_args::<token_ix::CancelSolEscrow>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "cancel_sol_escrow",
+                    format!("cancel SOL escrow {} and refund {}", address(1)?, address(0)?),
+                )
+                .field("sender", address(0)?)
+                .field("escrow", address(1)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::UpdateTokenMetadata>(data) {
+            let uri = args.uri.unwrap_or_else(|| "none".to_string());
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "update_token_metadata",
+                    format!("rename {} to {} ({})", address(1)?, args.name, args.symbol),
+                )
+                .field("admin", address(0)?)
+                .field("mint", address(1)?)
+                .field("name", args.name)
+                .field("symbol", args.symbol)
+                .field("uri", uri),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::MintTokens>(data) {
+            let value = mint_amount(args.amount)?;
+            return Some(
+                DecodedInstruction::new(instruction, PROGRAM, "mint_tokens", format!("mint {value} to {}", address(2)?))
+                    .field("admin", address(0)?)
+                    .field("mint", address(1)?)
+                    .field("destination", address(2)?)
+                    .field("amount", value),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::TransferTokens>(data) {
+            let value = mint_amount(args.amount)?;
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "transfer_tokens",
+                    format!("transfer {value} from {} to {}", address(2)?, address(3)?),
+                )
+                .field("owner", address(0)?)
+                .field("mint", address(1)?)
+                .field("from", address(2)?)
+                .field("to", address(3)?)
+                .field("amount", value),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::BurnTokens>(data) {
+            let value = mint_amount(args.amount)?;
+            return Some(
+                DecodedInstruction::new(instruction, PROGRAM, "burn_tokens", format!("burn {value} from {}", address(2)?))
+                    .field("owner", address(0)?)
+                    .field("mint", address(1)?)
+                    .field("token_account", address(2)?)
+                    .field("amount", value),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::CreateEscrow>(data) {
+            let value = mint_amount(args.amount)?;
+            let recipient = args
+                .recipient
+                .map_or("any recipient".to_string(), |recipient| context.address(&recipient));
+            let mut decoded = DecodedInstruction::new(
+                instruction,
+                PROGRAM,
+                "create_escrow",
+                format!("escrow {value} from {} for {recipient}", address(0)?),
+            )
+            .field("sender", address(0)?)
+            .field("mint", address(1)?)
+            .field("escrow", address(3)?)
+            .field("amount", value)
+            .field("recipient", recipient);
+            if let Some(release_after) = args.terms.release_after {
+                decoded = decoded.field("release_after", release_after.to_string());
+            }
+            if let Some(refund_after) = args.terms.refund_after {
+                decoded = decoded.field("refund_after", refund_after.to_string());
+            }
+            for (index, milestone) in args.terms.milestones.iter().enumerate() {
+                let value = mint_amount(milestone.amount)?;
+                let terms = match milestone.release_at {
+                    Some(release_at) => format!("{value}, due at {release_at}"),
+                    None => format!("{value} on approval"),
+                };
+                decoded = decoded.field(format!("milestone_{index}"), terms);
+            }
+            if let Some(arbiter) = args.terms.arbiter {
+                decoded = decoded.field("arbiter", context.address(&arbiter));
+            }
+            return Some(decoded);
+        }
+        if anchor_args::<token_ix::RaiseDispute>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "raise_dispute",
+                    format!("dispute escrow {} as {}", address(1)?, address(0)?),
+                )
+                .field("party", address(0)?)
+                .field("escrow", address(1)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::ResolveDispute>(data) {
+            let value = context.amount(args.recipient_amount, Some(&account(instruction, 2)?), None);
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "resolve_dispute",
+                    format!("resolve escrow {} with {value} to {} and the rest to {}", address(1)?, address(4)?, address(3)?),
+                )
+                .field("arbiter", address(0)?)
+                .field("escrow", address(1)?)
+                .field("recipient_amount", value),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::CreateVesting>(data) {
+            let value = mint_amount(args.total_amount)?;
+            let schedule = args.schedule;
+            let mut decoded = DecodedInstruction::new(
+                instruction,
+                PROGRAM,
+                "create_vesting",
+                format!("vest {value} from {} to {}", address(0)?, context.address(&args.beneficiary)),
+            )
+            .field("issuer", address(0)?)
+            .field("mint", address(1)?)
+            .field("vesting", address(3)?)
+            .field("total_amount", value)
+            .field("beneficiary", context.address(&args.beneficiary))
+            .field("start_time", schedule.start_time.to_string())
+            .field("cliff_seconds", schedule.cliff_seconds.to_string())
+            .field("duration_seconds", schedule.duration_seconds.to_string())
+            .field("release_interval_seconds", schedule.release_interval_seconds.to_string());
+            if let Some(revoke_authority) = args.revoke_authority {
+                decoded = decoded.field("revoke_authority", context.address(&revoke_authority));
+            }
+            return Some(decoded);
+        }
+        if anchor_args::<token_ix::ClaimVested>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "claim_vested",
+                    format!("claim vested tokens from {} to {}", address(1)?, address(0)?),
+                )
+                .field("beneficiary", address(0)?)
+                .field("vesting", address(1)?)
+                .field("mint", address(2)?),
+            );
+        }
+        if anchor_args::<token_ix::RevokeVesting>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "revoke_vesting",
+                    format!("revoke vesting {} and return the unvested tokens to {}", address(1)?, address(3)?),
+                )
+                .field("revoke_authority", address(0)?)
+                .field("vesting", address(1)?)
+                .field("mint", address(2)?)
+                .field("issuer", address(3)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::ApproveMilestone>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "approve_milestone",
+                    format!("approve milestone {} of escrow {}", args.index, address(1)?),
+                )
+                .field("sender", address(0)?)
+                .field("escrow", address(1)?)
+                .field("index", args.index.to_string()),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::ReleaseMilestone>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "release_milestone",
+                    format!("release milestone {} of escrow {} to {}", args.index, address(1)?, address(0)?),
+                )
+                .field("recipient", address(0)?)
+                .field("escrow", address(1)?)
+                .field("mint", address(2)?)
+                .field("index", args.index.to_string()),
+            );
+        }
+        if anchor_args::<token_ix::CancelEscrow>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "cancel_escrow",
+                    format!("cancel escrow {} and refund {}", address(1)?, address(0)?),
+                )
+                .field("sender", address(0)?)
+                .field("escrow", address(1)?)
+                .field("mint", address(2)?),
+            );
+        }
+        if anchor_args::<token_ix::MigrateEscrow>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "migrate_escrow",
+                    format!("grow escrow {} to the current layout", address(0)?),
+                )
+                .field("escrow", address(0)?)
+                .field("payer", address(1)?),
+            );
+        }
+        if anchor_args::<token_ix::ReleaseEscrow>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "release_escrow",
+                    format!("release escrow {} to {}", address(1)?, address(0)?),
+                )
+                .field("recipient", address(0)?)
+                .field("escrow", address(1)?)
+                .field("mint", address(2)?),
+            );
+        }
+        let pause = if anchor_args::<token_ix::PauseToken>(data).is_some() {
+            Some(("pause_token", "pause"))
+        } else if anchor_args::<token_ix::UnpauseToken>(data).is_some() {
+            Some(("unpause_token", "unpause"))
+        } else {
+            None
+        };
+        if let Some((name, verb)) = pause {
+            return Some(
+                DecodedInstruction::new(instruction, PROGRAM, name, format!("{verb} token {}", address(1)?))
+                    .field("admin", address(0)?)
+                    .field("mint", address(1)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::GrantRole>(data) {
+            let role = format!("{:?}", args.role);
+            let mut decoded = DecodedInstruction::new(
+                instruction,
+                PROGRAM,
+                "grant_role",
+                format!("grant {role} on {} to {}", address(1)?, context.address(&args.holder)),
+            )
+            .field("admin", address(0)?)
+            .field("mint", address(1)?)
+            .field("holder", context.address(&args.holder))
+            .field("role", role);
+            if let Some(total) = args.quota.total {
+                decoded = decoded.field("total_quota", mint_amount(total)?);
+            }
+            if let Some(per_period) = args.quota.per_period {
+                let value = mint_amount(per_period)?;
+                decoded = decoded.field("period_quota", format!("{value} per {}s", args.quota.period_seconds));
+            }
+            return Some(decoded);
+        }
+        if anchor_args::<token_ix::RevokeRole>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "revoke_role",
+                    format!("revoke role {} on {}", address(3)?, address(1)?),
+                )
+                .field("admin", address(0)?)
+                .field("mint", address(1)?)
+                .field("mint_role", address(3)?),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::ProposeTokenAdmin>(data) {
+            let successor = args.new_admin.map_or("nobody".to_string(), |admin| context.address(&admin));
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "propose_token_admin",
+                    format!("propose {successor} as admin of {}", address(1)?),
+                )
+                .field("admin", address(0)?)
+                .field("mint", address(1)?)
+                .field("new_admin", successor),
+            );
+        }
+        if let Some(args) = anchor_args::<token_ix::ProposeTokenAuthorityAdmin>(data) {
+            let successor = args.new_admin.map_or("nobody".to_string(), |admin| context.address(&admin));
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "propose_token_authority_admin",
+                    format!("propose {successor} as token authority admin"),
+                )
+                .field("admin", address(0)?)
+                .field("new_admin", successor),
+            );
+        }
+        if anchor_args::<token_ix::AcceptTokenAdmin>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "accept_token_admin",
+                    format!("accept admin of {} as {}", address(1)?, address(0)?),
+                )
+                .field("pending_admin", address(0)?)
+                .field("mint", address(1)?),
+            );
+        }
+        if anchor_args::<token_ix::AcceptTokenAuthorityAdmin>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "accept_token_authority_admin",
+                    format!("accept token authority admin as {}", address(0)?),
+                )
+                .field("pending_admin", address(0)?),
+            );
+        }
+        if anchor_args::<token_ix::RenounceTokenAdmin>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "renounce_token_admin",
+                    format!("renounce admin of {} for good", address(1)?),
+                )
+                .field("admin", address(0)?)
+                .field("mint", address(1)?),
+            );
+        }
+        if anchor_args::<token_ix::RenounceTokenAuthorityAdmin>(data).is_some() {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "renounce_token_authority_admin",
+                    "renounce token authority admin for good",
+                )
+                .field("admin", address(0)?),
+            );
+        }
+        let (name, verb) = if anchor_args::<token_ix::FreezeTokenAccount>(data).is_some() {
+            ("freeze_token_account", "freeze")
+        } else if anchor_args::<token_ix::ThawTokenAccount>(data).is_some() {
+            ("thaw_token_account", "thaw")
+        } else {
+            return None;
+        };
+        Some(
+            DecodedInstruction::new(instruction, PROGRAM, name, format!("{verb} token account {}", address(2)?))
+                .field("admin", address(0)?)
+                .field("mint", address(1)?)
+                .field("token_account", address(2)?),
+        )
+    }
+}
+
+// Workspace governance_module program
+
+pub struct GovernanceDecoder;
+
+fn vote_type_name(vote_type: &VoteType) -> &'static str {
+    match vote_type {
+        VoteType::For => "for",
+        VoteType::Against => "against",
+        VoteType::Abstain => "abstain",
+    }
+}
+
+fn proposal_type_description(proposal_type: &ProposalType, context: &DecodeContext) -> String {
+    match proposal_type {
+        ProposalType::Treasury { recipient, amount, token_mint } => {
+            let value = match token_mint {
+                Some(mint) => context.amount(*amount, Some(mint), None),
+                None => context.sol(*amount),
+            };
+            format!("treasury payment of {value} to {}", context.address(recipient))
+        }
+        ProposalType::ConfigChange { parameter, new_value } => {
+            format!("config change of {parameter} ({} bytes)", new_value.len())
+        }
+        ProposalType::Emergency { action } => format!("emergency action {action:?}"),
+        ProposalType::Custom { target_program, instruction_data } => format!(
+            "custom instruction for {} ({} bytes)",
+            context.address(target_program),
+            instruction_data.len()
+        ),
+    }
+}
+
+impl InstructionDecoder for GovernanceDecoder {
+    fn decode(&self, instruction: &PackagedInstruction, context: &DecodeContext) -> Option<DecodedInstruction> {
+        const PROGRAM: &str = "Governance";
+        let data = &instruction.data;
+        let address = |index: usize| account(instruction, index).map(|key| context.address(&key));
+
+        if let Some(args) = anchor_args::<governance_ix::CreateGovernance>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "create_governance",
+                    format!("create governance {} at {}", args.name, address(1)?),
+                )
+                .field("authority", address(0)?)
+                .field("governance", address(1)?)
+                .field("name", args.name)
+                .field("quorum_percentage", args.config.quorum_percentage.to_string())
+                .field("voting_period_hours", args.config.voting_period_hours.to_string()),
+            );
+        }
+        if let Some(args) = anchor_args::<governance_ix::CreateProposal>(data) {
+            let kind = proposal_type_description(&args.proposal_type, context);
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "create_proposal",
+                    format!("propose \"{}\" in {}: {kind}", args.title, address(1)?),
+                )
+                .field("proposer", address(0)?)
+                .field("governance", address(1)?)
+                .field("proposal", address(2)?)
+                .field("title", args.title)
+                .field("proposal_type", kind),
+            );
+        }
+        if let Some(args) = anchor_args::<governance_ix::Vote>(data) {
+            let vote = vote_type_name(&args.vote_type);
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "vote",
+                    format!("vote {vote} on proposal {} in {}", args.proposal_id, address(1)?),
+                )
+                .field("voter", address(0)?)
+                .field("governance", address(1)?)
+                .field("proposal_id", args.proposal_id.to_string())
+                .field("vote", vote),
+            );
+        }
+        if let Some(args) = anchor_args::<governance_ix::ExecuteProposal>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "execute_proposal",
+                    format!("execute proposal {} in {}", args.proposal_id, address(1)?),
+                )
+                .field("governance", address(1)?)
+                .field("proposal_id", args.proposal_id.to_string()),
+            );
+        }
+        if let Some(args) = anchor_args::<governance_ix::CancelProposal>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "cancel_proposal",
+                    format!("cancel proposal {} in {}: {}", args.proposal_id, address(1)?, args.reason),
+                )
+                .field("governance", address(1)?)
+                .field("proposal_id", args.proposal_id.to_string())
+                .field("reason", args.reason),
+            );
+        }
+        if let Some(args) = anchor_args::<governance_ix::UpdateGovernanceConfig>(data) {
+            return Some(
+                DecodedInstruction::new(
+                    instruction,
+                    PROGRAM,
+                    "update_governance_config",
+                    format!("update the config of {} from proposal {}", address(0)?, args.proposal_id),
+                )
+                .field("governance", address(0)?)
+                .field("proposal_id", args.proposal_id.to_string())
+                .field("quorum_percentage", args.new_config.quorum_percentage.to_string())
+                .field("voting_period_hours", args.new_config.voting_period_hours.to_string())
+                .field("execution_delay_hours", args.new_config.execution_delay_hours.to_string()),
+            );
+        }
+        let (name, verb) = if anchor_args::<governance_ix::EmergencyPause>(data).is_some() {
+            ("emergency_pause", "pause")
+        } else if anchor_args::<governance_ix::Unpause>(data).is_some() {
+            ("unpause", "unpause")
+        } else {
+            return None;
+        };
+        Some(
+            DecodedInstruction::new(instruction, PROGRAM, name, format!("{verb} governance {}", address(1)?))
+                .field("caller", address(0)?)
+                .field("governance", address(1)?),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::InstructionData;
+    use token_module::{EscrowTerms, MilestoneTerms};
+
+    use super::*;
+    use crate::PackagedAccountMeta;
+
+    fn packaged(program_id: Pubkey, accounts: &[Pubkey], data: Vec<u8>) -> PackagedInstruction {
+        PackagedInstruction {
+            program_id,
+            accounts: accounts
+                .iter()
+                .map(|pubkey| PackagedAccountMeta { pubkey: *pubkey, is_signer: false, is_writable: true })
+                .collect(),
+            data,
+        }
+    }
+
+    fn decode(instruction: &PackagedInstruction, context: &DecodeContext) -> DecodedInstruction {
+        DecoderRegistry::default().decode(instruction, context)
+    }
+
+    fn field<'a>(decoded: &'a DecodedInstruction, name: &str) -> &'a str {
+        decoded
+            .fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_else(|| panic!("no field {name}"))
+    }
+
+    #[test]
+    fn format_units_drops_trailing_zeros() {
+        assert_eq!(format_units(1_234, 0), "1234");
+        assert_eq!(format_units(0, 0), "0");
+        assert_eq!(format_units(1_500_000, 6), "1.5");
+        assert_eq!(format_units(2_000_000, 6), "2");
+        assert_eq!(format_units(120, 2), "1.2");
+        assert_eq!(format_units(5, 6), "0.000005");
+        assert_eq!(format_units(0, 6), "0");
+        assert_eq!(format_units(u64::MAX, 9), "18446744073.709551615");
+    }
+
+    #[test]
+    fn amounts_fall_back_without_mint_info() {
+        let mint = Pubkey::new_unique();
+        let context = DecodeContext::default();
+        assert_eq!(context.amount(5, Some(&mint), None), format!("5 base units of {mint}"));
+        assert_eq!(context.amount(5, Some(&mint), Some(1)), format!("0.5 of {mint}"));
+        let context = context.with_mint(mint, "USDC", 6);
+        assert_eq!(context.amount(5, Some(&mint), None), "0.000005 USDC");
+    }
+
+    #[test]
+    fn decodes_system_transfer() {
+        let (from, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = DecodeContext::default().with_label(from, "Treasury").with_label(to, "Alice");
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend(1_500_000_000u64.to_le_bytes());
+
+        let decoded = decode(&packaged(system_program::ID, &[from, to], data), &context);
+        assert_eq!(decoded.program, "System");
+        assert_eq!(decoded.instruction, "transfer");
+        assert_eq!(decoded.description, "transfer 1.5 SOL from Treasury to Alice");
+        assert_eq!(field(&decoded, "lamports"), "1.5 SOL");
+    }
+
+    #[test]
+    fn decodes_spl_transfer_checked() {
+        let (source, mint, destination, owner) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let context = DecodeContext::default()
+            .with_mint(mint, "USDC", 6)
+            .with_label(source, "Vault USDC")
+            .with_label(destination, "Payroll");
+        let mut data = vec![12];
+        data.extend(2_500_000u64.to_le_bytes());
+        data.push(6);
+
+        let accounts = [source, mint, destination, owner];
+        let decoded = decode(&packaged(anchor_spl::token::ID, &accounts, data.clone()), &context);
+        assert_eq!(decoded.program, "SPL Token");
+        assert_eq!(decoded.instruction, "transfer_checked");
+        assert_eq!(decoded.description, "transfer 2.5 USDC from Vault USDC to Payroll");
+        assert_eq!(field(&decoded, "authority"), owner.to_string());
+
+        let decoded = decode(&packaged(anchor_spl::token_2022::ID, &accounts, data), &context);
+        assert_eq!(decoded.program, "Token-2022");
+    }
+
+    #[test]
+    fn decodes_token_module_mint_tokens() {
+        let (admin, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let context = DecodeContext::default().with_mint(mint, "TKN", 9).with_label(destination, "Alice");
+        let data = token_ix::MintTokens { amount: 10_000_000_000 }.data();
+
+        let decoded = decode(&packaged(token_module::ID, &[admin, mint, destination], data), &context);
+        assert_eq!(decoded.program, "Token Module");
+        assert_eq!(decoded.instruction, "mint_tokens");
+        assert_eq!(decoded.description, "mint 10 TKN to Alice");
+        assert_eq!(field(&decoded, "admin"), admin.to_string());
+    }
+
+    #[test]
+    fn decodes_token_module_create_escrow() {
+        let accounts: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (sender, mint, escrow) = (accounts[0], accounts[1], accounts[3]);
+        let (recipient, arbiter) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = DecodeContext::default()
+            .with_mint(mint, "TKN", 2)
+            .with_label(sender, "Vault")
+            .with_label(recipient, "Contractor");
+        let data = token_ix::CreateEscrow {
+            amount: 1_000,
+            seed: [3; 32],
+            recipient: Some(recipient),
+            terms: EscrowTerms {
+                release_after: Some(100),
+                refund_after: None,
+                milestones: vec![
+                    MilestoneTerms { amount: 400, release_at: Some(200) },
+                    MilestoneTerms { amount: 600, release_at: None },
+                ],
+                arbiter: Some(arbiter),
+            },
+        }
+        .data();
+
+        let decoded = decode(&packaged(token_module::ID, &accounts, data), &context);
+        assert_eq!(decoded.instruction, "create_escrow");
+        assert_eq!(decoded.description, "escrow 10 TKN from Vault for Contractor");
+        assert_eq!(field(&decoded, "escrow"), escrow.to_string());
+        assert_eq!(field(&decoded, "release_after"), "100");
+        assert_eq!(field(&decoded, "milestone_0"), "4 TKN, due at 200");
+        assert_eq!(field(&decoded, "milestone_1"), "6 TKN on approval");
+        assert_eq!(field(&decoded, "arbiter"), arbiter.to_string());
+        assert!(!decoded.fields.iter().any(|(name, _)| name == "refund_after"));
+    }
+
+    #[test]
+    fn decodes_governance_vote() {
+        let (voter, governance) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let context = DecodeContext::default().with_label(governance, "DAO");
+        let data = governance_ix::Vote { proposal_id: 4, vote_type: VoteType::Against }.data();
+
+        let decoded = decode(&packaged(governance_module::ID, &[voter, governance], data), &context);
+        assert_eq!(decoded.program, "Governance");
+        assert_eq!(decoded.instruction, "vote");
+        assert_eq!(decoded.description, "vote against on proposal 4 in DAO");
+        assert_eq!(field(&decoded, "vote"), "against");
+    }
+
+    #[test]
+    fn truncated_or_unknown_instructions_fall_back() {
+        let context = DecodeContext::default();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // System transfer cut off inside the lamports
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend([1, 2, 3]);
+        let decoded = decode(&packaged(system_program::ID, &[first, second], data), &context);
+        assert_eq!(decoded.instruction, "unknown");
+        assert_eq!(
+            decoded.description,
+            format!("unknown instruction for program {} (2 accounts, 7 bytes of data)", system_program::ID)
+        );
+
+        // SPL transfer_checked without the decimals byte
+        let mut data = vec![12];
+        data.extend(5u64.to_le_bytes());
+        let decoded = decode(&packaged(anchor_spl::token::ID, &[first, second, first, second], data), &context);
+        assert_eq!(decoded.instruction, "unknown");
+
+        // Anchor discriminator with truncated arguments
+        let mut data = token_ix::MintTokens { amount: 1 }.data();
+        data.truncate(12);
+        let decoded = decode(&packaged(token_module::ID, &[first, second, first], data), &context);
+        assert_eq!(decoded.instruction, "unknown");
+
+        // Well-formed data but missing accounts
+        let data = governance_ix::Vote { proposal_id: 1, vote_type: VoteType::For }.data();
+        assert_eq!(decode(&packaged(governance_module::ID, &[first], data), &context).instruction, "unknown");
+
+        // Empty data and unregistered programs
+        assert_eq!(decode(&packaged(system_program::ID, &[], vec![]), &context).instruction, "unknown");
+        let program = Pubkey::new_unique();
+        let decoded = decode(&packaged(program, &[], vec![1]), &context);
+        assert_eq!((decoded.program, decoded.instruction), (program.to_string(), "unknown".to_string()));
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::Pubkey;
+
+pub type FetchError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("could not decode account {address}: {source}")]
+    Decode {
+        address: Pubkey,
+        source: anchor_lang::error::Error,
+    },
+    #[error("invalid address lookup table {0}")]
+    InvalidLookupTable(Pubkey),
+    #[error("lookup table {table} has no address at index {index}")]
+    LookupIndexOutOfBounds { table: Pubkey, index: u8 },
+    #[error("transaction does not carry a vault message")]
+    NotVaultTransaction,
+    #[error("could not decode vault message: {0}")]
+    InvalidVaultMessage(std::io::Error),
+    #[error("unsupported proposal package version {0}")]
+    UnsupportedPackageVersion(u8),
+    #[error("invalid proposal package: {0}")]
+    InvalidPackage(&'static str),
+    #[error("proposal package hash does not match")]
+    HashMismatch,
+    #[error("vault message would exceed the account limit")]
+    TooManyVaultAccounts,
+    #[error("only the vault may sign vault instructions, not {0}")]
+    UnsupportedSigner(Pubkey),
+    #[error("{0} is not a token program")]
+    InvalidTokenProgram(Pubkey),
+    #[error("fetch failed: {0}")]
+    Fetch(#[from] FetchError),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
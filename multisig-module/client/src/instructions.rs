@@ -0,0 +1,894 @@
+//! One builder per `multisig_module` instruction. PDAs are derived from the
+//! multisig address; transaction ids and nonces come from the caller, usually
+//! from a freshly fetched `Multisig` (`transaction_count` and `nonce`).
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::{AccountMeta, Instruction},
+    system_program, InstructionData, ToAccountMetas,
+};
+use solana_address_lookup_table_interface::program as address_lookup_table;
+
+use multisig_module::{
+    accounts, instruction, ConfigAction, DelegateScope, ExecutionSchedule, LookupTableAction,
+    ProposalExpiry, ProposalMetadata, TransactionType, VaultMessage, ID,
+};
+
+use crate::{audit_log_address, delegation_address, lookup_table_address, multisig_address, transaction_address, vault_address};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn audit_log(multisig: &Pubkey) -> Pubkey {
+    audit_log_address(multisig).0
+}
+
+fn transaction(multisig: &Pubkey, transaction_id: u64) -> Pubkey {
+    transaction_address(multisig, transaction_id).0
+}
+
+fn vault(multisig: &Pubkey) -> Pubkey {
+    vault_address(multisig).0
+}
+
+// Multisig management
+
+pub fn create_multisig(
+    creator: Pubkey,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+    admin_threshold: Option<u8>,
+) -> Instruction {
+    let multisig = multisig_address(&creator).0;
+    build(
+        accounts::CreateMultisig {
+            creator,
+            multisig,
+            audit_log: audit_log(&multisig),
+            system_program: system_program::ID,
+        },
+        instruction::CreateMultisig { owners, threshold, admin_threshold },
+    )
+}
+
+pub fn emergency_pause(multisig: Pubkey, caller: Pubkey, scope: Option<Vec<TransactionType>>) -> Instruction {
+    build(
+        accounts::EmergencyAction {
+            caller,
+            multisig,
+            audit_log: audit_log(&multisig),
+        },
+        instruction::EmergencyPause { scope },
+    )
+}
+
+pub fn initialize_audit_log(multisig: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAuditLog {
+            payer,
+            multisig,
+            audit_log: audit_log(&multisig),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAuditLog {},
+    )
+}
+
+/// Brings a multisig created by the baseline program to the current layout;
+/// run it before `initialize_audit_log` on such a multisig
+pub fn migrate_multisig(multisig: Pubkey, payer: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateMultisig { multisig, payer, system_program: system_program::ID },
+        instruction::MigrateMultisig {},
+    )
+}
+
+pub fn migrate_transaction(multisig: Pubkey, transaction_id: u64, payer: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateTransaction {
+            transaction: transaction(&multisig, transaction_id),
+            payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateTransaction {},
+    )
+}
+
+// Proposals
+
+fn propose_accounts(multisig: Pubkey, proposer: Pubkey, transaction_id: u64) -> accounts::ProposeTransaction {
+    accounts::ProposeTransaction {
+        proposer,
+        multisig,
+        audit_log: audit_log(&multisig),
+        transaction: transaction(&multisig, transaction_id),
+        system_program: system_program::ID,
+    }
+}
+
+/// Fields shared by every propose instruction
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub proposer: Pubkey,
+    /// The multisig's current `transaction_count`
+    pub transaction_id: u64,
+    /// The multisig's current `nonce`
+    pub nonce: u64,
+    pub expiry: Option<ProposalExpiry>,
+    pub metadata: Option<ProposalMetadata>,
+}
+
+pub fn propose_transaction(
+    proposal: Proposal,
+    instruction_data: Vec<u8>,
+    transaction_type: TransactionType,
+) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeTransaction {
+            instruction_data,
+            nonce: proposal.nonce,
+            transaction_type,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+pub fn propose_scheduled_transaction(
+    proposal: Proposal,
+    instruction_data: Vec<u8>,
+    transaction_type: TransactionType,
+    schedule: ExecutionSchedule,
+) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeScheduledTransaction {
+            instruction_data,
+            nonce: proposal.nonce,
+            transaction_type,
+            expiry: proposal.expiry,
+            schedule,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+/// Passes the message's lookup tables so the program can pin the keys they
+/// resolve to.
+pub fn propose_vault_transaction(
+    proposal: Proposal,
+    message: VaultMessage,
+    transaction_type: TransactionType,
+) -> Instruction {
+    let tables: Vec<Pubkey> = message.lookups.iter().map(|lookup| lookup.table).collect();
+    let mut ix = build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeVaultTransaction {
+            message,
+            nonce: proposal.nonce,
+            transaction_type,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    );
+    ix.accounts.extend(tables.into_iter().map(|table| AccountMeta::new_readonly(table, false)));
+    ix
+}
+
+/// Like [`propose_vault_transaction`], but the message runs once per due
+/// time of `schedule`.
+pub fn propose_scheduled_vault_transaction(
+    proposal: Proposal,
+    message: VaultMessage,
+    transaction_type: TransactionType,
+    schedule: ExecutionSchedule,
+) -> Instruction {
+    let tables: Vec<Pubkey> = message.lookups.iter().map(|lookup| lookup.table).collect();
+    let mut ix = build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeScheduledVaultTransaction {
+            message,
+            nonce: proposal.nonce,
+            transaction_type,
+            expiry: proposal.expiry,
+            schedule,
+            metadata: proposal.metadata,
+        },
+    );
+    ix.accounts.extend(tables.into_iter().map(|table| AccountMeta::new_readonly(table, false)));
+    ix
+}
+
+pub fn propose_config_transaction(proposal: Proposal, actions: Vec<ConfigAction>) -> Instruction {
+    build(
+        accounts::ProposeConfigTransaction {
+            proposer: proposal.proposer,
+            multisig: proposal.multisig,
+            audit_log: audit_log(&proposal.multisig),
+            transaction: transaction(&proposal.multisig, proposal.transaction_id),
+            system_program: system_program::ID,
+        },
+        instruction::ProposeConfigTransaction {
+            actions,
+            nonce: proposal.nonce,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+pub fn propose_close_multisig(proposal: Proposal, destination: Pubkey) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeCloseMultisig {
+            destination,
+            nonce: proposal.nonce,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+pub fn propose_swap_owner(proposal: Proposal, old_owner: Pubkey, new_owner: Pubkey) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeSwapOwner {
+            old_owner,
+            new_owner,
+            nonce: proposal.nonce,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+pub fn propose_extend_expiry(proposal: Proposal, target_transaction_id: u64, new_expiry: ProposalExpiry) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeExtendExpiry {
+            target_transaction_id,
+            new_expiry,
+            nonce: proposal.nonce,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+// Approval and execution
+
+/// Pass `delegated_by` when `approver` is a delegate approving for that owner
+pub fn approve_transaction(
+    multisig: Pubkey,
+    approver: Pubkey,
+    transaction_id: u64,
+    delegated_by: Option<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::ApproveTransaction {
+            approver,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            delegation: delegated_by.map(|owner| delegation_address(&multisig, &owner).0),
+        },
+        instruction::ApproveTransaction { transaction_id },
+    )
+}
+
+pub fn reject_transaction(multisig: Pubkey, owner: Pubkey, transaction_id: u64) -> Instruction {
+    build(
+        accounts::RejectTransaction {
+            owner,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::RejectTransaction { transaction_id },
+    )
+}
+
+/// `proposer` is the transaction's `proposer`; the deposit goes there or to
+/// the vault depending on how the proposal ended
+pub fn release_proposal_deposit(multisig: Pubkey, transaction_id: u64, proposer: Pubkey) -> Instruction {
+    build(
+        accounts::ReleaseProposalDeposit {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            proposer,
+            vault: vault(&multisig),
+        },
+        instruction::ReleaseProposalDeposit { transaction_id },
+    )
+}
+
+pub fn execute_transaction(multisig: Pubkey, executor: Pubkey, transaction_id: u64) -> Instruction {
+    build(
+        accounts::ExecuteTransaction {
+            executor,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::ExecuteTransaction { transaction_id },
+    )
+}
+
+pub fn execute_scheduled_transaction(multisig: Pubkey, cranker: Pubkey, transaction_id: u64) -> Instruction {
+    build(
+        accounts::ExecuteScheduledTransaction {
+            cranker,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::ExecuteScheduledTransaction { transaction_id },
+    )
+}
+
+/// `keys` is the message's resolved key list, see
+/// [`resolve_vault_message_keys`](crate::resolve_vault_message_keys).
+pub fn execute_vault_transaction(
+    multisig: Pubkey,
+    executor: Pubkey,
+    transaction_id: u64,
+    message: &VaultMessage,
+    keys: &[Pubkey],
+) -> Instruction {
+    let vault = vault(&multisig);
+    let mut ix = build(
+        accounts::ExecuteVaultTransaction {
+            executor,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            vault,
+        },
+        instruction::ExecuteVaultTransaction { transaction_id },
+    );
+
+    ix.accounts.extend(
+        message
+            .lookups
+            .iter()
+            .map(|lookup| AccountMeta::new_readonly(lookup.table, false)),
+    );
+    ix.accounts.extend(keys.iter().enumerate().map(|(index, key)| {
+        let is_writable = message.instructions.iter().any(|instruction| {
+            instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.index as usize == index && meta.is_writable)
+        });
+        AccountMeta { pubkey: *key, is_signer: false, is_writable }
+    }));
+    ix
+}
+
+pub fn execute_config_transaction(multisig: Pubkey, executor: Pubkey, transaction_id: u64) -> Instruction {
+    build(
+        accounts::ExecuteConfigTransaction {
+            executor,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::ExecuteConfigTransaction { transaction_id },
+    )
+}
+
+/// A vault token account to empty and close when the multisig is closed
+#[derive(Clone, Debug)]
+pub struct TokenSweep {
+    pub vault_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// A Transaction or Delegation account to close along with the multisig
+#[derive(Clone, Debug)]
+pub struct PendingClose {
+    pub account: Pubkey,
+    /// Proposer of a pending Transaction whose deposit is still refundable,
+    /// i.e. `deposit > 0` and not rejected
+    pub refund_to: Option<Pubkey>,
+}
+
+/// `proposer` is the proposer of the close proposal, refunded its deposit.
+/// `pending` must list every other transaction below the multisig's
+/// `transaction_count` in id order, then any delegations to close.
+pub fn close_multisig(
+    multisig: Pubkey,
+    executor: Pubkey,
+    transaction_id: u64,
+    destination: Pubkey,
+    proposer: Pubkey,
+    token_sweeps: &[TokenSweep],
+    pending: &[PendingClose],
+) -> Instruction {
+    let mut ix = build(
+        accounts::CloseMultisig {
+            executor,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            vault: vault(&multisig),
+            destination,
+            proposer,
+            system_program: system_program::ID,
+        },
+        instruction::CloseMultisig {
+            transaction_id,
+            token_account_count: token_sweeps.len() as u8,
+        },
+    );
+
+    for sweep in token_sweeps {
+        ix.accounts.extend([
+            AccountMeta::new(sweep.vault_token_account, false),
+            AccountMeta::new_readonly(sweep.mint, false),
+            AccountMeta::new(sweep.destination_token_account, false),
+            AccountMeta::new_readonly(sweep.token_program, false),
+        ]);
+    }
+    for close in pending {
+        ix.accounts.push(AccountMeta::new(close.account, false));
+        if let Some(refund_to) = close.refund_to {
+            ix.accounts.push(AccountMeta::new(refund_to, false));
+        }
+    }
+    ix
+}
+
+// Delegates
+
+pub fn register_delegate(
+    multisig: Pubkey,
+    owner: Pubkey,
+    delegate: Pubkey,
+    expires_at: i64,
+    scope: DelegateScope,
+) -> Instruction {
+    build(
+        accounts::RegisterDelegate {
+            owner,
+            multisig,
+            audit_log: audit_log(&multisig),
+            delegation: delegation_address(&multisig, &owner).0,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterDelegate { delegate, expires_at, scope },
+    )
+}
+
+pub fn revoke_delegate(multisig: Pubkey, owner: Pubkey) -> Instruction {
+    build(
+        accounts::RevokeDelegate {
+            owner,
+            multisig,
+            audit_log: audit_log(&multisig),
+            delegation: delegation_address(&multisig, &owner).0,
+        },
+        instruction::RevokeDelegate {},
+    )
+}
+
+// Address lookup tables
+
+pub fn create_lookup_table(multisig: Pubkey, payer: Pubkey, recent_slot: u64) -> Instruction {
+    build(
+        accounts::CreateLookupTable {
+            payer,
+            multisig,
+            audit_log: audit_log(&multisig),
+            vault: vault(&multisig),
+            lookup_table: lookup_table_address(&multisig, recent_slot).0,
+            address_lookup_table_program: address_lookup_table::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateLookupTable { recent_slot },
+    )
+}
+
+pub fn propose_lookup_table_transaction(
+    proposal: Proposal,
+    lookup_table: Pubkey,
+    action: LookupTableAction,
+) -> Instruction {
+    build(
+        propose_accounts(proposal.multisig, proposal.proposer, proposal.transaction_id),
+        instruction::ProposeLookupTableTransaction {
+            lookup_table,
+            action,
+            nonce: proposal.nonce,
+            expiry: proposal.expiry,
+            metadata: proposal.metadata,
+        },
+    )
+}
+
+pub fn extend_lookup_table(
+    multisig: Pubkey,
+    payer: Pubkey,
+    transaction_id: u64,
+    lookup_table: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExtendLookupTable {
+            payer,
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            vault: vault(&multisig),
+            lookup_table,
+            address_lookup_table_program: address_lookup_table::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ExtendLookupTable { transaction_id },
+    )
+}
+
+pub fn deactivate_lookup_table(multisig: Pubkey, transaction_id: u64, lookup_table: Pubkey) -> Instruction {
+    build(
+        accounts::DeactivateLookupTable {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            vault: vault(&multisig),
+            lookup_table,
+            address_lookup_table_program: address_lookup_table::ID,
+        },
+        instruction::DeactivateLookupTable { transaction_id },
+    )
+}
+
+pub fn close_lookup_table(multisig: Pubkey, transaction_id: u64, lookup_table: Pubkey) -> Instruction {
+    build(
+        accounts::CloseLookupTable {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            vault: vault(&multisig),
+            lookup_table,
+            address_lookup_table_program: address_lookup_table::ID,
+        },
+        instruction::CloseLookupTable { transaction_id },
+    )
+}
+
+// Admin actions, each authorized by an approved transaction
+
+pub fn change_threshold(multisig: Pubkey, transaction_id: u64, new_threshold: u8) -> Instruction {
+    build(
+        accounts::ChangeThreshold {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::ChangeThreshold { transaction_id, new_threshold },
+    )
+}
+
+pub fn add_owner(multisig: Pubkey, transaction_id: u64, new_owner: Pubkey) -> Instruction {
+    build(
+        accounts::AddOwner {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::AddOwner { transaction_id, new_owner },
+    )
+}
+
+pub fn remove_owner(multisig: Pubkey, transaction_id: u64, owner_to_remove: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveOwner {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::RemoveOwner { transaction_id, owner_to_remove },
+    )
+}
+
+pub fn swap_owner(multisig: Pubkey, transaction_id: u64, old_owner: Pubkey, new_owner: Pubkey) -> Instruction {
+    build(
+        accounts::SwapOwner {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::SwapOwner { transaction_id, old_owner, new_owner },
+    )
+}
+
+pub fn extend_expiry(
+    multisig: Pubkey,
+    transaction_id: u64,
+    target_transaction_id: u64,
+    new_expiry: ProposalExpiry,
+) -> Instruction {
+    build(
+        accounts::ExtendExpiry {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+            target_transaction: transaction(&multisig, target_transaction_id),
+        },
+        instruction::ExtendExpiry { transaction_id, target_transaction_id, new_expiry },
+    )
+}
+
+pub fn unpause(multisig: Pubkey, transaction_id: u64, scope: Option<Vec<TransactionType>>) -> Instruction {
+    build(
+        accounts::UnpauseMultisig {
+            multisig,
+            audit_log: audit_log(&multisig),
+            transaction: transaction(&multisig, transaction_id),
+        },
+        instruction::Unpause { transaction_id, scope },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+    use multisig_module::{VaultAccountMeta, VaultInstruction, VaultLookup};
+
+    use super::*;
+
+    /// PDAs derived from the program's literal seeds, independent of `crate::pda`
+    fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &ID).0
+    }
+
+    fn transaction_pda(multisig: &Pubkey, transaction_id: u64) -> Pubkey {
+        pda(&[b"transaction", multisig.as_ref(), &transaction_id.to_le_bytes()])
+    }
+
+    fn assert_builds(ix: &Instruction, expected: impl ToAccountMetas, discriminator: &[u8]) {
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(ix.accounts, expected.to_account_metas(None));
+        assert!(ix.data.starts_with(discriminator));
+    }
+
+    fn proposal(multisig: Pubkey, proposer: Pubkey) -> Proposal {
+        Proposal {
+            multisig,
+            proposer,
+            transaction_id: 4,
+            nonce: 9,
+            expiry: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn create_multisig_derives_the_program_pdas() {
+        let creator = Pubkey::new_unique();
+        let multisig = pda(&[b"multisig", creator.as_ref()]);
+        let ix = create_multisig(creator, vec![creator], 1, None);
+        assert_builds(
+            &ix,
+            accounts::CreateMultisig {
+                creator,
+                multisig,
+                audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+                system_program: system_program::ID,
+            },
+            instruction::CreateMultisig::DISCRIMINATOR,
+        );
+        assert!(ix.accounts[0].is_signer && ix.accounts[1].is_writable);
+    }
+
+    #[test]
+    fn approve_transaction_passes_the_delegation_only_when_delegated() {
+        let multisig = Pubkey::new_unique();
+        let approver = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let expected = |delegation| accounts::ApproveTransaction {
+            approver,
+            multisig,
+            audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+            transaction: transaction_pda(&multisig, 2),
+            delegation,
+        };
+
+        let undelegated = approve_transaction(multisig, approver, 2, None);
+        assert_builds(
+            &undelegated,
+            expected(None),
+            instruction::ApproveTransaction::DISCRIMINATOR,
+        );
+        let delegated = approve_transaction(multisig, approver, 2, Some(owner));
+        assert_builds(
+            &delegated,
+            expected(Some(pda(&[b"delegation", multisig.as_ref(), owner.as_ref()]))),
+            instruction::ApproveTransaction::DISCRIMINATOR,
+        );
+        // Anchor marks an omitted optional account with the program id
+        assert_eq!(undelegated.accounts[4].pubkey, ID);
+    }
+
+    #[test]
+    fn propose_vault_transaction_appends_the_lookup_tables() {
+        let multisig = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let tables = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let message = VaultMessage {
+            account_keys: vec![Pubkey::new_unique()],
+            lookups: tables
+                .iter()
+                .map(|table| VaultLookup { table: *table, writable_indexes: vec![0], readonly_indexes: vec![] })
+                .collect(),
+            instructions: vec![],
+        };
+
+        let ix = propose_vault_transaction(proposal(multisig, proposer), message, TransactionType::Custom);
+        let mut expected = accounts::ProposeTransaction {
+            proposer,
+            multisig,
+            audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+            transaction: transaction_pda(&multisig, 4),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        expected.extend(tables.iter().map(|table| AccountMeta::new_readonly(*table, false)));
+        assert_eq!(ix.accounts, expected);
+        assert!(ix.data.starts_with(instruction::ProposeVaultTransaction::DISCRIMINATOR));
+    }
+
+    #[test]
+    fn execute_vault_transaction_appends_tables_then_keys() {
+        let multisig = Pubkey::new_unique();
+        let executor = Pubkey::new_unique();
+        let vault = pda(&[b"vault", multisig.as_ref()]);
+        let (program, recipient, table, loaded) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let message = VaultMessage {
+            account_keys: vec![program, vault, recipient],
+            lookups: vec![VaultLookup { table, writable_indexes: vec![], readonly_indexes: vec![0] }],
+            instructions: vec![VaultInstruction {
+                program_id_index: 0,
+                accounts: vec![
+                    VaultAccountMeta { index: 1, is_signer: true, is_writable: true },
+                    VaultAccountMeta { index: 2, is_signer: false, is_writable: true },
+                    VaultAccountMeta { index: 3, is_signer: false, is_writable: false },
+                ],
+                data: vec![1, 2, 3],
+            }],
+        };
+        let keys = [program, vault, recipient, loaded];
+
+        let ix = execute_vault_transaction(multisig, executor, 6, &message, &keys);
+        let mut expected = accounts::ExecuteVaultTransaction {
+            executor,
+            multisig,
+            audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+            transaction: transaction_pda(&multisig, 6),
+            vault,
+        }
+        .to_account_metas(None);
+        expected.extend([
+            AccountMeta::new_readonly(table, false),
+            AccountMeta::new_readonly(program, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(recipient, false),
+            AccountMeta::new_readonly(loaded, false),
+        ]);
+        assert_eq!(ix.accounts, expected);
+        assert!(ix.data.starts_with(instruction::ExecuteVaultTransaction::DISCRIMINATOR));
+    }
+
+    #[test]
+    fn close_multisig_appends_sweeps_then_pending_accounts() {
+        let multisig = Pubkey::new_unique();
+        let (executor, destination, proposer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let sweep = TokenSweep {
+            vault_token_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            destination_token_account: Pubkey::new_unique(),
+            token_program: anchor_spl::token::ID,
+        };
+        let refunded = PendingClose { account: transaction_pda(&multisig, 1), refund_to: Some(Pubkey::new_unique()) };
+        let closed = PendingClose { account: transaction_pda(&multisig, 2), refund_to: None };
+        let delegation = PendingClose { account: Pubkey::new_unique(), refund_to: None };
+
+        let ix = close_multisig(
+            multisig,
+            executor,
+            3,
+            destination,
+            proposer,
+            std::slice::from_ref(&sweep),
+            &[refunded.clone(), closed.clone(), delegation.clone()],
+        );
+        let mut expected = accounts::CloseMultisig {
+            executor,
+            multisig,
+            audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+            transaction: transaction_pda(&multisig, 3),
+            vault: pda(&[b"vault", multisig.as_ref()]),
+            destination,
+            proposer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        expected.extend([
+            AccountMeta::new(sweep.vault_token_account, false),
+            AccountMeta::new_readonly(sweep.mint, false),
+            AccountMeta::new(sweep.destination_token_account, false),
+            AccountMeta::new_readonly(sweep.token_program, false),
+            AccountMeta::new(refunded.account, false),
+            AccountMeta::new(refunded.refund_to.unwrap(), false),
+            AccountMeta::new(closed.account, false),
+            AccountMeta::new(delegation.account, false),
+        ]);
+        assert_eq!(ix.accounts, expected);
+        assert_eq!(
+            ix.data,
+            instruction::CloseMultisig { transaction_id: 3, token_account_count: 1 }.data()
+        );
+    }
+
+    #[test]
+    fn lookup_table_builders_use_the_vault_table() {
+        let multisig = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let vault = pda(&[b"vault", multisig.as_ref()]);
+        let lookup_table = lookup_table_address(&multisig, 77).0;
+
+        assert_builds(
+            &create_lookup_table(multisig, payer, 77),
+            accounts::CreateLookupTable {
+                payer,
+                multisig,
+                audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+                vault,
+                lookup_table,
+                address_lookup_table_program: address_lookup_table::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreateLookupTable::DISCRIMINATOR,
+        );
+        assert_builds(
+            &extend_lookup_table(multisig, payer, 5, lookup_table),
+            accounts::ExtendLookupTable {
+                payer,
+                multisig,
+                audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+                transaction: transaction_pda(&multisig, 5),
+                vault,
+                lookup_table,
+                address_lookup_table_program: address_lookup_table::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ExtendLookupTable::DISCRIMINATOR,
+        );
+    }
+
+    #[test]
+    fn extend_expiry_passes_both_transactions() {
+        let multisig = Pubkey::new_unique();
+        let ix = extend_expiry(multisig, 8, 2, ProposalExpiry::Never);
+        assert_builds(
+            &ix,
+            accounts::ExtendExpiry {
+                multisig,
+                audit_log: pda(&[b"audit_log", multisig.as_ref()]),
+                transaction: transaction_pda(&multisig, 8),
+                target_transaction: transaction_pda(&multisig, 2),
+            },
+            instruction::ExtendExpiry::DISCRIMINATOR,
+        );
+    }
+}
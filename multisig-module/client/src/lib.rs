@@ -0,0 +1,32 @@
+//! Client helpers for the `multisig_module` program: PDA derivation,
+//! instruction builders, vault message compilation, account decoding,
+//! execution summaries, portable proposal packages and readable instruction
+//! descriptions.
+//!
+//! The crate does not depend on an RPC client. Fetching goes through the
+//! [`AccountFetcher`] trait so services can plug in whichever client they
+//! already use.
+
+pub mod accounts;
+pub mod decode;
+pub mod error;
+pub mod instructions;
+pub mod message;
+pub mod package;
+pub mod pda;
+pub mod summary;
+
+pub use accounts::*;
+pub use decode::*;
+pub use error::*;
+pub use message::*;
+pub use package::*;
+pub use pda::*;
+pub use summary::*;
+
+pub use multisig_module::{
+    self, ID as PROGRAM_ID,
+    AuditLog, Delegation, Multisig, Transaction,
+    ConfigAction, DelegateScope, ExecutionSchedule, LookupTableAction, ProposalExpiry, ProposalMetadata,
+    TransactionType, VaultAccountMeta, VaultInstruction, VaultLookup, VaultMessage,
+};
@@ -0,0 +1,159 @@
+//! Compiling ordinary instructions into vault messages, plus ready-made
+//! messages for mints whose `token_module` admin is the multisig vault.
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::instruction::Instruction,
+    InstructionData, ToAccountMetas,
+};
+
+use multisig_module::{VaultAccountMeta, VaultInstruction, VaultMessage, MAX_VAULT_ACCOUNTS};
+use token_module::{accounts as token_accounts, instruction as token_ix};
+
+use crate::{vault_address, ClientError, Result};
+
+/// Builds a vault message without lookup tables. Keys are listed in order of
+/// first use, and the vault is the only account allowed to sign.
+pub fn compile_vault_message(multisig: &Pubkey, instructions: &[Instruction]) -> Result<VaultMessage> {
+    let vault = vault_address(multisig).0;
+    let mut account_keys: Vec<Pubkey> = Vec::new();
+    let mut index_of = |key: Pubkey| -> Result<u8> {
+        let index = match account_keys.iter().position(|existing| *existing == key) {
+            Some(index) => index,
+            None => {
+                account_keys.push(key);
+                account_keys.len() - 1
+            }
+        };
+        match index < MAX_VAULT_ACCOUNTS {
+            true => Ok(index as u8),
+            false => Err(ClientError::TooManyVaultAccounts),
+        }
+    };
+
+    let instructions = instructions
+        .iter()
+        .map(|instruction| {
+            let program_id_index = index_of(instruction.program_id)?;
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    if meta.is_signer && meta.pubkey != vault {
+                        return Err(ClientError::UnsupportedSigner(meta.pubkey));
+                    }
+                    Ok(VaultAccountMeta {
+                        index: index_of(meta.pubkey)?,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Ok(VaultInstruction {
+                program_id_index,
+                accounts,
+                data: instruction.data.clone(),
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(VaultMessage {
+        account_keys,
+        lookups: Vec::new(),
+        instructions,
+    })
+}
+
+fn token_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_metadata", mint.as_ref()], &token_module::ID).0
+}
+
+fn mint_authority(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"mint_authority", mint.as_ref()], &token_module::ID).0
+}
+
+fn token_module_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: token_module::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `token_program` is the mint's owner, SPL Token or Token-2022
+fn check_token_program(token_program: &Pubkey) -> Result<()> {
+    match *token_program == anchor_spl::token::ID || *token_program == anchor_spl::token_2022::ID {
+        true => Ok(()),
+        false => Err(ClientError::InvalidTokenProgram(*token_program)),
+    }
+}
+
+/// `token_module::mint_tokens` signed by the vault as the mint's admin
+pub fn mint_tokens_message(
+    multisig: &Pubkey,
+    mint: Pubkey,
+    destination: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+) -> Result<VaultMessage> {
+    check_token_program(&token_program)?;
+    let instruction = token_module_instruction(
+        token_accounts::MintTokens {
+            admin: vault_address(multisig).0,
+            mint,
+            destination,
+            token_metadata: token_metadata(&mint),
+            mint_authority: mint_authority(&mint),
+            token_program,
+            role: None,
+        },
+        token_ix::MintTokens { amount },
+    );
+    compile_vault_message(multisig, &[instruction])
+}
+
+/// `token_module::freeze_token_account` signed by the vault as the mint's admin
+pub fn freeze_token_account_message(
+    multisig: &Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    token_program: Pubkey,
+) -> Result<VaultMessage> {
+    check_token_program(&token_program)?;
+    let instruction = token_module_instruction(
+        token_accounts::FreezeTokenAccount {
+            admin: vault_address(multisig).0,
+            mint,
+            token_account,
+            token_metadata: token_metadata(&mint),
+            mint_authority: mint_authority(&mint),
+            token_program,
+            role: None,
+        },
+        token_ix::FreezeTokenAccount {},
+    );
+    compile_vault_message(multisig, &[instruction])
+}
+
+/// `token_module::thaw_token_account` signed by the vault as the mint's admin
+pub fn thaw_token_account_message(
+    multisig: &Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    token_program: Pubkey,
+) -> Result<VaultMessage> {
+    check_token_program(&token_program)?;
+    let instruction = token_module_instruction(
+        token_accounts::ThawTokenAccount {
+            admin: vault_address(multisig).0,
+            mint,
+            token_account,
+            token_metadata: token_metadata(&mint),
+            mint_authority: mint_authority(&mint),
+            token_program,
+            role: None,
+        },
+        token_ix::ThawTokenAccount {},
+    );
+    compile_vault_message(multisig, &[instruction])
+}
@@ -0,0 +1,455 @@
+//! Portable proposal packages for reviewing proposals without RPC access.
+//!
+//! A package is borsh encoded. It travels as base64 or as a JSON envelope
+//! that wraps the base64 payload next to a few readable fields. The hash
+//! covers everything an approver signs off on. It leaves out `expires_at`,
+//! which `extend_expiry` can change, and the approvals.
+//!
+//! Packages carry no approvals or signatures. Owners approve on chain with
+//! `approve_transaction`, so collecting a partially signed proposal offline
+//! is out of scope; the approvals so far are read from the `Transaction`.
+
+use std::collections::HashMap;
+
+use anchor_lang::{
+    prelude::{borsh, Pubkey},
+    solana_program::hash::{hash, Hash},
+    AnchorDeserialize, AnchorSerialize,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+
+use multisig_module::{ExecutionSchedule, ProposalMetadata, Transaction, TransactionType, VaultMessage};
+
+use crate::{
+    decode_vault_message, fetch_lookup_tables, fetch_transaction, resolve_vault_message_keys,
+    AccountFetcher, ClientError, Result,
+};
+
+pub const PROPOSAL_PACKAGE_VERSION: u8 = 1;
+pub const PROPOSAL_PACKAGE_FORMAT: &str = "multisig-proposal-package";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PackagedAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// A vault message instruction with every key resolved
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PackagedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<PackagedAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProposalPackage {
+    pub version: u8,
+    pub multisig: Pubkey,
+    pub transaction_id: u64,
+    pub proposer: Pubkey,
+    pub transaction_type: TransactionType,
+    pub created_at: i64,
+    /// Informational only, not covered by `hash`
+    pub expires_at: Option<i64>,
+    pub schedule: Option<ExecutionSchedule>,
+    pub metadata: ProposalMetadata,
+    pub vault_message: bool,
+    pub instruction_data: Vec<u8>,
+    /// Decoded vault message instructions; empty for other payloads
+    pub instructions: Vec<PackagedInstruction>,
+    pub hash: [u8; 32],
+}
+
+/// The hashed part of a package, in hashing order
+#[derive(AnchorSerialize)]
+struct HashedFields<'a> {
+    version: u8,
+    multisig: &'a Pubkey,
+    transaction_id: u64,
+    proposer: &'a Pubkey,
+    transaction_type: &'a TransactionType,
+    created_at: i64,
+    schedule: &'a Option<ExecutionSchedule>,
+    metadata: &'a ProposalMetadata,
+    vault_message: bool,
+    instruction_data: &'a [u8],
+    instructions: &'a [PackagedInstruction],
+}
+
+/// JSON form of a package. The readable fields are checked against the
+/// payload when the envelope is opened.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProposalPackageEnvelope {
+    pub format: String,
+    pub version: u8,
+    pub multisig: String,
+    pub transaction_id: u64,
+    pub hash: String,
+    pub package: String,
+}
+
+/// Expands a vault message into full instructions using the resolved keys
+pub fn decode_vault_instructions(message: &VaultMessage, keys: &[Pubkey]) -> Result<Vec<PackagedInstruction>> {
+    let key = |index: u8| {
+        keys.get(index as usize)
+            .copied()
+            .ok_or(ClientError::InvalidPackage("vault message index out of bounds"))
+    };
+
+    message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            Ok(PackagedInstruction {
+                program_id: key(instruction.program_id_index)?,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| {
+                        Ok(PackagedAccountMeta {
+                            pubkey: key(meta.index)?,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+                data: instruction.data.clone(),
+            })
+        })
+        .collect()
+}
+
+fn decode_instructions(transaction: &Transaction, lookup_tables: &HashMap<Pubkey, Vec<Pubkey>>) -> Result<Vec<PackagedInstruction>> {
+    if !transaction.vault_message {
+        return Ok(Vec::new());
+    }
+    let message = decode_vault_message(transaction)?;
+    let keys = resolve_vault_message_keys(&message, lookup_tables)?;
+    decode_vault_instructions(&message, &keys)
+}
+
+impl ProposalPackage {
+    /// Builds a package from on-chain data. `lookup_tables` must hold the
+    /// addresses of every table a vault message references.
+    pub fn new(
+        multisig: Pubkey,
+        transaction: &Transaction,
+        lookup_tables: &HashMap<Pubkey, Vec<Pubkey>>,
+    ) -> Result<Self> {
+        let mut package = Self {
+            version: PROPOSAL_PACKAGE_VERSION,
+            multisig,
+            transaction_id: transaction.transaction_id,
+            proposer: transaction.proposer,
+            transaction_type: transaction.transaction_type.clone(),
+            created_at: transaction.created_at,
+            expires_at: transaction.expiry(),
+            schedule: transaction.schedule.clone(),
+            metadata: transaction.metadata.clone(),
+            vault_message: transaction.vault_message,
+            instruction_data: transaction.instruction_data.clone(),
+            instructions: decode_instructions(transaction, lookup_tables)?,
+            hash: [0; 32],
+        };
+        package.hash = package.compute_hash();
+        Ok(package)
+    }
+
+    pub fn fetch(fetcher: &impl AccountFetcher, multisig: &Pubkey, transaction_id: u64) -> Result<Self> {
+        let transaction = fetch_transaction(fetcher, multisig, transaction_id)?;
+        let lookup_tables = match transaction.vault_message {
+            true => fetch_lookup_tables(fetcher, &decode_vault_message(&transaction)?)?,
+            false => HashMap::new(),
+        };
+        Self::new(*multisig, &transaction, &lookup_tables)
+    }
+
+    pub fn compute_hash(&self) -> [u8; 32] {
+        let fields = HashedFields {
+            version: self.version,
+            multisig: &self.multisig,
+            transaction_id: self.transaction_id,
+            proposer: &self.proposer,
+            transaction_type: &self.transaction_type,
+            created_at: self.created_at,
+            schedule: &self.schedule,
+            metadata: &self.metadata,
+            vault_message: self.vault_message,
+            instruction_data: &self.instruction_data,
+            instructions: &self.instructions,
+        };
+        hash(&fields.try_to_vec().expect("serializing into a Vec cannot fail")).to_bytes()
+    }
+
+    /// Checks internal consistency only: the version is supported, `hash`
+    /// matches the fields, and for vault messages the decoded instructions
+    /// agree with `instruction_data`, except for keys loaded from lookup
+    /// tables. Anyone can build a consistent package for a proposal that
+    /// does not exist, so passing this is no reason to approve.
+    pub fn verify_integrity(&self) -> Result<()> {
+        if self.version != PROPOSAL_PACKAGE_VERSION {
+            return Err(ClientError::UnsupportedPackageVersion(self.version));
+        }
+        if self.compute_hash() != self.hash {
+            return Err(ClientError::HashMismatch);
+        }
+        if !self.vault_message {
+            return match self.instructions.is_empty() {
+                true => Ok(()),
+                false => Err(ClientError::InvalidPackage("instructions without a vault message")),
+            };
+        }
+
+        let message = VaultMessage::try_from_slice(&self.instruction_data).map_err(ClientError::InvalidVaultMessage)?;
+        if message.instructions.len() != self.instructions.len() {
+            return Err(ClientError::InvalidPackage("instruction count differs from the vault message"));
+        }
+        for (stored, decoded) in message.instructions.iter().zip(&self.instructions) {
+            let static_key = |index: u8| message.account_keys.get(index as usize);
+            let matches = stored.data == decoded.data
+                && static_key(stored.program_id_index) == Some(&decoded.program_id)
+                && stored.accounts.len() == decoded.accounts.len()
+                && stored.accounts.iter().zip(&decoded.accounts).all(|(meta, account)| {
+                    meta.is_signer == account.is_signer
+                        && meta.is_writable == account.is_writable
+                        && static_key(meta.index).is_none_or(|key| *key == account.pubkey)
+                });
+            if !matches {
+                return Err(ClientError::InvalidPackage("decoded instructions differ from the vault message"));
+            }
+        }
+        Ok(())
+    }
+
+    /// The check to run before approving: on top of `verify_integrity`,
+    /// rebuilds the package from the on-chain transaction and requires the
+    /// same hash. `fetch_and_verify` does the same through a fetcher.
+    pub fn verify_against(
+        &self,
+        multisig: &Pubkey,
+        transaction: &Transaction,
+        lookup_tables: &HashMap<Pubkey, Vec<Pubkey>>,
+    ) -> Result<()> {
+        self.verify_integrity()?;
+        let onchain = Self::new(*multisig, transaction, lookup_tables)?;
+        if onchain.hash != self.hash {
+            return Err(ClientError::HashMismatch);
+        }
+        Ok(())
+    }
+
+    pub fn fetch_and_verify(&self, fetcher: &impl AccountFetcher) -> Result<()> {
+        let onchain = Self::fetch(fetcher, &self.multisig, self.transaction_id)?;
+        self.verify_integrity()?;
+        if onchain.hash != self.hash {
+            return Err(ClientError::HashMismatch);
+        }
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.try_to_vec().expect("serializing into a Vec cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let version = *bytes.first().ok_or(ClientError::InvalidPackage("empty package"))?;
+        if version != PROPOSAL_PACKAGE_VERSION {
+            return Err(ClientError::UnsupportedPackageVersion(version));
+        }
+        Self::try_from_slice(bytes).map_err(|_| ClientError::InvalidPackage("malformed package"))
+    }
+
+    pub fn to_base64(&self) -> String {
+        BASE64.encode(self.to_bytes())
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        let bytes = BASE64
+            .decode(encoded.trim())
+            .map_err(|_| ClientError::InvalidPackage("invalid base64"))?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn to_envelope(&self) -> ProposalPackageEnvelope {
+        ProposalPackageEnvelope {
+            format: PROPOSAL_PACKAGE_FORMAT.to_string(),
+            version: self.version,
+            multisig: self.multisig.to_string(),
+            transaction_id: self.transaction_id,
+            hash: Hash::new_from_array(self.hash).to_string(),
+            package: self.to_base64(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_envelope()).expect("envelope fields are plain strings and numbers")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let envelope: ProposalPackageEnvelope =
+            serde_json::from_str(json).map_err(|_| ClientError::InvalidPackage("invalid JSON envelope"))?;
+        envelope.open()
+    }
+}
+
+impl ProposalPackageEnvelope {
+    /// Decodes the payload and checks it against the readable fields
+    pub fn open(&self) -> Result<ProposalPackage> {
+        if self.format != PROPOSAL_PACKAGE_FORMAT {
+            return Err(ClientError::InvalidPackage("unknown envelope format"));
+        }
+        if self.version != PROPOSAL_PACKAGE_VERSION {
+            return Err(ClientError::UnsupportedPackageVersion(self.version));
+        }
+        let package = ProposalPackage::from_base64(&self.package)?;
+        if package.multisig.to_string() != self.multisig
+            || package.transaction_id != self.transaction_id
+            || Hash::new_from_array(package.hash).to_string() != self.hash
+        {
+            return Err(ClientError::InvalidPackage("envelope fields differ from the package"));
+        }
+        Ok(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{prelude::AccountMeta, solana_program::instruction::Instruction, AccountSerialize};
+    use multisig_module::NO_EXPIRY;
+
+    use super::*;
+    use crate::{compile_vault_message, vault_address};
+
+    fn vault_transaction(multisig: &Pubkey, lamports: u64) -> Transaction {
+        let vault = vault_address(multisig).0;
+        let instruction = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMeta::new(vault, true), AccountMeta::new(Pubkey::new_unique(), false)],
+            data: lamports.to_le_bytes().to_vec(),
+        };
+        let message = compile_vault_message(multisig, &[instruction]).unwrap();
+        Transaction {
+            transaction_id: 3,
+            created_at: 1_700_000_000,
+            expires_at: 1_700_086_400,
+            executed: false,
+            created_slot: 42,
+            multisig: *multisig,
+            proposer: Pubkey::new_unique(),
+            transaction_type: TransactionType::Custom,
+            approvals: vec![true, false, false],
+            instruction_data: message.try_to_vec().unwrap(),
+            schedule: None,
+            runs_executed: 0,
+            metadata: ProposalMetadata {
+                title: Some("Pay the auditor".to_string()),
+                ..Default::default()
+            },
+            vault_message: true,
+            resolved_keys_hash: [0; 32],
+            deposit: 0,
+            rejections: vec![false, false, false],
+            rejected: false,
+        }
+    }
+
+    fn package() -> (Pubkey, Transaction, ProposalPackage) {
+        let multisig = Pubkey::new_unique();
+        let transaction = vault_transaction(&multisig, 1_000);
+        let package = ProposalPackage::new(multisig, &transaction, &HashMap::new()).unwrap();
+        (multisig, transaction, package)
+    }
+
+    #[test]
+    fn round_trips_through_every_encoding() {
+        let (_, transaction, package) = package();
+        assert_eq!(package.instructions.len(), 1);
+        assert_eq!(package.instructions[0].data, 1_000u64.to_le_bytes());
+        assert_eq!(package.instructions[0].accounts[0].pubkey, vault_address(&package.multisig).0);
+
+        assert_eq!(ProposalPackage::from_bytes(&package.to_bytes()).unwrap(), package);
+        assert_eq!(ProposalPackage::from_base64(&package.to_base64()).unwrap(), package);
+        assert_eq!(ProposalPackage::from_json(&package.to_json()).unwrap(), package);
+        package.verify_integrity().unwrap();
+        package.verify_against(&package.multisig, &transaction, &HashMap::new()).unwrap();
+    }
+
+    #[test]
+    fn fetch_and_verify_reads_the_transaction_account() {
+        let (multisig, transaction, package) = package();
+        let mut data = Vec::new();
+        transaction.try_serialize(&mut data).unwrap();
+        let fetcher = HashMap::from([(crate::transaction_address(&multisig, 3).0, data)]);
+        package.fetch_and_verify(&fetcher).unwrap();
+    }
+
+    #[test]
+    fn expiry_and_approvals_are_not_hashed() {
+        let (multisig, mut transaction, package) = package();
+        transaction.expires_at = NO_EXPIRY;
+        transaction.approvals = vec![true, true, false];
+        package.verify_against(&multisig, &transaction, &HashMap::new()).unwrap();
+    }
+
+    #[test]
+    fn tampered_fields_fail_the_hash() {
+        let (_, _, package) = package();
+
+        let mut tampered = package.clone();
+        tampered.metadata.title = Some("Pay the attacker".to_string());
+        assert!(matches!(tampered.verify_integrity(), Err(ClientError::HashMismatch)));
+
+        let mut tampered = package.clone();
+        tampered.proposer = Pubkey::new_unique();
+        assert!(matches!(tampered.verify_integrity(), Err(ClientError::HashMismatch)));
+
+        let mut tampered = package;
+        tampered.version = PROPOSAL_PACKAGE_VERSION + 1;
+        assert!(matches!(tampered.verify_integrity(), Err(ClientError::UnsupportedPackageVersion(_))));
+        assert!(matches!(
+            ProposalPackage::from_bytes(&tampered.to_bytes()),
+            Err(ClientError::UnsupportedPackageVersion(_))
+        ));
+    }
+
+    #[test]
+    fn rehashed_instructions_must_match_the_vault_message() {
+        let (_, _, mut package) = package();
+        package.instructions[0].accounts[1].pubkey = Pubkey::new_unique();
+        package.hash = package.compute_hash();
+        assert!(matches!(package.verify_integrity(), Err(ClientError::InvalidPackage(_))));
+    }
+
+    #[test]
+    fn consistent_forgeries_only_fail_against_the_chain() {
+        let (multisig, transaction, _) = package();
+        let forged = ProposalPackage::new(multisig, &vault_transaction(&multisig, 1_000_000), &HashMap::new()).unwrap();
+        forged.verify_integrity().unwrap();
+        assert!(matches!(
+            forged.verify_against(&multisig, &transaction, &HashMap::new()),
+            Err(ClientError::HashMismatch)
+        ));
+    }
+
+    #[test]
+    fn envelope_fields_must_match_the_payload() {
+        let (_, _, package) = package();
+
+        let mut envelope = package.to_envelope();
+        envelope.transaction_id += 1;
+        assert!(matches!(envelope.open(), Err(ClientError::InvalidPackage(_))));
+
+        let mut envelope = package.to_envelope();
+        envelope.multisig = Pubkey::new_unique().to_string();
+        assert!(matches!(envelope.open(), Err(ClientError::InvalidPackage(_))));
+
+        let mut envelope = package.to_envelope();
+        envelope.format = "something-else".to_string();
+        assert!(matches!(envelope.open(), Err(ClientError::InvalidPackage(_))));
+
+        assert!(matches!(ProposalPackage::from_base64("not base64!"), Err(ClientError::InvalidPackage(_))));
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::Pubkey;
+use solana_address_lookup_table_interface::instruction::derive_lookup_table_address;
+
+use multisig_module::ID;
+
+pub fn multisig_address(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"multisig", creator.as_ref()], &ID)
+}
+
+pub fn transaction_address(multisig: &Pubkey, transaction_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"transaction", multisig.as_ref(), &transaction_id.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn audit_log_address(multisig: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"audit_log", multisig.as_ref()], &ID)
+}
+
+pub fn delegation_address(multisig: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"delegation", multisig.as_ref(), owner.as_ref()], &ID)
+}
+
+/// The PDA that signs vault transactions and holds the multisig's funds
+pub fn vault_address(multisig: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", multisig.as_ref()], &ID)
+}
+
+/// Lookup table created by `create_lookup_table` with the vault as authority
+pub fn lookup_table_address(multisig: &Pubkey, recent_slot: u64) -> (Pubkey, u8) {
+    let (vault, _) = vault_address(multisig);
+    derive_lookup_table_address(&vault, recent_slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn key(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    /// Addresses pinned for a fixed creator, so a changed seed or program id
+    /// shows up here before it reaches a deployed multisig
+    #[test]
+    fn addresses_match_known_seeds() {
+        let creator = Pubkey::new_from_array([1; 32]);
+        let (multisig, bump) = multisig_address(&creator);
+        assert_eq!((multisig, bump), (key("2qx8jw9XRDgs5gckbzKch3PBDkeBT7668da2uJeD5hnZ"), 254));
+        assert_eq!(transaction_address(&multisig, 7).0, key("BfFFbEtykqKXg5UHUDZcQrRvPoCqLxu22L5bBAzXiX1G"));
+        assert_eq!(audit_log_address(&multisig).0, key("A3ZFd64SFjceLDN76Zn2XTK4EUeyE7tsproLhMH6sLR9"));
+        assert_eq!(vault_address(&multisig).0, key("9QZSNN2YMYxvPhbBaFj9J8fTwvzo9RsV6Ju3e2WKSKNz"));
+        assert_eq!(delegation_address(&multisig, &creator).0, key("Db9MQQ8KruGWuZpNpNHoKSVACw6ywKL3VUkfZbHE7uwG"));
+        assert_eq!(lookup_table_address(&multisig, 1234).0, key("8EvxKZ6e2tLH4pYrzLsgqS4QptLMmG9mP6KpCvJB8isu"));
+    }
+
+    #[test]
+    fn addresses_use_the_program_seeds() {
+        let creator = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let (multisig, _) = multisig_address(&creator);
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &ID);
+
+        assert_eq!(multisig_address(&creator), pda(&[b"multisig", creator.as_ref()]));
+        assert_eq!(
+            transaction_address(&multisig, 513),
+            pda(&[b"transaction", multisig.as_ref(), &513u64.to_le_bytes()])
+        );
+        assert_eq!(audit_log_address(&multisig), pda(&[b"audit_log", multisig.as_ref()]));
+        assert_eq!(delegation_address(&multisig, &owner), pda(&[b"delegation", multisig.as_ref(), owner.as_ref()]));
+        assert_eq!(vault_address(&multisig), pda(&[b"vault", multisig.as_ref()]));
+        assert_ne!(lookup_table_address(&multisig, 1).0, lookup_table_address(&multisig, 2).0);
+    }
+}
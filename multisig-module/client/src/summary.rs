@@ -0,0 +1,317 @@
+use anchor_lang::prelude::Pubkey;
+
+use multisig_module::{Multisig, Transaction, TransactionType};
+
+use crate::decode_vault_message;
+
+/// The instruction that carries out a transaction once it is approved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecuteWith {
+    ExecuteTransaction,
+    ExecuteScheduledTransaction,
+    ExecuteVaultTransaction,
+    ExecuteConfigTransaction,
+    CloseMultisig,
+    ChangeThreshold,
+    AddOwner,
+    RemoveOwner,
+    SwapOwner,
+    ExtendExpiry,
+    /// `deactivate_lookup_table` or `close_lookup_table`
+    LookupTable,
+}
+
+/// Something that currently prevents execution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Blocker {
+    AlreadyExecuted,
+    /// Rejected by enough owners that the threshold can no longer be met
+    Rejected,
+    Expired { expired_at: i64 },
+    Paused,
+    MissingApprovals { missing: u8 },
+    ScheduleNotDue { due_at: i64 },
+    ScheduleCompleted,
+}
+
+/// What is needed to execute a transaction, computed with the same rules the
+/// program applies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionSummary {
+    pub transaction_id: u64,
+    pub transaction_type: TransactionType,
+    pub execute_with: ExecuteWith,
+    pub required_approvals: u8,
+    pub approved_by: Vec<Pubkey>,
+    /// Owners who have not approved yet
+    pub pending_owners: Vec<Pubkey>,
+    /// Whether the instruction must be signed by an owner; scheduled runs,
+    /// including scheduled vault messages, and admin actions can be sent by
+    /// anyone
+    pub owner_must_sign: bool,
+    /// Tables whose accounts must be passed to `execute_vault_transaction`
+    pub lookup_tables: Vec<Pubkey>,
+    pub blockers: Vec<Blocker>,
+}
+
+impl ExecutionSummary {
+    pub fn is_ready(&self) -> bool {
+        self.blockers.is_empty()
+    }
+}
+
+/// `now` is the current unix timestamp, e.g. from the cluster's clock sysvar
+pub fn execution_summary(multisig: &Multisig, transaction: &Transaction, now: i64) -> ExecutionSummary {
+    let execute_with = match transaction.transaction_type {
+        TransactionType::Config => ExecuteWith::ExecuteConfigTransaction,
+        TransactionType::CloseMultisig => ExecuteWith::CloseMultisig,
+        TransactionType::ChangeThreshold => ExecuteWith::ChangeThreshold,
+        TransactionType::AddOwner => ExecuteWith::AddOwner,
+        TransactionType::RemoveOwner => ExecuteWith::RemoveOwner,
+        TransactionType::SwapOwner => ExecuteWith::SwapOwner,
+        TransactionType::ExtendExpiry => ExecuteWith::ExtendExpiry,
+        TransactionType::LookupTable => ExecuteWith::LookupTable,
+        _ if transaction.vault_message => ExecuteWith::ExecuteVaultTransaction,
+        _ if transaction.schedule.is_some() => ExecuteWith::ExecuteScheduledTransaction,
+        _ => ExecuteWith::ExecuteTransaction,
+    };
+
+    let required_approvals = multisig.required_approvals(&transaction.transaction_type);
+
+    let (approved_by, pending_owners): (Vec<_>, Vec<_>) = multisig
+        .owners
+        .iter()
+        .zip(&transaction.approvals)
+        .partition(|(_, approved)| **approved);
+    let approved_by: Vec<Pubkey> = approved_by.into_iter().map(|(owner, _)| *owner).collect();
+    let pending_owners: Vec<Pubkey> = pending_owners.into_iter().map(|(owner, _)| *owner).collect();
+
+    let mut blockers = Vec::new();
+    if transaction.executed {
+        blockers.push(Blocker::AlreadyExecuted);
+    }
+    if transaction.rejected {
+        blockers.push(Blocker::Rejected);
+    }
+    if let Some(expires_at) = transaction.expiry() {
+        if !transaction.executed && now > expires_at {
+            blockers.push(Blocker::Expired { expired_at: expires_at });
+        }
+    }
+    if multisig.is_paused_for_transaction(&transaction.transaction_type, transaction.vault_message) {
+        blockers.push(Blocker::Paused);
+    }
+    let approvals = approved_by.len() as u8;
+    if approvals < required_approvals {
+        blockers.push(Blocker::MissingApprovals { missing: required_approvals - approvals });
+    }
+    if let Some(schedule) = &transaction.schedule {
+        match schedule.due_at(transaction.runs_executed) {
+            Some(due_at) if now < due_at => blockers.push(Blocker::ScheduleNotDue { due_at }),
+            Some(_) => {}
+            None => blockers.push(Blocker::ScheduleCompleted),
+        }
+    }
+
+    let lookup_tables = decode_vault_message(transaction)
+        .map(|message| message.lookups.iter().map(|lookup| lookup.table).collect())
+        .unwrap_or_default();
+
+    ExecutionSummary {
+        transaction_id: transaction.transaction_id,
+        transaction_type: transaction.transaction_type.clone(),
+        execute_with,
+        required_approvals,
+        approved_by,
+        pending_owners,
+        owner_must_sign: match execute_with {
+            ExecuteWith::ExecuteVaultTransaction => transaction.schedule.is_none(),
+            ExecuteWith::ExecuteTransaction
+            | ExecuteWith::ExecuteConfigTransaction
+            | ExecuteWith::CloseMultisig => true,
+            _ => false,
+        },
+        lookup_tables,
+        blockers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use anchor_lang::{AccountSerialize, AnchorSerialize};
+    use multisig_module::{ExecutionSchedule, ExpiryConfig, ProposalMetadata, VaultMessage, NO_EXPIRY};
+
+    use super::*;
+    use crate::{fetch_multisig, fetch_transaction, multisig_address, transaction_address};
+
+    const NOW: i64 = 1_700_000_000;
+
+    struct Fixture {
+        owners: Vec<Pubkey>,
+        multisig: Multisig,
+        transaction: Transaction,
+    }
+
+    impl Fixture {
+        /// A 2-of-3 multisig (3 for admin types) with a custom proposal
+        /// approved by its first two owners
+        fn new() -> Self {
+            let owners = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+            let multisig = Multisig {
+                owners: owners.clone(),
+                threshold: 2,
+                admin_threshold: 3,
+                transaction_count: 1,
+                bump: 255,
+                paused: false,
+                paused_by: Pubkey::default(),
+                paused_at: 0,
+                created_at: NOW - 3_600,
+                nonce: 1,
+                last_proposal_slot: 10,
+                expiry_config: ExpiryConfig::default(),
+                paused_types: vec![],
+                proposal_deposit: 0,
+            };
+            let transaction = Transaction {
+                transaction_id: 0,
+                created_at: NOW - 60,
+                expires_at: NOW + 60,
+                executed: false,
+                created_slot: 10,
+                multisig: multisig_address(&owners[0]).0,
+                proposer: owners[0],
+                transaction_type: TransactionType::Custom,
+                approvals: vec![true, true, false],
+                instruction_data: vec![1],
+                schedule: None,
+                runs_executed: 0,
+                metadata: ProposalMetadata::default(),
+                vault_message: false,
+                resolved_keys_hash: [0; 32],
+                deposit: 0,
+                rejections: vec![false; 3],
+                rejected: false,
+            };
+            Self { owners, multisig, transaction }
+        }
+
+        /// Round-trips both accounts through a `HashMap` fetcher, the way an
+        /// offline service would read them
+        fn summary(&self, now: i64) -> ExecutionSummary {
+            let address = multisig_address(&self.owners[0]).0;
+            let mut multisig_data = Vec::new();
+            self.multisig.try_serialize(&mut multisig_data).unwrap();
+            let mut transaction_data = Vec::new();
+            self.transaction.try_serialize(&mut transaction_data).unwrap();
+            let fetcher = HashMap::from([
+                (address, multisig_data),
+                (transaction_address(&address, self.transaction.transaction_id).0, transaction_data),
+            ]);
+
+            let multisig = fetch_multisig(&fetcher, &address).unwrap();
+            let transaction = fetch_transaction(&fetcher, &address, self.transaction.transaction_id).unwrap();
+            execution_summary(&multisig, &transaction, now)
+        }
+    }
+
+    #[test]
+    fn approved_transaction_is_ready() {
+        let fixture = Fixture::new();
+        let summary = fixture.summary(NOW);
+        assert!(summary.is_ready());
+        assert_eq!(summary.execute_with, ExecuteWith::ExecuteTransaction);
+        assert_eq!(summary.required_approvals, 2);
+        assert_eq!(summary.approved_by, fixture.owners[..2]);
+        assert_eq!(summary.pending_owners, fixture.owners[2..]);
+        assert!(summary.owner_must_sign);
+    }
+
+    #[test]
+    fn admin_types_need_the_admin_threshold() {
+        let mut fixture = Fixture::new();
+        fixture.transaction.transaction_type = TransactionType::Config;
+        let summary = fixture.summary(NOW);
+        assert_eq!(summary.execute_with, ExecuteWith::ExecuteConfigTransaction);
+        assert_eq!(summary.required_approvals, 3);
+        assert_eq!(summary.blockers, vec![Blocker::MissingApprovals { missing: 1 }]);
+    }
+
+    #[test]
+    fn executed_rejected_and_expired_block() {
+        let mut fixture = Fixture::new();
+        fixture.transaction.executed = true;
+        assert_eq!(fixture.summary(NOW + 120).blockers, vec![Blocker::AlreadyExecuted]);
+
+        let mut fixture = Fixture::new();
+        fixture.transaction.rejected = true;
+        assert_eq!(fixture.summary(NOW).blockers, vec![Blocker::Rejected]);
+
+        let fixture = Fixture::new();
+        assert_eq!(fixture.summary(NOW + 60).blockers, vec![]);
+        assert_eq!(fixture.summary(NOW + 61).blockers, vec![Blocker::Expired { expired_at: NOW + 60 }]);
+    }
+
+    #[test]
+    fn pauses_block_matching_types_and_vault_messages() {
+        let mut fixture = Fixture::new();
+        fixture.multisig.paused = true;
+        assert_eq!(fixture.summary(NOW).blockers, vec![Blocker::Paused]);
+
+        let mut fixture = Fixture::new();
+        fixture.multisig.paused_types = vec![TransactionType::TokenTransfer];
+        assert!(fixture.summary(NOW).is_ready());
+
+        // A vault message can move funds under any type
+        let message = VaultMessage { account_keys: vec![], lookups: vec![], instructions: vec![] };
+        fixture.transaction.vault_message = true;
+        fixture.transaction.instruction_data = message.try_to_vec().unwrap();
+        let summary = fixture.summary(NOW);
+        assert_eq!(summary.execute_with, ExecuteWith::ExecuteVaultTransaction);
+        assert_eq!(summary.blockers, vec![Blocker::Paused]);
+    }
+
+    #[test]
+    fn schedules_block_until_due_and_after_the_last_run() {
+        let mut fixture = Fixture::new();
+        fixture.transaction.expires_at = NO_EXPIRY;
+        fixture.transaction.schedule = Some(ExecutionSchedule::At { times: vec![NOW + 10, NOW + 20] });
+
+        let summary = fixture.summary(NOW);
+        assert_eq!(summary.execute_with, ExecuteWith::ExecuteScheduledTransaction);
+        assert!(!summary.owner_must_sign);
+        assert_eq!(summary.blockers, vec![Blocker::ScheduleNotDue { due_at: NOW + 10 }]);
+        assert!(fixture.summary(NOW + 10).is_ready());
+
+        fixture.transaction.runs_executed = 2;
+        assert_eq!(fixture.summary(NOW + 30).blockers, vec![Blocker::ScheduleCompleted]);
+    }
+
+    #[test]
+    fn scheduled_vault_messages_run_through_the_vault_executor() {
+        let mut fixture = Fixture::new();
+        let message = VaultMessage { account_keys: vec![], lookups: vec![], instructions: vec![] };
+        fixture.transaction.vault_message = true;
+        fixture.transaction.instruction_data = message.try_to_vec().unwrap();
+        assert!(fixture.summary(NOW).owner_must_sign);
+
+        fixture.transaction.schedule = Some(ExecutionSchedule::At { times: vec![NOW + 10] });
+        let summary = fixture.summary(NOW);
+        assert_eq!(summary.execute_with, ExecuteWith::ExecuteVaultTransaction);
+        assert!(!summary.owner_must_sign);
+        assert_eq!(summary.blockers, vec![Blocker::ScheduleNotDue { due_at: NOW + 10 }]);
+    }
+
+    #[test]
+    fn blockers_accumulate() {
+        let mut fixture = Fixture::new();
+        fixture.transaction.approvals = vec![false, true, false];
+        fixture.multisig.paused = true;
+        assert_eq!(
+            fixture.summary(NOW + 61).blockers,
+            vec![Blocker::Expired { expired_at: NOW + 60 }, Blocker::Paused, Blocker::MissingApprovals { missing: 1 }]
+        );
+    }
+}
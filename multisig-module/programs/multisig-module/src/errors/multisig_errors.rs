@@ -64,4 +64,84 @@ pub enum MultisigError {
     InvalidAdminThreshold,
     #[msg("Transaction too complex")]
     TransactionTooComplex,
-}
\ No newline at end of file
+    #[msg("Too many config actions: maximum 10 allowed")]
+    TooManyConfigActions,
+    #[msg("Invalid config actions")]
+    InvalidConfigActions,
+    #[msg("Invalid execution schedule")]
+    InvalidSchedule,
+    #[msg("Transaction has no execution schedule")]
+    NotScheduled,
+    #[msg("Scheduled run is not due yet")]
+    ScheduleNotDue,
+    #[msg("All scheduled runs have been executed")]
+    ScheduleCompleted,
+    #[msg("Scheduled transactions must be executed through execute_scheduled_transaction")]
+    ScheduledTransaction,
+    #[msg("Invalid delegate")]
+    InvalidDelegate,
+    #[msg("Signer is not the registered delegate")]
+    UnauthorizedDelegate,
+    #[msg("Delegate has expired")]
+    DelegateExpired,
+    #[msg("Delegate is not allowed to approve this transaction type")]
+    DelegateScopeViolation,
+    #[msg("Proposal title too long: maximum 64 bytes")]
+    TitleTooLong,
+    #[msg("Proposal memo too long: maximum 256 bytes")]
+    MemoTooLong,
+    #[msg("Document URI too long: maximum 200 bytes")]
+    UriTooLong,
+    #[msg("Document URI must not be empty")]
+    EmptyUri,
+    #[msg("Requested expiry exceeds the multisig maximum")]
+    ExpiryTooLong,
+    #[msg("Proposals without expiry are not allowed on this multisig")]
+    NoExpiryNotAllowed,
+    #[msg("Invalid expiry config: default exceeds maximum")]
+    InvalidExpiryConfig,
+    #[msg("New expiry must be later than the current one")]
+    ExpiryNotExtended,
+    #[msg("Invalid vault message")]
+    InvalidVaultMessage,
+    #[msg("Vault messages may not invoke the multisig program")]
+    VaultReentrancy,
+    #[msg("Transaction does not carry a vault message")]
+    NotVaultTransaction,
+    #[msg("Vault transactions must be executed through execute_vault_transaction")]
+    VaultTransaction,
+    #[msg("Invalid address lookup table")]
+    InvalidLookupTable,
+    #[msg("Lookup table index out of bounds")]
+    LookupIndexOutOfBounds,
+    #[msg("Lookup table is not owned by the vault")]
+    LookupTableNotVaultOwned,
+    #[msg("Lookup table addresses changed since the proposal")]
+    LookupKeysChanged,
+    #[msg("Remaining accounts do not match the vault message")]
+    AccountMismatch,
+    #[msg("Account must be writable")]
+    AccountNotWritable,
+    #[msg("Only the vault may sign vault instructions")]
+    InvalidSigner,
+    #[msg("Destination does not match the approved proposal")]
+    InvalidDestination,
+    #[msg("Account cannot be closed with this multisig")]
+    InvalidCloseAccount,
+    #[msg("Invalid token program")]
+    InvalidTokenProgram,
+    #[msg("Pause scope must list between one and 16 transaction types")]
+    InvalidPauseScope,
+    #[msg("Transaction was rejected by the owners")]
+    TransactionRejected,
+    #[msg("Owner already rejected this transaction")]
+    AlreadyRejected,
+    #[msg("Transaction holds no proposal deposit")]
+    NoProposalDeposit,
+    #[msg("Deposit is locked until the proposal executes, expires or is rejected")]
+    DepositLocked,
+    #[msg("Arguments do not match the approved proposal")]
+    ProposalMismatch,
+    #[msg("Every transaction of the multisig must be closed with it")]
+    TransactionsNotClosed,
+}
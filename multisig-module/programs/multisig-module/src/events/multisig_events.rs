@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::TransactionType;
+use crate::{TransactionType, ConfigAction, ExecutionSchedule, DelegateScope, ProposalMetadata};
 
 #[event]
 pub struct MultisigCreated {
@@ -15,6 +15,8 @@ pub struct MultisigCreated {
 pub struct MultisigPaused {
     pub multisig: Pubkey,
     pub paused_by: Pubkey,
+    pub scope: Option<Vec<TransactionType>>,
+    pub paused_types: Vec<TransactionType>,
     pub paused_at: i64,
 }
 
@@ -22,6 +24,8 @@ pub struct MultisigPaused {
 pub struct MultisigUnpaused {
     pub multisig: Pubkey,
     pub transaction: Pubkey,
+    pub scope: Option<Vec<TransactionType>>,
+    pub paused_types: Vec<TransactionType>,
     pub unpaused_at: i64,
 }
 
@@ -32,8 +36,11 @@ pub struct TransactionProposed {
     pub proposer: Pubkey,
     pub transaction_id: u64,
     pub transaction_type: TransactionType,
-    pub expires_at: i64,
+    pub expires_at: Option<i64>,
     pub created_at: i64,
+    pub schedule: Option<ExecutionSchedule>,
+    pub metadata: ProposalMetadata,
+    pub deposit: u64,
 }
 
 #[event]
@@ -41,11 +48,23 @@ pub struct TransactionApproved {
     pub multisig: Pubkey,
     pub transaction: Pubkey,
     pub approver: Pubkey,
+    pub delegate: Option<Pubkey>,
     pub transaction_id: u64,
     pub approval_count: u8,
     pub required_approvals: u8,
 }
 
+#[event]
+pub struct TransactionRejected {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub owner: Pubkey,
+    pub transaction_id: u64,
+    pub rejection_count: u8,
+    /// The threshold can no longer be met and the proposal is dead
+    pub rejected: bool,
+}
+
 #[event]
 pub struct TransactionExecuted {
     pub multisig: Pubkey,
@@ -57,6 +76,19 @@ pub struct TransactionExecuted {
     pub executed_at: i64,
 }
 
+#[event]
+pub struct ScheduledTransactionExecuted {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub cranker: Pubkey,
+    pub transaction_id: u64,
+    pub transaction_type: TransactionType,
+    pub run_index: u16,
+    pub total_runs: u16,
+    pub due_at: i64,
+    pub executed_at: i64,
+}
+
 #[event]
 pub struct ThresholdChanged {
     pub multisig: Pubkey,
@@ -82,4 +114,131 @@ pub struct OwnerRemoved {
     pub removed_owner: Pubkey,
     pub total_owners: u8,
     pub removed_at: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct OwnerSwapped {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub owner_index: u8,
+    pub swapped_at: i64,
+}
+
+#[event]
+pub struct ConfigTransactionExecuted {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub executor: Pubkey,
+    pub transaction_id: u64,
+    pub actions: Vec<ConfigAction>,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub admin_threshold: u8,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct DelegateRegistered {
+    pub multisig: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegateScope,
+    pub expires_at: i64,
+    pub registered_at: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub multisig: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub revoked_at: i64,
+}
+
+#[event]
+pub struct TransactionExpiryExtended {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub target_transaction: Pubkey,
+    pub old_expires_at: Option<i64>,
+    pub new_expires_at: Option<i64>,
+    pub extended_at: i64,
+}
+
+#[event]
+pub struct VaultTransactionExecuted {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub executor: Pubkey,
+    pub vault: Pubkey,
+    pub transaction_id: u64,
+    pub transaction_type: TransactionType,
+    pub approval_count: u8,
+    pub instruction_count: u8,
+    pub lookup_tables: Vec<Pubkey>,
+    pub executed_at: i64,
+}
+
+#[event]
+pub struct LookupTableCreated {
+    pub multisig: Pubkey,
+    pub lookup_table: Pubkey,
+    pub authority: Pubkey,
+    pub payer: Pubkey,
+    pub recent_slot: u64,
+    pub created_at: i64,
+}
+
+#[event]
+pub struct LookupTableExtended {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub lookup_table: Pubkey,
+    pub payer: Pubkey,
+    pub new_addresses: Vec<Pubkey>,
+    pub extended_at: i64,
+}
+
+#[event]
+pub struct LookupTableDeactivated {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub lookup_table: Pubkey,
+    pub deactivated_at: i64,
+}
+
+#[event]
+pub struct LookupTableClosed {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub lookup_table: Pubkey,
+    pub recipient: Pubkey,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct MultisigClosed {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub executor: Pubkey,
+    pub destination: Pubkey,
+    pub lamports_swept: u64,
+    pub token_accounts_swept: u8,
+    pub accounts_closed: u16,
+    pub deposits_refunded: u64,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct ProposalDepositReleased {
+    pub multisig: Pubkey,
+    pub transaction: Pubkey,
+    pub transaction_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// True when returned to the proposer, false when forfeited to the vault
+    pub refunded: bool,
+    pub released_at: i64,
+}
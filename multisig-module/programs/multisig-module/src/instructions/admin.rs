@@ -1,9 +1,10 @@
 use anchor_lang::{prelude::*};
 use crate::{
-    MultisigError,
-    ThresholdChanged, OwnerAdded, OwnerRemoved, MultisigUnpaused,
-    ChangeThreshold, AddOwner, RemoveOwner, UnpauseMultisig
+    AuditAction, ProposalExpiry, ProposalMetadata, TransactionType, MultisigError, NO_EXPIRY,
+    ThresholdChanged, OwnerAdded, OwnerRemoved, OwnerSwapped, MultisigUnpaused, TransactionExpiryExtended,
+    ProposeTransaction, ChangeThreshold, AddOwner, RemoveOwner, SwapOwner, ExtendExpiry, UnpauseMultisig
 };
+use super::transaction::{open_transaction, NewTransaction};
 
 pub fn change_threshold(
         ctx: Context<ChangeThreshold>,
@@ -13,7 +14,7 @@ pub fn change_threshold(
         let multisig = &mut ctx.accounts.multisig;
         let transaction = &mut ctx.accounts.transaction;
 
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
         multisig.validate_state()?;
         transaction.validate_state(multisig)?;
 
@@ -27,6 +28,9 @@ pub fn change_threshold(
         multisig.threshold = new_threshold; 
         transaction.executed = true; 
 
+        // Admin executions are permissionless once approved, so there is no actor
+        ctx.accounts.audit_log.append(AuditAction::ThresholdChanged, Pubkey::default(), Some(transaction.transaction_id))?;
+
         emit!(ThresholdChanged {
           multisig: multisig.key(),
           transaction: transaction.key(),
@@ -47,7 +51,7 @@ pub fn change_threshold(
         let multisig = &mut ctx.accounts.multisig;
         let transaction = &mut ctx.accounts.transaction;
 
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
         multisig.validate_state()?;
         transaction.validate_state(multisig)?;
 
@@ -63,6 +67,8 @@ pub fn change_threshold(
         multisig.owners.push(new_owner); 
         transaction.executed = true; 
 
+        ctx.accounts.audit_log.append(AuditAction::OwnerAdded, Pubkey::default(), Some(transaction.transaction_id))?;
+
         emit!(OwnerAdded {
           multisig: multisig.key(),
           transaction: transaction.key(),
@@ -83,7 +89,7 @@ pub fn change_threshold(
         let multisig = &mut ctx.accounts.multisig;
         let transaction = &mut ctx.accounts.transaction;
 
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
         multisig.validate_state()?;
         transaction.validate_state(multisig)?;
 
@@ -105,6 +111,8 @@ pub fn change_threshold(
 
         transaction.executed = true; 
 
+        ctx.accounts.audit_log.append(AuditAction::OwnerRemoved, Pubkey::default(), Some(transaction.transaction_id))?;
+
         emit!(OwnerRemoved {
           multisig: multisig.key(),
           transaction: transaction.key(),
@@ -117,28 +125,224 @@ pub fn change_threshold(
         Ok(())
     }
 
+/// Proposes replacing `old_owner` with `new_owner`. The pair is stored in the
+/// proposal so `swap_owner` can only carry out exactly what was approved.
+pub fn propose_swap_owner(
+        ctx: Context<ProposeTransaction>,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        require!(new_owner != Pubkey::default(), MultisigError::InvalidOwner);
+        require!(multisig.owners.contains(&old_owner), MultisigError::OwnerNotFound);
+        require!(!multisig.owners.contains(&new_owner), MultisigError::DuplicateOwners);
+
+        let instruction_data = (old_owner, new_owner).try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type: TransactionType::SwapOwner,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+    pub fn swap_owner(
+        ctx: Context<SwapOwner>,
+        transaction_id: u64,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(
+            transaction.transaction_type == TransactionType::SwapOwner,
+            MultisigError::InvalidTransactionType
+        );
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(transaction.is_admin_ready_to_execute(multisig.admin_threshold), MultisigError::NotEnoughAdminApprovals);
+
+        let approved = <(Pubkey, Pubkey)>::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::ProposalMismatch)?;
+        require!(approved == (old_owner, new_owner), MultisigError::ProposalMismatch);
+
+        require!(new_owner != Pubkey::default(), MultisigError::InvalidOwner);
+        require!(!multisig.owners.contains(&new_owner), MultisigError::DuplicateOwners);
+
+        let owner_index = multisig.owners
+            .iter()
+            .position(|&owner| owner == old_owner)
+            .ok_or(MultisigError::OwnerNotFound)?;
+
+        // Replace in place so the owner keeps its slot in every pending
+        // transaction's approvals array.
+        multisig.owners[owner_index] = new_owner;
+        multisig.validate_state()?;
+
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::OwnerSwapped, Pubkey::default(), Some(transaction.transaction_id))?;
+
+        emit!(OwnerSwapped {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          old_owner,
+          new_owner,
+          owner_index: owner_index as u8,
+          swapped_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Owner {} swapped for {} at index {}", old_owner, new_owner, owner_index);
+        Ok(())
+    }
+
+/// Proposes moving the expiry of `target_transaction_id`. The target and
+/// the new expiry are stored so `extend_expiry` can only apply what was approved.
+pub fn propose_extend_expiry(
+        ctx: Context<ProposeTransaction>,
+        target_transaction_id: u64,
+        new_expiry: ProposalExpiry,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        require!(
+            target_transaction_id < ctx.accounts.multisig.transaction_count,
+            MultisigError::InvalidTransactionId
+        );
+
+        let instruction_data = (target_transaction_id, new_expiry).try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type: TransactionType::ExtendExpiry,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+    pub fn extend_expiry(
+        ctx: Context<ExtendExpiry>,
+        transaction_id: u64,
+        target_transaction_id: u64,
+        new_expiry: ProposalExpiry,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        let target = &mut ctx.accounts.target_transaction;
+
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+
+        require!(
+            transaction.transaction_type == TransactionType::ExtendExpiry,
+            MultisigError::InvalidTransactionType
+        );
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(transaction.is_ready_to_execute(multisig.threshold), MultisigError::NotEnoughApprovals);
+
+        let approved = <(u64, ProposalExpiry)>::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::ProposalMismatch)?;
+        require!(approved == (target_transaction_id, new_expiry), MultisigError::ProposalMismatch);
+
+        // The target may already have lapsed; extending it revives it
+        require!(target.key() != transaction.key(), MultisigError::InvalidTransaction);
+        require!(!target.executed, MultisigError::AlreadyExecuted);
+
+        let clock = Clock::get()?;
+        let old_expires_at = target.expiry();
+        let new_expires_at = multisig.expiry_config.resolve(Some(new_expiry), clock.unix_timestamp)?;
+
+        match (old_expires_at, new_expires_at) {
+            (Some(old), Some(new)) => require!(new > old, MultisigError::ExpiryNotExtended),
+            (None, _) => return Err(error!(MultisigError::ExpiryNotExtended)),
+            (Some(_), None) => {}
+        }
+
+        target.expires_at = new_expires_at.unwrap_or(NO_EXPIRY);
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::ExpiryExtended, Pubkey::default(), Some(transaction.transaction_id))?;
+
+        emit!(TransactionExpiryExtended {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          target_transaction: target.key(),
+          old_expires_at,
+          new_expires_at,
+          extended_at: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Transaction {} expiry extended from {:?} to {:?}",
+            target.transaction_id,
+            old_expires_at,
+            new_expires_at
+        );
+        Ok(())
+    }
+
+/// Lifts every pause when `scope` is `None`, otherwise resumes only the
+/// listed transaction types.
 pub fn unpause(
         ctx: Context<UnpauseMultisig>,
         _transaction_id: u64,
+        scope: Option<Vec<TransactionType>>,
     ) -> Result<()> {
         let multisig = &mut ctx.accounts.multisig;
         let transaction = &mut ctx.accounts.transaction;
 
-        require!(multisig.paused, MultisigError::NotPaused);
         require!(!transaction.executed, MultisigError::AlreadyExecuted);
         require!(transaction.is_ready_to_execute(multisig.threshold), MultisigError::NotEnoughApprovals);
 
-        multisig.paused = false;
-        multisig.paused_by = Pubkey::default();
-        multisig.paused_at = 0;
+        multisig.lift_pause(scope.as_deref())?;
         transaction.executed = true;
 
+        ctx.accounts.audit_log.append(AuditAction::Unpaused, Pubkey::default(), Some(transaction.transaction_id))?;
+
         emit!(MultisigUnpaused {
           multisig:multisig.key(),
           transaction: transaction.key(),
+          scope: scope.clone(),
+          paused_types: multisig.paused_types.clone(),
           unpaused_at: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Multisig unpaused");
+        msg!("Multisig unpaused for {:?}", scope);
         Ok(())
     }
\ No newline at end of file
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::{
+    token::ID as TOKEN_PROGRAM_ID,
+    token_2022::ID as TOKEN_2022_PROGRAM_ID,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TransferChecked},
+};
+use crate::{
+    Transaction, Delegation, ProposalExpiry, ProposalMetadata, TransactionType, MultisigError,
+    MultisigClosed,
+    ProposeTransaction, CloseMultisig,
+};
+use super::transaction::{open_transaction, NewTransaction};
+
+pub fn propose_close_multisig(
+        ctx: Context<ProposeTransaction>,
+        destination: Pubkey,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        require!(destination != Pubkey::default(), MultisigError::InvalidDestination);
+
+        let instruction_data = destination.try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type: TransactionType::CloseMultisig,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+/// Retires the multisig: sweeps the vault's token accounts and SOL to the
+/// approved destination, then closes every other proposal of the multisig,
+/// the listed delegations, the multisig, its audit log and this proposal. All reclaimed rent goes to
+/// the destination. Deposits still held are refunded to their proposers,
+/// except those of rejected proposals, which are forfeited like the vault.
+pub fn close_multisig<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseMultisig<'info>>,
+        transaction_id: u64,
+        token_account_count: u8,
+    ) -> Result<()> {
+        let executor = &ctx.accounts.executor;
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        let vault = &ctx.accounts.vault;
+        let destination = &ctx.accounts.destination;
+
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(
+            transaction.transaction_type == TransactionType::CloseMultisig,
+            MultisigError::InvalidTransactionType
+        );
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+
+        let approved_destination = Pubkey::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::InvalidDestination)?;
+        require_keys_eq!(approved_destination, destination.key(), MultisigError::InvalidDestination);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.slot > transaction.created_slot + 1,
+            MultisigError::SameSlotExecution
+        );
+
+        let is_owner = multisig.owners.iter().any(|owner| owner == executor.key);
+        require!(is_owner, MultisigError::OwnerNotFound);
+
+        require!(
+            transaction.is_admin_ready_to_execute(multisig.admin_threshold),
+            MultisigError::NotEnoughAdminApprovals
+        );
+        let approval_count = transaction.approval_count() as u8;
+
+        let token_group_len = token_account_count as usize * 4;
+        require!(
+            ctx.remaining_accounts.len() >= token_group_len,
+            MultisigError::AccountMismatch
+        );
+        let (token_groups, pending_accounts) = ctx.remaining_accounts.split_at(token_group_len);
+
+        let multisig_key = multisig.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", multisig_key.as_ref(), &[ctx.bumps.vault]];
+        let signer_seeds = &[vault_seeds];
+
+        for group in token_groups.chunks(4) {
+            let (vault_token_account, mint, destination_token_account, token_program) =
+                (&group[0], &group[1], &group[2], &group[3]);
+
+            require!(
+                token_program.key() == TOKEN_PROGRAM_ID || token_program.key() == TOKEN_2022_PROGRAM_ID,
+                MultisigError::InvalidTokenProgram
+            );
+            require_keys_eq!(*vault_token_account.owner, token_program.key(), MultisigError::InvalidTokenProgram);
+
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(vault_token_account)?;
+            require_keys_eq!(token_account.owner, vault.key(), MultisigError::InvalidCloseAccount);
+            require_keys_eq!(token_account.mint, mint.key(), MultisigError::InvalidCloseAccount);
+            let mint_account = InterfaceAccount::<Mint>::try_from(mint)?;
+
+            let destination_account = InterfaceAccount::<TokenAccount>::try_from(destination_token_account)?;
+            require_keys_eq!(destination_account.owner, approved_destination, MultisigError::InvalidDestination);
+            require_keys_eq!(destination_account.mint, mint.key(), MultisigError::InvalidDestination);
+
+            if token_account.amount > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        TransferChecked {
+                            from: vault_token_account.clone(),
+                            mint: mint.clone(),
+                            to: destination_token_account.clone(),
+                            authority: vault.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    token_account.amount,
+                    mint_account.decimals,
+                )?;
+            }
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program.clone(),
+                CloseAccount {
+                    account: vault_token_account.clone(),
+                    destination: destination.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let lamports_swept = vault.lamports();
+        if lamports_swept > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: vault.to_account_info(),
+                        to: destination.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                lamports_swept,
+            )?;
+        }
+
+        let mut deposits_refunded: u64 = 0;
+        if transaction.deposit > 0 {
+            let deposit = transaction.deposit;
+            transaction.deposit = 0;
+            transaction.sub_lamports(deposit)?;
+            ctx.accounts.proposer.add_lamports(deposit)?;
+            deposits_refunded += deposit;
+        }
+
+        // Every other proposal ever opened must close with the multisig, in id
+        // order, or a re-created multisig would find its PDAs already taken
+        let mut accounts_closed: u16 = 0;
+        let mut remaining = pending_accounts.iter();
+        for id in (0..multisig.transaction_count).filter(|id| *id != transaction_id) {
+            let account = remaining.next().ok_or(MultisigError::TransactionsNotClosed)?;
+            let mut pending = Account::<Transaction>::try_from(account)
+                .map_err(|_| MultisigError::TransactionsNotClosed)?;
+            require_keys_eq!(pending.multisig, multisig_key, MultisigError::InvalidCloseAccount);
+            require!(pending.transaction_id == id, MultisigError::TransactionsNotClosed);
+
+            if pending.deposit > 0 && !pending.rejected {
+                let proposer = remaining.next().ok_or(MultisigError::AccountMismatch)?;
+                require_keys_eq!(proposer.key(), pending.proposer, MultisigError::InvalidDestination);
+                let deposit = pending.deposit;
+                pending.deposit = 0;
+                pending.sub_lamports(deposit)?;
+                proposer.add_lamports(deposit)?;
+                deposits_refunded += deposit;
+            }
+            pending.close(destination.to_account_info())?;
+            accounts_closed += 1;
+        }
+
+        for account in remaining {
+            let delegation = Account::<Delegation>::try_from(account)
+                .map_err(|_| MultisigError::InvalidCloseAccount)?;
+            require_keys_eq!(delegation.multisig, multisig_key, MultisigError::InvalidCloseAccount);
+            delegation.close(destination.to_account_info())?;
+            accounts_closed += 1;
+        }
+
+        transaction.executed = true;
+
+        emit!(MultisigClosed {
+          multisig: multisig_key,
+          transaction: transaction.key(),
+          executor: executor.key(),
+          destination: destination.key(),
+          lamports_swept,
+          token_accounts_swept: token_account_count,
+          accounts_closed,
+          deposits_refunded,
+          closed_at: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Multisig {} closed with {}/{} approvals. Swept {} lamports and {} token accounts to {}, closed {} pending accounts",
+            multisig_key,
+            approval_count,
+            multisig.admin_threshold,
+            lamports_swept,
+            token_account_count,
+            destination.key(),
+            accounts_closed
+        );
+        Ok(())
+    }
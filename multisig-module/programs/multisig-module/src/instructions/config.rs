@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::{
+    ConfigAction, ProposalExpiry, ProposalMetadata, AuditAction, TransactionType, MultisigError, MAX_CONFIG_ACTIONS,
+    ConfigTransactionExecuted,
+    ProposeConfigTransaction, ExecuteConfigTransaction,
+};
+use super::transaction::{open_transaction, NewTransaction};
+
+pub fn propose_config_transaction(
+        ctx: Context<ProposeConfigTransaction>,
+        actions: Vec<ConfigAction>,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        require!(!actions.is_empty(), MultisigError::EmptyTransaction);
+        require!(actions.len() <= MAX_CONFIG_ACTIONS, MultisigError::TooManyConfigActions);
+
+        // Dry run against the current state so obviously invalid bundles are
+        // rejected up front; the bundle is validated again at execution.
+        let mut preview = (*ctx.accounts.multisig).clone();
+        preview.apply_config_actions(&actions)?;
+
+        let lifts_pause = actions.contains(&ConfigAction::Unpause);
+        let instruction_data = actions.try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type: TransactionType::Config,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause,
+            },
+        )
+    }
+
+pub fn execute_config_transaction(
+        ctx: Context<ExecuteConfigTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        let executor = &ctx.accounts.executor;
+        let multisig = &mut ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(
+            transaction.transaction_type == TransactionType::Config,
+            MultisigError::InvalidTransactionType
+        );
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+
+        let actions = Vec::<ConfigAction>::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::InvalidConfigActions)?;
+
+        // A paused multisig can only run bundles that lift the pause
+        require!(
+            transaction.lifts_pause() || !multisig.is_paused_for(&TransactionType::Config),
+            MultisigError::MultisigPaused
+        );
+
+        let clock = Clock::get()?;
+        require!(
+            clock.slot > transaction.created_slot + 1,
+            MultisigError::SameSlotExecution
+        );
+
+        let is_owner = multisig.owners.iter().any(|owner| owner == executor.key);
+        require!(is_owner, MultisigError::OwnerNotFound);
+
+        require!(
+            transaction.is_admin_ready_to_execute(multisig.admin_threshold),
+            MultisigError::NotEnoughAdminApprovals
+        );
+        let approval_count = transaction.approval_count() as u8;
+
+        multisig.apply_config_actions(&actions)?;
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::ConfigExecuted, executor.key(), Some(transaction_id))?;
+
+        emit!(ConfigTransactionExecuted {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          executor: executor.key(),
+          transaction_id,
+          actions: actions.clone(),
+          owners: multisig.owners.clone(),
+          threshold: multisig.threshold,
+          admin_threshold: multisig.admin_threshold,
+          executed_at: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Config transaction {} applied {} actions with {}/{} approvals. Owners: {}, threshold {}, admin threshold {}",
+            transaction_id,
+            actions.len(),
+            approval_count,
+            multisig.admin_threshold,
+            multisig.owners.len(),
+            multisig.threshold,
+            multisig.admin_threshold
+        );
+        Ok(())
+    }
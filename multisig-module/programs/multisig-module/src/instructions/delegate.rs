@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::{
+    DelegateScope, AuditAction, MultisigError,
+    DelegateRegistered, DelegateRevoked,
+    RegisterDelegate, RevokeDelegate,
+};
+
+pub fn register_delegate(
+        ctx: Context<RegisterDelegate>,
+        delegate: Pubkey,
+        expires_at: i64,
+        scope: DelegateScope,
+    ) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        let multisig = &ctx.accounts.multisig;
+        let delegation = &mut ctx.accounts.delegation;
+
+        let is_owner = multisig.owners.iter().any(|existing| existing == owner.key);
+        require!(is_owner, MultisigError::OwnerNotFound);
+
+        require!(delegate != Pubkey::default(), MultisigError::InvalidDelegate);
+        require!(delegate != owner.key(), MultisigError::InvalidDelegate);
+        require!(scope.transaction_types.len() <= 8, MultisigError::InvalidDelegate);
+
+        let clock = Clock::get()?;
+        require!(expires_at > clock.unix_timestamp, MultisigError::DelegateExpired);
+
+        delegation.multisig = multisig.key();
+        delegation.owner = owner.key();
+        delegation.delegate = delegate;
+        delegation.scope = scope.clone();
+        delegation.expires_at = expires_at;
+        delegation.created_at = clock.unix_timestamp;
+        delegation.bump = ctx.bumps.delegation;
+
+        ctx.accounts.audit_log.append(AuditAction::DelegateRegistered, owner.key(), None)?;
+
+        emit!(DelegateRegistered {
+          multisig: multisig.key(),
+          owner: owner.key(),
+          delegate,
+          scope,
+          expires_at,
+          registered_at: clock.unix_timestamp,
+        });
+
+        msg!("Owner {} registered delegate {} until {}", owner.key, delegate, expires_at);
+        Ok(())
+    }
+
+pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        let multisig = &ctx.accounts.multisig;
+        let delegation = &ctx.accounts.delegation;
+
+        ctx.accounts.audit_log.append(AuditAction::DelegateRevoked, owner.key(), None)?;
+
+        emit!(DelegateRevoked {
+          multisig: multisig.key(),
+          owner: owner.key(),
+          delegate: delegation.delegate,
+          revoked_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Owner {} revoked delegate {}", owner.key, delegation.delegate);
+        Ok(())
+    }
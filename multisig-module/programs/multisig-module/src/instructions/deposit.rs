@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::{
+    AuditAction, MultisigError,
+    ProposalDepositReleased,
+    ReleaseProposalDeposit,
+};
+
+/// How long a proposal that never expires keeps its deposit locked
+pub const NO_EXPIRY_DEPOSIT_LOCK_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Permissionless settlement of a proposal deposit: returned to the proposer
+/// once the proposal executed or expired, forfeited to the vault once the
+/// owners rejected it. A proposal that never expires has its deposit
+/// returned after `NO_EXPIRY_DEPOSIT_LOCK_SECONDS` and stays open.
+pub fn release_proposal_deposit(
+        ctx: Context<ReleaseProposalDeposit>,
+        _transaction_id: u64,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+
+        let amount = transaction.deposit;
+        require!(amount > 0, MultisigError::NoProposalDeposit);
+
+        let now = Clock::get()?.unix_timestamp;
+        let lock_elapsed = transaction.expiry().is_none()
+            && now >= transaction.created_at.saturating_add(NO_EXPIRY_DEPOSIT_LOCK_SECONDS);
+
+        let refunded = if transaction.rejected {
+            false
+        } else if transaction.executed || transaction.is_expired()? || lock_elapsed {
+            true
+        } else {
+            return err!(MultisigError::DepositLocked);
+        };
+        let recipient = match refunded {
+            true => ctx.accounts.proposer.to_account_info(),
+            false => ctx.accounts.vault.to_account_info(),
+        };
+
+        transaction.deposit = 0;
+        transaction.sub_lamports(amount)?;
+        recipient.add_lamports(amount)?;
+
+        let transaction_id = transaction.transaction_id;
+        ctx.accounts.audit_log.append(AuditAction::ProposalDepositReleased, Pubkey::default(), Some(transaction_id))?;
+
+        emit!(ProposalDepositReleased {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          transaction_id,
+          recipient: recipient.key(),
+          amount,
+          refunded,
+          released_at: now,
+        });
+
+        msg!(
+            "Deposit of {} lamports for transaction {} {} {}",
+            amount,
+            transaction_id,
+            if refunded { "refunded to" } else { "forfeited to vault" },
+            recipient.key()
+        );
+        Ok(())
+    }
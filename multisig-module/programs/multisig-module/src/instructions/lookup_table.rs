@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use solana_address_lookup_table_interface::instruction as lookup_table_instruction;
+use crate::{
+    AuditAction, LookupTableAction, Multisig, ProposalExpiry, ProposalMetadata, Transaction,
+    TransactionType, MultisigError,
+    LookupTableCreated, LookupTableExtended, LookupTableDeactivated, LookupTableClosed,
+    ProposeTransaction, CreateLookupTable, ExtendLookupTable, DeactivateLookupTable, CloseLookupTable,
+};
+use super::transaction::{open_transaction, NewTransaction};
+
+/// Most addresses a single extend can carry within the transaction size limit
+pub const MAX_LOOKUP_TABLE_EXTEND: usize = 20;
+
+pub fn create_lookup_table(
+        ctx: Context<CreateLookupTable>,
+        recent_slot: u64,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let payer = &ctx.accounts.payer;
+        let vault = &ctx.accounts.vault;
+
+        require!(!multisig.is_paused_for(&TransactionType::LookupTable), MultisigError::MultisigPaused);
+        let is_owner = multisig.owners.iter().any(|owner| owner == payer.key);
+        require!(is_owner, MultisigError::OwnerNotFound);
+
+        let (ix, lookup_table) =
+            lookup_table_instruction::create_lookup_table(vault.key(), payer.key(), recent_slot);
+        require_keys_eq!(lookup_table, ctx.accounts.lookup_table.key(), MultisigError::InvalidLookupTable);
+
+        invoke(
+            &ix,
+            &[
+                ctx.accounts.lookup_table.to_account_info(),
+                vault.to_account_info(),
+                payer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        ctx.accounts.audit_log.append(AuditAction::LookupTableCreated, payer.key(), None)?;
+
+        emit!(LookupTableCreated {
+          multisig: multisig.key(),
+          lookup_table,
+          authority: vault.key(),
+          payer: payer.key(),
+          recent_slot,
+          created_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Lookup table {} created for vault {}", lookup_table, vault.key());
+        Ok(())
+    }
+
+/// Proposes a change to a vault-owned lookup table. The table and the action
+/// are stored so the matching instruction can only apply what was approved.
+pub fn propose_lookup_table_transaction(
+        ctx: Context<ProposeTransaction>,
+        lookup_table: Pubkey,
+        action: LookupTableAction,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        if let LookupTableAction::Extend { new_addresses } = &action {
+            require!(
+                !new_addresses.is_empty() && new_addresses.len() <= MAX_LOOKUP_TABLE_EXTEND,
+                MultisigError::InvalidLookupTable
+            );
+        }
+
+        let instruction_data = (lookup_table, action).try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type: TransactionType::LookupTable,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+/// Checks that `transaction` is an approved `LookupTable` proposal for
+/// `lookup_table` and returns the action it approved.
+fn approved_action(
+        multisig: &Multisig,
+        transaction: &Transaction,
+        lookup_table: &Pubkey,
+    ) -> Result<LookupTableAction> {
+        require!(
+            transaction.transaction_type == TransactionType::LookupTable,
+            MultisigError::InvalidTransactionType
+        );
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        transaction.validate_state(multisig)?;
+        require!(transaction.is_ready_to_execute(multisig.threshold), MultisigError::NotEnoughApprovals);
+
+        let (approved_table, action) = <(Pubkey, LookupTableAction)>::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::ProposalMismatch)?;
+        require_keys_eq!(approved_table, *lookup_table, MultisigError::ProposalMismatch);
+        Ok(action)
+    }
+
+/// Extending only appends, so indices used by pending proposals stay valid.
+/// The addresses still need approval since vault messages resolve against them.
+pub fn extend_lookup_table(
+        ctx: Context<ExtendLookupTable>,
+        _transaction_id: u64,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let payer = &ctx.accounts.payer;
+        let vault = &ctx.accounts.vault;
+        let lookup_table = &ctx.accounts.lookup_table;
+        let transaction = &mut ctx.accounts.transaction;
+
+        let is_owner = multisig.owners.iter().any(|owner| owner == payer.key);
+        require!(is_owner, MultisigError::OwnerNotFound);
+        let new_addresses = match approved_action(multisig, transaction, &lookup_table.key())? {
+            LookupTableAction::Extend { new_addresses } => new_addresses,
+            _ => return Err(error!(MultisigError::ProposalMismatch)),
+        };
+
+        let ix = lookup_table_instruction::extend_lookup_table(
+            lookup_table.key(),
+            vault.key(),
+            Some(payer.key()),
+            new_addresses.clone(),
+        );
+        let multisig_key = multisig.key();
+        invoke_signed(
+            &ix,
+            &[
+                lookup_table.to_account_info(),
+                vault.to_account_info(),
+                payer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[b"vault", multisig_key.as_ref(), &[ctx.bumps.vault]]],
+        )?;
+
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::LookupTableExtended, payer.key(), Some(transaction.transaction_id))?;
+
+        emit!(LookupTableExtended {
+          multisig: multisig_key,
+          transaction: transaction.key(),
+          lookup_table: lookup_table.key(),
+          payer: payer.key(),
+          new_addresses: new_addresses.clone(),
+          extended_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Lookup table {} extended with {} addresses", lookup_table.key(), new_addresses.len());
+        Ok(())
+    }
+
+pub fn deactivate_lookup_table(
+        ctx: Context<DeactivateLookupTable>,
+        _transaction_id: u64,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        let vault = &ctx.accounts.vault;
+        let lookup_table = &ctx.accounts.lookup_table;
+
+        require!(
+            approved_action(multisig, transaction, &lookup_table.key())? == LookupTableAction::Deactivate,
+            MultisigError::ProposalMismatch
+        );
+
+        let ix = lookup_table_instruction::deactivate_lookup_table(lookup_table.key(), vault.key());
+        let multisig_key = multisig.key();
+        invoke_signed(
+            &ix,
+            &[lookup_table.to_account_info(), vault.to_account_info()],
+            &[&[b"vault", multisig_key.as_ref(), &[ctx.bumps.vault]]],
+        )?;
+
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::LookupTableDeactivated, Pubkey::default(), Some(transaction.transaction_id))?;
+
+        emit!(LookupTableDeactivated {
+          multisig: multisig_key,
+          transaction: transaction.key(),
+          lookup_table: lookup_table.key(),
+          deactivated_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Lookup table {} deactivated", lookup_table.key());
+        Ok(())
+    }
+
+/// Only possible once the deactivation has cooled down; the rent goes back
+/// to the vault.
+pub fn close_lookup_table(
+        ctx: Context<CloseLookupTable>,
+        _transaction_id: u64,
+    ) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        let vault = &ctx.accounts.vault;
+        let lookup_table = &ctx.accounts.lookup_table;
+
+        require!(
+            approved_action(multisig, transaction, &lookup_table.key())? == LookupTableAction::Close,
+            MultisigError::ProposalMismatch
+        );
+
+        let ix = lookup_table_instruction::close_lookup_table(lookup_table.key(), vault.key(), vault.key());
+        let multisig_key = multisig.key();
+        invoke_signed(
+            &ix,
+            &[
+                lookup_table.to_account_info(),
+                vault.to_account_info(),
+                vault.to_account_info(),
+            ],
+            &[&[b"vault", multisig_key.as_ref(), &[ctx.bumps.vault]]],
+        )?;
+
+        transaction.executed = true;
+
+        ctx.accounts.audit_log.append(AuditAction::LookupTableClosed, Pubkey::default(), Some(transaction.transaction_id))?;
+
+        emit!(LookupTableClosed {
+          multisig: multisig_key,
+          transaction: transaction.key(),
+          lookup_table: lookup_table.key(),
+          recipient: vault.key(),
+          closed_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Lookup table {} closed, rent returned to vault {}", lookup_table.key(), vault.key());
+        Ok(())
+    }
@@ -1,6 +1,12 @@
 pub mod admin;
+pub mod close;
+pub mod config;
+pub mod delegate;
+pub mod deposit;
+pub mod lookup_table;
 pub mod multisig;
 pub mod transaction;
+pub mod vault;
 
 // pub use admin::*;
 // pub use multisig::*;
@@ -8,7 +14,12 @@ pub mod transaction;
 
 
 pub use crate::{
-    CreateMultisig, EmergencyAction,
-    ProposeTransaction, ApproveTransaction, ExecuteTransaction,
-    ChangeThreshold, AddOwner, RemoveOwner, UnpauseMultisig
+    CreateMultisig, EmergencyAction, InitializeAuditLog, MigrateMultisig, MigrateTransaction,
+    ProposeTransaction, ApproveTransaction, RejectTransaction, ExecuteTransaction, ExecuteScheduledTransaction,
+    ReleaseProposalDeposit,
+    ProposeConfigTransaction, ExecuteConfigTransaction,
+    ExecuteVaultTransaction, CreateLookupTable, ExtendLookupTable, DeactivateLookupTable, CloseLookupTable,
+    RegisterDelegate, RevokeDelegate,
+    CloseMultisig,
+    ChangeThreshold, AddOwner, RemoveOwner, SwapOwner, ExtendExpiry, UnpauseMultisig
 };
\ No newline at end of file
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::{MultisigError, MultisigCreated, MultisigPaused, CreateMultisig, EmergencyAction};
+use anchor_lang::system_program;
+use crate::{
+    Multisig, AuditAction, ExpiryConfig, TransactionType, MultisigError, MultisigCreated, MultisigPaused,
+    CreateMultisig, EmergencyAction, InitializeAuditLog, MigrateMultisig,
+};
 
 
 pub fn create_multisig(
@@ -41,9 +45,20 @@ pub fn create_multisig(
         multisig.created_at = Clock::get()?.unix_timestamp;
         multisig.nonce = 0;
         multisig.last_proposal_slot = 0;
+        multisig.expiry_config = ExpiryConfig::default();
+        multisig.paused_types = Vec::new();
+        multisig.proposal_deposit = 0;
 
         multisig.validate_state()?;
 
+        let audit_log = &mut ctx.accounts.audit_log;
+        audit_log.multisig = multisig.key();
+        audit_log.head = 0;
+        audit_log.total_records = 0;
+        audit_log.bump = ctx.bumps.audit_log;
+        audit_log.records = Vec::new();
+        audit_log.append(AuditAction::MultisigCreated, ctx.accounts.creator.key(), None)?;
+
         emit!(MultisigCreated {
           multisig: multisig.key(),
           creator: ctx.accounts.creator.key(),
@@ -59,23 +74,80 @@ pub fn create_multisig(
     }
 
 
-pub fn emergency_pause(ctx: Context<EmergencyAction>) -> Result<()> {
+/// Pauses everything when `scope` is `None`, otherwise only the listed
+/// transaction types.
+pub fn emergency_pause(ctx: Context<EmergencyAction>, scope: Option<Vec<TransactionType>>) -> Result<()> {
         let multisig = &mut ctx.accounts.multisig;
         let caller = &ctx.accounts.caller;
 
         let is_owner = multisig.owners.iter().any(|owner| owner == caller.key);
         require!(is_owner, MultisigError::OwnerNotFound);
 
-        multisig.paused = true;
-        multisig.paused_by = caller.key();
-        multisig.paused_at = Clock::get()?.unix_timestamp;
+        multisig.pause(scope.as_deref(), caller.key(), Clock::get()?.unix_timestamp)?;
+
+        ctx.accounts.audit_log.append(AuditAction::Paused, caller.key(), None)?;
 
         emit!(MultisigPaused {
           multisig: multisig.key(),
           paused_by: caller.key(),
+          scope: scope.clone(),
+          paused_types: multisig.paused_types.clone(),
           paused_at: multisig.paused_at,
         });
 
-        msg!("Multisig paused by {}", caller.key);
+        msg!("Multisig paused by {} for {:?}", caller.key, scope);
+        Ok(())
+    }
+
+/// Backfills the audit log for multisigs created before it existed.
+pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        let audit_log = &mut ctx.accounts.audit_log;
+        audit_log.multisig = ctx.accounts.multisig.key();
+        audit_log.head = 0;
+        audit_log.total_records = 0;
+        audit_log.bump = ctx.bumps.audit_log;
+        audit_log.records = Vec::new();
+
+        msg!("Audit log initialized for multisig {}", audit_log.multisig);
         Ok(())
-    }
\ No newline at end of file
+    }
+
+/// Rewrites a multisig created by the baseline program into the current
+/// layout so it deserializes again, e.g. for `initialize_audit_log`.
+/// Permissionless: the new settings take defaults that keep the multisig
+/// behaving as before. A no-op for accounts already migrated.
+pub fn migrate_multisig(ctx: Context<MigrateMultisig>) -> Result<()> {
+        let account = &ctx.accounts.multisig;
+        let Some(multisig) = Multisig::migrate(&account.try_borrow_data()?)? else {
+            return Ok(());
+        };
+
+        grow_account(account, &ctx.accounts.payer, &ctx.accounts.system_program, 8 + Multisig::INIT_SPACE)?;
+        multisig.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        msg!("Migrated multisig {}", account.key());
+        Ok(())
+    }
+
+/// Reallocates `account` to at least `len` bytes, topping up its rent from
+/// `payer`. The new bytes are zero, as fresh realloc space always is.
+pub(crate) fn grow_account<'info>(account: &AccountInfo<'info>, payer: &Signer<'info>, system_program: &Program<'info, System>, len: usize) -> Result<()> {
+    if account.data_len() >= len {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                }
+            ),
+            shortfall
+        )?;
+    }
+    account.realloc(len, false)?;
+    Ok(())
+}
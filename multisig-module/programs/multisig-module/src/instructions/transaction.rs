@@ -1,21 +1,114 @@
 use anchor_lang::{prelude::*};
+use anchor_lang::system_program::{self, Transfer};
 use crate::{
-    TransactionType, MultisigError,
-    TransactionProposed, TransactionApproved, TransactionExecuted,
-    ProposeTransaction, ApproveTransaction, ExecuteTransaction,
+    Multisig, Transaction, AuditLog, AuditAction, TransactionType, ExecutionSchedule, ProposalExpiry, ProposalMetadata, MultisigError,
+    NO_EXPIRY,
+    TransactionProposed, TransactionApproved, TransactionRejected, TransactionExecuted, ScheduledTransactionExecuted,
+    ProposeTransaction, ApproveTransaction, RejectTransaction, ExecuteTransaction, ExecuteScheduledTransaction,
+    MigrateTransaction,
     calculate_instruction_complexity
 };
+use super::multisig::grow_account;
 
 pub fn propose_transaction(
         ctx: Context<ProposeTransaction>,
         instruction_data: Vec<u8>,
         nonce: u64,
         transaction_type: TransactionType,
-        expires_in_hours: Option<u8>,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
     ) -> Result<()> {
-        let multisig = &mut ctx.accounts.multisig;
-        let transaction = &mut ctx.accounts.transaction;
-        let proposer = &ctx.accounts.proposer;
+        require!(
+            !transaction_type.is_structured(),
+            MultisigError::InvalidTransactionType
+        );
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type,
+                expiry,
+                schedule: None,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+    pub fn propose_scheduled_transaction(
+        ctx: Context<ProposeTransaction>,
+        instruction_data: Vec<u8>,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        schedule: ExecutionSchedule,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        require!(!transaction_type.is_admin(), MultisigError::InvalidTransactionType);
+        schedule.validate(Clock::get()?.unix_timestamp)?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type,
+                expiry,
+                schedule: Some(schedule),
+                metadata: metadata.unwrap_or_default(),
+                vault_message: false,
+                lifts_pause: false,
+            },
+        )
+    }
+
+    /// Proposal parameters common to every proposal instruction.
+    pub(crate) struct NewTransaction {
+        pub instruction_data: Vec<u8>,
+        pub nonce: u64,
+        pub transaction_type: TransactionType,
+        pub expiry: Option<ProposalExpiry>,
+        pub schedule: Option<ExecutionSchedule>,
+        pub metadata: ProposalMetadata,
+        pub vault_message: bool,
+        /// Lets the proposal through while its type is paused
+        pub lifts_pause: bool,
+    }
+
+    /// Shared by every proposal instruction: enforces the rate limit, nonce and
+    /// ownership checks, initializes the transaction account and locks the
+    /// multisig's proposal deposit in it.
+    pub(crate) fn open_transaction<'info>(
+        multisig: &mut Account<'info, Multisig>,
+        transaction: &mut Account<'info, Transaction>,
+        audit_log: &mut Account<'info, AuditLog>,
+        proposer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+        new_transaction: NewTransaction,
+    ) -> Result<()> {
+        let NewTransaction {
+            instruction_data,
+            nonce,
+            transaction_type,
+            expiry,
+            schedule,
+            metadata,
+            vault_message,
+            lifts_pause,
+        } = new_transaction;
 
         let clock = Clock::get()?;
         require!(
@@ -26,21 +119,32 @@ pub fn propose_transaction(
         require!(nonce == multisig.nonce, MultisigError::InvalidNonce);
         multisig.nonce = multisig.nonce.checked_add(1).ok_or(MultisigError::NonceOverflow)?;
 
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(
+            lifts_pause || !multisig.is_paused_for_transaction(&transaction_type, vault_message),
+            MultisigError::MultisigPaused
+        );
         multisig.validate_state()?;
 
         require!(!instruction_data.is_empty(), MultisigError::EmptyTransaction);
         require!(instruction_data.len() <= 1000, MultisigError::TransactionTooLarge);
+        metadata.validate()?;
 
-        let complexity_score = calculate_instruction_complexity(&instruction_data)?;
-        require!(complexity_score <= 100, MultisigError::TransactionTooComplex);
+        // Structured payloads are validated by their own instructions
+        let structured = vault_message || transaction_type.is_structured();
+        if !structured {
+            let complexity_score = calculate_instruction_complexity(&instruction_data)?;
+            require!(complexity_score <= 100, MultisigError::TransactionTooComplex);
+        }
 
         let is_owner = multisig.owners.iter().any(|owner| owner == proposer.key);
         require!(is_owner, MultisigError::OwnerNotFound);
 
         let current_transaction_id = multisig.transaction_count;
-        let expiration_hours = expires_in_hours.unwrap_or(72);
-        let expires_at = clock.unix_timestamp + (expiration_hours as i64 * 3600);
+        // A schedule keeps the proposal alive until its last run is due
+        let expires_from = schedule
+            .as_ref()
+            .map_or(clock.unix_timestamp, |schedule| schedule.last_due_at());
+        let expires_at = multisig.expiry_config.resolve(expiry, expires_from)?;
 
         transaction.multisig = multisig.key();
         transaction.proposer = proposer.key();
@@ -48,16 +152,39 @@ pub fn propose_transaction(
         transaction.transaction_id = current_transaction_id;
         transaction.executed = false;
         transaction.created_at = clock.unix_timestamp;
-        transaction.expires_at = expires_at;
+        transaction.expires_at = expires_at.unwrap_or(NO_EXPIRY);
         transaction.transaction_type = transaction_type.clone();
         transaction.approvals = vec![false; multisig.owners.len()];
         transaction.created_slot = clock.slot;
+        transaction.schedule = schedule.clone();
+        transaction.runs_executed = 0;
+        transaction.metadata = metadata.clone();
+        transaction.vault_message = vault_message;
+        transaction.rejections = vec![false; multisig.owners.len()];
+        transaction.rejected = false;
+
+        let deposit = multisig.proposal_deposit;
+        if deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    Transfer {
+                        from: proposer.to_account_info(),
+                        to: transaction.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+        transaction.deposit = deposit;
 
         multisig.transaction_count = multisig.transaction_count
             .checked_add(1)
             .ok_or(MultisigError::TransactionCountOverflow)?;
         multisig.last_proposal_slot = clock.slot;
 
+        audit_log.append(AuditAction::TransactionProposed, proposer.key(), Some(current_transaction_id))?;
+
         emit!(TransactionProposed {
           multisig: multisig.key(),
           transaction: transaction.key(),
@@ -66,10 +193,13 @@ pub fn propose_transaction(
           transaction_type: transaction_type.clone(),
           expires_at,
           created_at: clock.unix_timestamp,
+          schedule,
+          metadata,
+          deposit,
         });
 
         msg!(
-            "Transaction {} of type {:?} proposed by {} with {} bytes of data, expires at {}", 
+            "Transaction {} of type {:?} proposed by {} with {} bytes of data, expires at {:?}", 
             current_transaction_id,
             transaction_type,
             proposer.key(),
@@ -87,16 +217,35 @@ pub fn propose_transaction(
         let multisig = &ctx.accounts.multisig;
         let transaction = &mut ctx.accounts.transaction;
 
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(
+            transaction.lifts_pause()
+                || !multisig.is_paused_for_transaction(&transaction.transaction_type, transaction.vault_message),
+            MultisigError::MultisigPaused
+        );
         multisig.validate_state()?;
         transaction.validate_state(multisig)?;
         require!(!transaction.executed, MultisigError::AlreadyExecuted);
         require!(transaction.transaction_id == transaction_id ,MultisigError::InvalidTransactionId
         );
 
+        // A delegate approves in the slot of the owner that registered it
+        let (approving_owner, delegate) = match &ctx.accounts.delegation {
+            Some(delegation) => {
+                require!(delegation.multisig == multisig.key(), MultisigError::InvalidDelegate);
+                require!(delegation.delegate == approver.key(), MultisigError::UnauthorizedDelegate);
+                require!(!delegation.is_expired()?, MultisigError::DelegateExpired);
+                require!(
+                    delegation.allows(transaction),
+                    MultisigError::DelegateScopeViolation
+                );
+                (delegation.owner, Some(approver.key()))
+            }
+            None => (approver.key(), None),
+        };
+
         let owner_index = multisig.owners
             .iter()
-            .position(|owner| owner == approver.key)
+            .position(|owner| *owner == approving_owner)
             .ok_or(MultisigError::OwnerNotFound)?;
 
         require!(
@@ -111,14 +260,21 @@ pub fn propose_transaction(
         require!(!transaction.approvals[owner_index], MultisigError::AlreadyApproved);
 
         transaction.approvals[owner_index] = true;
+        // Approving withdraws an earlier rejection by the same owner
+        if let Some(rejection) = transaction.rejections.get_mut(owner_index) {
+            *rejection = false;
+        }
 
         let approval_count = transaction.approvals.iter().filter(|&&approved| approved).count();
 
+        ctx.accounts.audit_log.append(AuditAction::TransactionApproved, approver.key(), Some(transaction_id))?;
+
 
         emit!(TransactionApproved {
           multisig: multisig.key(),
           transaction: transaction.key(),
-          approver: approver.key(),
+          approver: approving_owner,
+          delegate,
           transaction_id,
           approval_count: approval_count as u8,
           required_approvals: multisig.threshold,
@@ -127,12 +283,67 @@ pub fn propose_transaction(
         msg!(
         "Transaction {} approved by {}. Approvals: {}/{}",
         transaction_id,
-        approver.key,
+        approving_owner,
         approval_count,
         multisig.threshold
         );Ok(())
     }
 
+    /// Records an owner's rejection. Once the remaining owners can no longer
+    /// reach the threshold the proposal is marked rejected, which blocks
+    /// execution and forfeits its deposit to the vault.
+    pub fn reject_transaction(
+        ctx: Context<RejectTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        let owner = &ctx.accounts.owner;
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+
+        let owner_index = multisig.owners
+            .iter()
+            .position(|existing| existing == owner.key)
+            .ok_or(MultisigError::OwnerNotFound)?;
+        require!(
+            transaction.rejections.len() == multisig.owners.len(),
+            MultisigError::ApprovalArrayMismatch
+        );
+        require!(!transaction.rejections[owner_index], MultisigError::AlreadyRejected);
+
+        transaction.rejections[owner_index] = true;
+        transaction.approvals[owner_index] = false;
+        transaction.rejected = transaction.threshold_unreachable(multisig);
+
+        let rejection_count = transaction.rejection_count();
+
+        ctx.accounts.audit_log.append(AuditAction::TransactionRejected, owner.key(), Some(transaction_id))?;
+
+        emit!(TransactionRejected {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          owner: owner.key(),
+          transaction_id,
+          rejection_count: rejection_count as u8,
+          rejected: transaction.rejected,
+        });
+
+        msg!(
+            "Transaction {} rejected by {}. Rejections: {}/{}, rejected: {}",
+            transaction_id,
+            owner.key(),
+            rejection_count,
+            multisig.owners.len(),
+            transaction.rejected
+        );
+        Ok(())
+    }
+
 
     pub fn execute_transaction(
         ctx: Context<ExecuteTransaction>,
@@ -143,11 +354,17 @@ pub fn propose_transaction(
         let transaction = &mut ctx.accounts.transaction;
 
         
-        require!(!multisig.paused, MultisigError::MultisigPaused);
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
         require!(!transaction.executed, MultisigError::AlreadyExecuted);
         multisig.validate_state()?;
         transaction.validate_state(multisig)?;
         require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(
+            !transaction.transaction_type.is_structured(),
+            MultisigError::InvalidTransactionType
+        );
+        require!(transaction.schedule.is_none(), MultisigError::ScheduledTransaction);
+        require!(!transaction.vault_message, MultisigError::VaultTransaction);
 
         let clock = Clock::get()?;
         require!(
@@ -158,10 +375,7 @@ pub fn propose_transaction(
         let is_owner = multisig.owners.iter().any(|owner| owner == executor.key);
         require!(is_owner, MultisigError::OwnerNotFound);
 
-        let required_approvals = match transaction.transaction_type {
-            TransactionType::AdminAction => multisig.admin_threshold,
-            _ => multisig.threshold,
-        };
+        let required_approvals = multisig.required_approvals(&transaction.transaction_type);
 
         let approval_count = transaction.approval_count() as u8;
 
@@ -170,6 +384,8 @@ pub fn propose_transaction(
 
         transaction.executed = true;
 
+        ctx.accounts.audit_log.append(AuditAction::TransactionExecuted, executor.key(), Some(transaction_id))?;
+
         emit!(TransactionExecuted {
           multisig: multisig.key(),
           transaction: transaction.key(),
@@ -190,4 +406,84 @@ pub fn propose_transaction(
         );
 
         Ok(())
-    }
\ No newline at end of file
+    }
+
+    pub fn execute_scheduled_transaction(
+        ctx: Context<ExecuteScheduledTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        let cranker = &ctx.accounts.cranker;
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+
+        require!(!multisig.is_paused_for(&transaction.transaction_type), MultisigError::MultisigPaused);
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+
+        require!(!transaction.vault_message, MultisigError::VaultTransaction);
+
+        let schedule = transaction.schedule.clone().ok_or(MultisigError::NotScheduled)?;
+        let total_runs = schedule.total_runs();
+        let run = transaction.runs_executed;
+        require!(run < total_runs, MultisigError::ScheduleCompleted);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.slot > transaction.created_slot + 1,
+            MultisigError::SameSlotExecution
+        );
+
+        let due_at = schedule.due_at(run).ok_or(MultisigError::InvalidSchedule)?;
+        require!(clock.unix_timestamp >= due_at, MultisigError::ScheduleNotDue);
+
+        let approval_count = transaction.approval_count() as u8;
+        require!(approval_count >= multisig.threshold, MultisigError::NotEnoughApprovals);
+
+        transaction.runs_executed = run + 1;
+        if transaction.runs_executed == total_runs {
+            transaction.executed = true;
+        }
+
+        ctx.accounts.audit_log.append(AuditAction::ScheduledRunExecuted, cranker.key(), Some(transaction_id))?;
+
+        emit!(ScheduledTransactionExecuted {
+          multisig: multisig.key(),
+          transaction: transaction.key(),
+          cranker: cranker.key(),
+          transaction_id,
+          transaction_type: transaction.transaction_type.clone(),
+          run_index: run,
+          total_runs,
+          due_at,
+          executed_at: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Scheduled transaction {} run {}/{} (due at {}) executed by {}",
+            transaction_id,
+            run + 1,
+            total_runs,
+            due_at,
+            cranker.key
+        );
+
+        Ok(())
+    }
+
+/// Rewrites a transaction proposed under the baseline program into the
+/// current layout so its proposal can still be approved and executed.
+/// Permissionless and a no-op for accounts already migrated.
+pub fn migrate_transaction(ctx: Context<MigrateTransaction>) -> Result<()> {
+        let account = &ctx.accounts.transaction;
+        let Some(transaction) = Transaction::migrate(&account.try_borrow_data()?)? else {
+            return Ok(());
+        };
+
+        grow_account(account, &ctx.accounts.payer, &ctx.accounts.system_program, 8 + Transaction::INIT_SPACE)?;
+        transaction.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+        msg!("Migrated transaction {} of multisig {}", transaction.transaction_id, transaction.multisig);
+        Ok(())
+    }
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use solana_address_lookup_table_interface::{program as address_lookup_table, state::AddressLookupTable};
+use crate::{
+    VaultMessage, ExecutionSchedule, ProposalExpiry, ProposalMetadata, AuditAction, TransactionType,
+    MultisigError,
+    VaultTransactionExecuted, ScheduledTransactionExecuted,
+    ProposeTransaction, ExecuteVaultTransaction,
+};
+use super::transaction::{open_transaction, NewTransaction};
+
+pub fn propose_vault_transaction(
+        ctx: Context<ProposeTransaction>,
+        message: VaultMessage,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        open_vault_transaction(ctx, message, nonce, transaction_type, expiry, None, metadata)
+    }
+
+/// Approved once, the message then runs once per due time of the schedule,
+/// e.g. a monthly payroll transfer. Anyone may crank a due run.
+pub fn propose_scheduled_vault_transaction(
+        ctx: Context<ProposeTransaction>,
+        message: VaultMessage,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        schedule: ExecutionSchedule,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        schedule.validate(Clock::get()?.unix_timestamp)?;
+        open_vault_transaction(ctx, message, nonce, transaction_type, expiry, Some(schedule), metadata)
+    }
+
+fn open_vault_transaction(
+        ctx: Context<ProposeTransaction>,
+        message: VaultMessage,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        schedule: Option<ExecutionSchedule>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        require!(!transaction_type.is_admin(), MultisigError::InvalidTransactionType);
+        message.validate()?;
+
+        // Lookup addresses are pinned now so approvers sign off on fixed keys
+        let (vault, _) = Pubkey::find_program_address(
+            &[b"vault", ctx.accounts.multisig.key().as_ref()],
+            &crate::ID,
+        );
+        require!(
+            ctx.remaining_accounts.len() == message.lookups.len(),
+            MultisigError::AccountMismatch
+        );
+        let keys = resolve_keys(&message, ctx.remaining_accounts, &vault)?;
+        let resolved_keys_hash = VaultMessage::keys_hash(&keys);
+
+        let instruction_data = message.try_to_vec()?;
+
+        let accounts = ctx.accounts;
+        open_transaction(
+            &mut accounts.multisig,
+            &mut accounts.transaction,
+            &mut accounts.audit_log,
+            &accounts.proposer,
+            &accounts.system_program,
+            NewTransaction {
+                instruction_data,
+                nonce,
+                transaction_type,
+                expiry,
+                schedule,
+                metadata: metadata.unwrap_or_default(),
+                vault_message: true,
+                lifts_pause: false,
+            },
+        )?;
+
+        accounts.transaction.resolved_keys_hash = resolved_keys_hash;
+        Ok(())
+    }
+
+/// Runs an approved vault message. Scheduled messages run once per call,
+/// only when their next run is due, and may be cranked by anyone.
+pub fn execute_vault_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteVaultTransaction<'info>>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        let executor = &ctx.accounts.executor;
+        let multisig = &ctx.accounts.multisig;
+        let transaction = &mut ctx.accounts.transaction;
+        let vault = &ctx.accounts.vault;
+
+        require!(
+            !multisig.is_paused_for_transaction(&transaction.transaction_type, true),
+            MultisigError::MultisigPaused
+        );
+        require!(!transaction.executed, MultisigError::AlreadyExecuted);
+        multisig.validate_state()?;
+        transaction.validate_state(multisig)?;
+        require!(transaction.transaction_id == transaction_id, MultisigError::InvalidTransactionId);
+        require!(transaction.vault_message, MultisigError::NotVaultTransaction);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.slot > transaction.created_slot + 1,
+            MultisigError::SameSlotExecution
+        );
+
+        // (run index, total runs, due time) of a scheduled message
+        let scheduled_run = match transaction.schedule.clone() {
+            Some(schedule) => {
+                let total_runs = schedule.total_runs();
+                let run = transaction.runs_executed;
+                require!(run < total_runs, MultisigError::ScheduleCompleted);
+                let due_at = schedule.due_at(run).ok_or(MultisigError::InvalidSchedule)?;
+                require!(clock.unix_timestamp >= due_at, MultisigError::ScheduleNotDue);
+                Some((run, total_runs, due_at))
+            }
+            None => {
+                let is_owner = multisig.owners.iter().any(|owner| owner == executor.key);
+                require!(is_owner, MultisigError::OwnerNotFound);
+                None
+            }
+        };
+
+        let required_approvals = multisig.required_approvals(&transaction.transaction_type);
+        let approval_count = transaction.approval_count() as u8;
+        require!(approval_count >= required_approvals, MultisigError::NotEnoughApprovals);
+
+        let message = VaultMessage::try_from_slice(&transaction.instruction_data)
+            .map_err(|_| MultisigError::InvalidVaultMessage)?;
+        message.validate()?;
+
+        require!(
+            ctx.remaining_accounts.len() >= message.lookups.len(),
+            MultisigError::AccountMismatch
+        );
+        let (table_accounts, message_accounts) = ctx.remaining_accounts.split_at(message.lookups.len());
+        let keys = resolve_keys(&message, table_accounts, &vault.key())?;
+        require!(
+            VaultMessage::keys_hash(&keys) == transaction.resolved_keys_hash,
+            MultisigError::LookupKeysChanged
+        );
+        require!(message_accounts.len() == keys.len(), MultisigError::AccountMismatch);
+
+        for (index, (key, account)) in keys.iter().zip(message_accounts).enumerate() {
+            require_keys_eq!(*key, account.key(), MultisigError::AccountMismatch);
+            // Readonly lookup addresses stay readonly even if passed writable
+            if !message.is_writable_index(index) {
+                continue;
+            }
+            let needs_write = message.instructions.iter().any(|instruction| {
+                instruction
+                    .accounts
+                    .iter()
+                    .any(|meta| meta.index as usize == index && meta.is_writable)
+            });
+            require!(!needs_write || account.is_writable, MultisigError::AccountNotWritable);
+        }
+
+        let multisig_key = multisig.key();
+        let vault_seeds: &[&[u8]] = &[b"vault", multisig_key.as_ref(), &[ctx.bumps.vault]];
+
+        for instruction in &message.instructions {
+            let program_index = instruction.program_id_index as usize;
+            let mut metas = Vec::with_capacity(instruction.accounts.len());
+            let mut infos = Vec::with_capacity(instruction.accounts.len() + 1);
+
+            for meta in &instruction.accounts {
+                let index = meta.index as usize;
+                if meta.is_signer {
+                    require_keys_eq!(keys[index], vault.key(), MultisigError::InvalidSigner);
+                }
+                metas.push(AccountMeta {
+                    pubkey: keys[index],
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                });
+                infos.push(message_accounts[index].clone());
+            }
+            infos.push(message_accounts[program_index].clone());
+
+            let ix = Instruction {
+                program_id: keys[program_index],
+                accounts: metas,
+                data: instruction.data.clone(),
+            };
+            invoke_signed(&ix, &infos, &[vault_seeds])?;
+        }
+
+        match scheduled_run {
+            Some((run, total_runs, due_at)) => {
+                transaction.runs_executed = run + 1;
+                transaction.executed = transaction.runs_executed == total_runs;
+
+                ctx.accounts.audit_log.append(AuditAction::ScheduledRunExecuted, executor.key(), Some(transaction_id))?;
+
+                emit!(ScheduledTransactionExecuted {
+                  multisig: multisig_key,
+                  transaction: transaction.key(),
+                  cranker: executor.key(),
+                  transaction_id,
+                  transaction_type: transaction.transaction_type.clone(),
+                  run_index: run,
+                  total_runs,
+                  due_at,
+                  executed_at: clock.unix_timestamp,
+                });
+            }
+            None => {
+                transaction.executed = true;
+
+                ctx.accounts.audit_log.append(AuditAction::VaultTransactionExecuted, executor.key(), Some(transaction_id))?;
+            }
+        }
+
+        emit!(VaultTransactionExecuted {
+          multisig: multisig_key,
+          transaction: transaction.key(),
+          executor: executor.key(),
+          vault: vault.key(),
+          transaction_id,
+          transaction_type: transaction.transaction_type.clone(),
+          approval_count,
+          instruction_count: message.instructions.len() as u8,
+          lookup_tables: message.lookups.iter().map(|lookup| lookup.table).collect(),
+          executed_at: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Vault transaction {} executed {} instructions with {} accounts from {} lookup tables. Had {}/{} approvals",
+            transaction_id,
+            message.instructions.len(),
+            keys.len(),
+            message.lookups.len(),
+            approval_count,
+            required_approvals
+        );
+        Ok(())
+    }
+
+/// Expands the message's key list in v0 order, loading lookup addresses
+/// from the provided table accounts. Only tables the vault controls are
+/// accepted, since their contents can only change through a proposal.
+fn resolve_keys(message: &VaultMessage, table_accounts: &[AccountInfo], vault: &Pubkey) -> Result<Vec<Pubkey>> {
+    let mut writable = Vec::new();
+    let mut readonly = Vec::new();
+
+    for (lookup, table_account) in message.lookups.iter().zip(table_accounts) {
+        require_keys_eq!(lookup.table, table_account.key(), MultisigError::InvalidLookupTable);
+        require_keys_eq!(*table_account.owner, address_lookup_table::ID, MultisigError::InvalidLookupTable);
+
+        let data = table_account.try_borrow_data()?;
+        let table = AddressLookupTable::deserialize(&data)
+            .map_err(|_| MultisigError::InvalidLookupTable)?;
+        require!(
+            table.meta.authority == Some(*vault),
+            MultisigError::LookupTableNotVaultOwned
+        );
+
+        for &index in &lookup.writable_indexes {
+            let address = table
+                .addresses
+                .get(index as usize)
+                .ok_or(MultisigError::LookupIndexOutOfBounds)?;
+            writable.push(*address);
+        }
+        for &index in &lookup.readonly_indexes {
+            let address = table
+                .addresses
+                .get(index as usize)
+                .ok_or(MultisigError::LookupIndexOutOfBounds)?;
+            readonly.push(*address);
+        }
+    }
+
+    let mut keys = message.account_keys.clone();
+    keys.extend(writable);
+    keys.extend(readonly);
+    Ok(keys)
+}
@@ -13,7 +13,7 @@ pub use events::*;
 pub use errors::*;
 pub use utils::*;
 
-use instructions::{multisig, transaction, admin};
+use instructions::{multisig, transaction, admin, config, delegate, deposit, vault, lookup_table, close};
 
 #[program]
 pub mod multisig_module {
@@ -29,8 +29,23 @@ pub mod multisig_module {
         multisig::create_multisig(ctx, owners, threshold, admin_threshold)
     }
 
-    pub fn emergency_pause(ctx: Context<EmergencyAction>) -> Result<()> {
-        multisig::emergency_pause(ctx)
+    pub fn emergency_pause(
+        ctx: Context<EmergencyAction>,
+        scope: Option<Vec<TransactionType>>,
+    ) -> Result<()> {
+        multisig::emergency_pause(ctx, scope)
+    }
+
+    pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        multisig::initialize_audit_log(ctx)
+    }
+
+    pub fn migrate_multisig(ctx: Context<MigrateMultisig>) -> Result<()> {
+        multisig::migrate_multisig(ctx)
+    }
+
+    pub fn migrate_transaction(ctx: Context<MigrateTransaction>) -> Result<()> {
+        transaction::migrate_transaction(ctx)
     }
 
     // Transaction functions
@@ -39,9 +54,22 @@ pub mod multisig_module {
         instruction_data: Vec<u8>,
         nonce: u64,
         transaction_type: TransactionType,
-        expires_in_hours: Option<u8>,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        transaction::propose_transaction(ctx, instruction_data, nonce, transaction_type, expiry, metadata)
+    }
+
+    pub fn propose_scheduled_transaction(
+        ctx: Context<ProposeTransaction>,
+        instruction_data: Vec<u8>,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        schedule: ExecutionSchedule,
+        metadata: Option<ProposalMetadata>,
     ) -> Result<()> {
-        transaction::propose_transaction(ctx, instruction_data, nonce, transaction_type, expires_in_hours)
+        transaction::propose_scheduled_transaction(ctx, instruction_data, nonce, transaction_type, expiry, schedule, metadata)
     }
 
     pub fn approve_transaction(
@@ -51,6 +79,20 @@ pub mod multisig_module {
         transaction::approve_transaction(ctx, transaction_id)
     }
 
+    pub fn reject_transaction(
+        ctx: Context<RejectTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        transaction::reject_transaction(ctx, transaction_id)
+    }
+
+    pub fn release_proposal_deposit(
+        ctx: Context<ReleaseProposalDeposit>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        deposit::release_proposal_deposit(ctx, transaction_id)
+    }
+
     pub fn execute_transaction(
         ctx: Context<ExecuteTransaction>,
         transaction_id: u64,
@@ -58,6 +100,135 @@ pub mod multisig_module {
         transaction::execute_transaction(ctx, transaction_id)
     }
 
+    pub fn execute_scheduled_transaction(
+        ctx: Context<ExecuteScheduledTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        transaction::execute_scheduled_transaction(ctx, transaction_id)
+    }
+
+    // Vault functions
+    pub fn propose_vault_transaction(
+        ctx: Context<ProposeTransaction>,
+        message: VaultMessage,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        vault::propose_vault_transaction(ctx, message, nonce, transaction_type, expiry, metadata)
+    }
+
+    pub fn propose_scheduled_vault_transaction(
+        ctx: Context<ProposeTransaction>,
+        message: VaultMessage,
+        nonce: u64,
+        transaction_type: TransactionType,
+        expiry: Option<ProposalExpiry>,
+        schedule: ExecutionSchedule,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        vault::propose_scheduled_vault_transaction(ctx, message, nonce, transaction_type, expiry, schedule, metadata)
+    }
+
+    pub fn execute_vault_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteVaultTransaction<'info>>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        vault::execute_vault_transaction(ctx, transaction_id)
+    }
+
+    // Address lookup table functions
+    pub fn create_lookup_table(
+        ctx: Context<CreateLookupTable>,
+        recent_slot: u64,
+    ) -> Result<()> {
+        lookup_table::create_lookup_table(ctx, recent_slot)
+    }
+
+    pub fn propose_lookup_table_transaction(
+        ctx: Context<ProposeTransaction>,
+        lookup_table: Pubkey,
+        action: LookupTableAction,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        lookup_table::propose_lookup_table_transaction(ctx, lookup_table, action, nonce, expiry, metadata)
+    }
+
+    pub fn extend_lookup_table(
+        ctx: Context<ExtendLookupTable>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        lookup_table::extend_lookup_table(ctx, transaction_id)
+    }
+
+    pub fn deactivate_lookup_table(
+        ctx: Context<DeactivateLookupTable>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        lookup_table::deactivate_lookup_table(ctx, transaction_id)
+    }
+
+    pub fn close_lookup_table(
+        ctx: Context<CloseLookupTable>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        lookup_table::close_lookup_table(ctx, transaction_id)
+    }
+
+    // Delegate functions
+    pub fn register_delegate(
+        ctx: Context<RegisterDelegate>,
+        delegate: Pubkey,
+        expires_at: i64,
+        scope: DelegateScope,
+    ) -> Result<()> {
+        delegate::register_delegate(ctx, delegate, expires_at, scope)
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        delegate::revoke_delegate(ctx)
+    }
+
+    // Config transaction functions
+    pub fn propose_config_transaction(
+        ctx: Context<ProposeConfigTransaction>,
+        actions: Vec<ConfigAction>,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        config::propose_config_transaction(ctx, actions, nonce, expiry, metadata)
+    }
+
+    pub fn execute_config_transaction(
+        ctx: Context<ExecuteConfigTransaction>,
+        transaction_id: u64,
+    ) -> Result<()> {
+        config::execute_config_transaction(ctx, transaction_id)
+    }
+
+    // Close functions
+    pub fn propose_close_multisig(
+        ctx: Context<ProposeTransaction>,
+        destination: Pubkey,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        close::propose_close_multisig(ctx, destination, nonce, expiry, metadata)
+    }
+
+    pub fn close_multisig<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseMultisig<'info>>,
+        transaction_id: u64,
+        token_account_count: u8,
+    ) -> Result<()> {
+        close::close_multisig(ctx, transaction_id, token_account_count)
+    }
+
     // Admin functions
     pub fn change_threshold(
         ctx: Context<ChangeThreshold>,
@@ -83,10 +254,51 @@ pub mod multisig_module {
         admin::remove_owner(ctx, transaction_id, owner_to_remove)
     }
 
+    pub fn propose_swap_owner(
+        ctx: Context<ProposeTransaction>,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        admin::propose_swap_owner(ctx, old_owner, new_owner, nonce, expiry, metadata)
+    }
+
+    pub fn swap_owner(
+        ctx: Context<SwapOwner>,
+        transaction_id: u64,
+        old_owner: Pubkey,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        admin::swap_owner(ctx, transaction_id, old_owner, new_owner)
+    }
+
+    pub fn propose_extend_expiry(
+        ctx: Context<ProposeTransaction>,
+        target_transaction_id: u64,
+        new_expiry: ProposalExpiry,
+        nonce: u64,
+        expiry: Option<ProposalExpiry>,
+        metadata: Option<ProposalMetadata>,
+    ) -> Result<()> {
+        admin::propose_extend_expiry(ctx, target_transaction_id, new_expiry, nonce, expiry, metadata)
+    }
+
+    pub fn extend_expiry(
+        ctx: Context<ExtendExpiry>,
+        transaction_id: u64,
+        target_transaction_id: u64,
+        new_expiry: ProposalExpiry,
+    ) -> Result<()> {
+        admin::extend_expiry(ctx, transaction_id, target_transaction_id, new_expiry)
+    }
+
     pub fn unpause(
         ctx: Context<UnpauseMultisig>,
         transaction_id: u64,
+        scope: Option<Vec<TransactionType>>,
     ) -> Result<()> {
-        admin::unpause(ctx, transaction_id)
+        admin::unpause(ctx, transaction_id, scope)
     }
 }
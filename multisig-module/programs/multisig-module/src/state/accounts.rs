@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use solana_address_lookup_table_interface::program as address_lookup_table;
 use crate::{ MultisigError};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
@@ -9,7 +10,359 @@ pub enum TransactionType {
     ChangeThreshold,
     AddOwner,
     RemoveOwner,
-    Custom
+    Custom,
+    SwapOwner,
+    Config,
+    ExtendExpiry,
+    LookupTable,
+    CloseMultisig,
+}
+
+impl TransactionType {
+    /// Types that govern the multisig itself. They cannot be scheduled or
+    /// carry a vault message.
+    pub fn is_admin(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::AdminAction
+                | TransactionType::ChangeThreshold
+                | TransactionType::AddOwner
+                | TransactionType::RemoveOwner
+                | TransactionType::SwapOwner
+                | TransactionType::Config
+                | TransactionType::ExtendExpiry
+                | TransactionType::LookupTable
+                | TransactionType::CloseMultisig
+        )
+    }
+
+    /// Types whose payload is proposed and executed by a dedicated
+    /// instruction rather than the generic propose/execute pair
+    pub fn is_structured(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::Config
+                | TransactionType::CloseMultisig
+                | TransactionType::SwapOwner
+                | TransactionType::ExtendExpiry
+                | TransactionType::LookupTable
+        )
+    }
+
+    /// Types whose proposals exist to move funds out of the vault
+    pub fn moves_funds(&self) -> bool {
+        matches!(self, TransactionType::Transfer | TransactionType::TokenTransfer)
+    }
+}
+
+/// Lifetime requested by a proposal. `None` at the call site means the
+/// multisig's default.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ProposalExpiry {
+    After { hours: u32 },
+    Never,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct ExpiryConfig {
+    /// Lifetime of proposals that do not request one
+    pub default_hours: u32,
+    /// Longest lifetime a proposal may request; `None` means no cap
+    pub max_hours: Option<u32>,
+    /// Whether proposals may opt out of expiry entirely
+    pub allow_no_expiry: bool,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            default_hours: 72,
+            max_hours: Some(u8::MAX as u32),
+            allow_no_expiry: false,
+        }
+    }
+}
+
+impl ExpiryConfig {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(max_hours) = self.max_hours {
+            require!(self.default_hours <= max_hours, MultisigError::InvalidExpiryConfig);
+        }
+        Ok(())
+    }
+
+    /// Turns a requested lifetime into an expiry timestamp counted from
+    /// `from`, or `None` for a proposal that never expires.
+    pub fn resolve(&self, expiry: Option<ProposalExpiry>, from: i64) -> Result<Option<i64>> {
+        match expiry.unwrap_or(ProposalExpiry::After { hours: self.default_hours }) {
+            ProposalExpiry::After { hours } => {
+                if let Some(max_hours) = self.max_hours {
+                    require!(hours <= max_hours, MultisigError::ExpiryTooLong);
+                }
+                let expires_at = from
+                    .checked_add(hours as i64 * 3600)
+                    .ok_or(MultisigError::ExpiryTooLong)?;
+                Ok(Some(expires_at))
+            }
+            ProposalExpiry::Never => {
+                require!(self.allow_no_expiry, MultisigError::NoExpiryNotAllowed);
+                Ok(None)
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub enum ConfigAction {
+    AddOwner { new_owner: Pubkey },
+    RemoveOwner { owner: Pubkey },
+    SwapOwner { old_owner: Pubkey, new_owner: Pubkey },
+    ChangeThreshold { new_threshold: u8 },
+    ChangeAdminThreshold { new_admin_threshold: u8 },
+    Unpause,
+    SetExpiryConfig { expiry_config: ExpiryConfig },
+    SetProposalDeposit { deposit: u64 },
+}
+
+/// `Transaction::expires_at` of a proposal that never expires
+pub const NO_EXPIRY: i64 = i64::MAX;
+
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_MEMO_LEN: usize = 256;
+pub const MAX_URI_LEN: usize = 200;
+
+/// Off-chain document backing a proposal, pinned by its content hash
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct ProposalDocument {
+    pub content_hash: [u8; 32],
+    #[max_len(200)]
+    pub uri: String,
+}
+
+/// Human context shown to approvers alongside the raw instruction data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct ProposalMetadata {
+    #[max_len(64)]
+    pub title: Option<String>,
+    #[max_len(256)]
+    pub memo: Option<String>,
+    pub document: Option<ProposalDocument>,
+}
+
+impl ProposalMetadata {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(title) = &self.title {
+            require!(title.len() <= MAX_TITLE_LEN, MultisigError::TitleTooLong);
+        }
+        if let Some(memo) = &self.memo {
+            require!(memo.len() <= MAX_MEMO_LEN, MultisigError::MemoTooLong);
+        }
+        if let Some(document) = &self.document {
+            require!(!document.uri.is_empty(), MultisigError::EmptyUri);
+            require!(document.uri.len() <= MAX_URI_LEN, MultisigError::UriTooLong);
+        }
+        Ok(())
+    }
+}
+
+pub const MAX_SCHEDULED_RUNS: u16 = 120;
+pub const MAX_SCHEDULE_TIMES: usize = 12;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub enum ExecutionSchedule {
+    /// `count` runs, the first at `start_at` and then every `interval_seconds`
+    Recurring {
+        start_at: i64,
+        interval_seconds: i64,
+        count: u16,
+    },
+    /// One run at each of the listed unix timestamps, in increasing order
+    At {
+        #[max_len(12)]
+        times: Vec<i64>,
+    },
+}
+
+impl ExecutionSchedule {
+    pub fn validate(&self, now: i64) -> Result<()> {
+        match self {
+            ExecutionSchedule::Recurring { start_at, interval_seconds, count } => {
+                require!(*start_at >= now, MultisigError::InvalidSchedule);
+                require!(*interval_seconds > 0, MultisigError::InvalidSchedule);
+                require!(*count > 0 && *count <= MAX_SCHEDULED_RUNS, MultisigError::InvalidSchedule);
+                let span = interval_seconds
+                    .checked_mul(*count as i64 - 1)
+                    .ok_or(MultisigError::InvalidSchedule)?;
+                start_at.checked_add(span).ok_or(MultisigError::InvalidSchedule)?;
+            }
+            ExecutionSchedule::At { times } => {
+                require!(!times.is_empty(), MultisigError::InvalidSchedule);
+                require!(times.len() <= MAX_SCHEDULE_TIMES, MultisigError::InvalidSchedule);
+                require!(times[0] >= now, MultisigError::InvalidSchedule);
+                for i in 1..times.len() {
+                    require!(times[i - 1] < times[i], MultisigError::InvalidSchedule);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn total_runs(&self) -> u16 {
+        match self {
+            ExecutionSchedule::Recurring { count, .. } => *count,
+            ExecutionSchedule::At { times } => times.len() as u16,
+        }
+    }
+
+    /// When run number `run` (zero based) becomes due
+    pub fn due_at(&self, run: u16) -> Option<i64> {
+        match self {
+            ExecutionSchedule::Recurring { start_at, interval_seconds, count } => {
+                if run >= *count {
+                    return None;
+                }
+                interval_seconds
+                    .checked_mul(run as i64)
+                    .and_then(|offset| start_at.checked_add(offset))
+            }
+            ExecutionSchedule::At { times } => times.get(run as usize).copied(),
+        }
+    }
+
+    pub fn last_due_at(&self) -> i64 {
+        self.due_at(self.total_runs().saturating_sub(1)).unwrap_or_default()
+    }
+}
+
+pub const MAX_VAULT_INSTRUCTIONS: usize = 8;
+pub const MAX_VAULT_LOOKUPS: usize = 4;
+pub const MAX_VAULT_ACCOUNTS: usize = 64;
+
+/// Account reference inside a `VaultInstruction`. `index` points into the
+/// message's combined key list, see `VaultMessage`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultAccountMeta {
+    pub index: u8,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<VaultAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Addresses loaded from one address lookup table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultLookup {
+    pub table: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// Change to a vault-owned lookup table, approved through a `LookupTable`
+/// proposal together with the table it applies to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum LookupTableAction {
+    Extend { new_addresses: Vec<Pubkey> },
+    Deactivate,
+    Close,
+}
+
+/// Instructions executed by the vault PDA once a proposal is approved.
+///
+/// Keys are indexed like a v0 message: `account_keys` first, then the
+/// writable addresses of every lookup in order, then the readonly ones.
+/// Program ids must come from `account_keys`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultMessage {
+    pub account_keys: Vec<Pubkey>,
+    pub lookups: Vec<VaultLookup>,
+    pub instructions: Vec<VaultInstruction>,
+}
+
+impl VaultMessage {
+    pub fn writable_lookup_count(&self) -> usize {
+        self.lookups.iter().map(|lookup| lookup.writable_indexes.len()).sum()
+    }
+
+    pub fn key_count(&self) -> usize {
+        let readonly: usize = self.lookups.iter().map(|lookup| lookup.readonly_indexes.len()).sum();
+        self.account_keys.len() + self.writable_lookup_count() + readonly
+    }
+
+    /// Commits to a resolved key list, lookup addresses included
+    pub fn keys_hash(keys: &[Pubkey]) -> [u8; 32] {
+        let keys: Vec<&[u8]> = keys.iter().map(|key| key.as_ref()).collect();
+        anchor_lang::solana_program::hash::hashv(&keys).to_bytes()
+    }
+
+    /// Readonly lookup addresses can never be passed as writable
+    pub fn is_writable_index(&self, index: usize) -> bool {
+        index < self.account_keys.len() + self.writable_lookup_count()
+    }
+
+    /// Lamports moved by the System transfers and account creations the
+    /// vault signs. `None` when a signed instruction's value can't be read
+    /// from the message, such as a CPI into any other program.
+    pub fn signed_lamports(&self) -> Option<u64> {
+        let mut total: u64 = 0;
+        for instruction in &self.instructions {
+            if !instruction.accounts.iter().any(|meta| meta.is_signer) {
+                continue;
+            }
+            let program_id = self.account_keys.get(instruction.program_id_index as usize)?;
+            if *program_id != System::id() {
+                return None;
+            }
+            // CreateAccount (0), Transfer (2) and TransferWithSeed (11) all
+            // carry the lamports right after the u32 discriminant
+            let tag = u32::from_le_bytes(instruction.data.get(..4)?.try_into().ok()?);
+            if !matches!(tag, 0 | 2 | 11) {
+                return None;
+            }
+            let lamports = u64::from_le_bytes(instruction.data.get(4..12)?.try_into().ok()?);
+            total = total.checked_add(lamports)?;
+        }
+        Some(total)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.instructions.is_empty() && self.instructions.len() <= MAX_VAULT_INSTRUCTIONS,
+            MultisigError::InvalidVaultMessage
+        );
+        require!(self.lookups.len() <= MAX_VAULT_LOOKUPS, MultisigError::InvalidVaultMessage);
+        for lookup in &self.lookups {
+            require!(
+                !lookup.writable_indexes.is_empty() || !lookup.readonly_indexes.is_empty(),
+                MultisigError::InvalidVaultMessage
+            );
+        }
+
+        let key_count = self.key_count();
+        require!(key_count <= MAX_VAULT_ACCOUNTS, MultisigError::InvalidVaultMessage);
+
+        for instruction in &self.instructions {
+            let program_id = self
+                .account_keys
+                .get(instruction.program_id_index as usize)
+                .ok_or(MultisigError::InvalidVaultMessage)?;
+            require!(*program_id != crate::ID, MultisigError::VaultReentrancy);
+
+            for meta in &instruction.accounts {
+                let index = meta.index as usize;
+                require!(index < key_count, MultisigError::InvalidVaultMessage);
+                require!(
+                    !meta.is_writable || self.is_writable_index(index),
+                    MultisigError::InvalidVaultMessage
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 
@@ -28,6 +381,15 @@ pub struct CreateMultisig<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -46,6 +408,13 @@ pub struct ProposeTransaction<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
     #[account(
         init,
         payer = proposer,
@@ -63,6 +432,37 @@ pub struct ProposeTransaction<'info> {
 
 }
 
+#[derive(Accounts)]
+pub struct ProposeConfigTransaction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &multisig.transaction_count.to_le_bytes()
+        ],
+        bump,
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(transaction_id: u64)]
 pub struct ApproveTransaction<'info> {
@@ -71,6 +471,13 @@ pub struct ApproveTransaction<'info> {
 
     pub multisig: Account<'info, Multisig>,
 
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
     #[account(
         mut,
         seeds = [
@@ -82,6 +489,77 @@ pub struct ApproveTransaction<'info> {
         constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
     )]
     pub transaction: Account<'info, Transaction>,
+
+    /// Present when `approver` signs as an owner's delegate
+    #[account(
+        seeds = [b"delegation", multisig.key().as_ref(), delegation.owner.as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Option<Account<'info, Delegation>>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct RejectTransaction<'info> {
+    pub owner: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct ReleaseProposalDeposit<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: refund recipient, pinned to the proposer
+    #[account(mut, address = transaction.proposer @ MultisigError::InvalidDestination)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: receives the deposit of rejected proposals
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
@@ -92,6 +570,279 @@ pub struct ExecuteTransaction<'info> {
 
     pub multisig: Account<'info, Multisig>,
 
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct ExecuteScheduledTransaction<'info> {
+    pub cranker: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct ExecuteConfigTransaction<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct ExecuteVaultTransaction<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"transaction",
+            multisig.key().as_ref(),
+            &transaction_id.to_le_bytes()
+        ],
+        bump,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: PDA that signs the message's instructions; holds no data
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+    // remaining_accounts: the message's lookup tables in order, followed by
+    // every key of the message in index order
+}
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    /// CHECK: lookup table authority, never signs at creation
+    #[account(
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: derived from the vault and `recent_slot` in the handler and
+    /// initialized by the address lookup table program
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address checked
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct ExtendLookupTable<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = transaction.transaction_id == transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: lookup table authority
+    #[account(
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: owner checked; the lookup table program checks the authority
+    #[account(mut, owner = address_lookup_table::ID @ MultisigError::InvalidLookupTable)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address checked
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct DeactivateLookupTable<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = transaction.transaction_id == transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: lookup table authority
+    #[account(
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: owner checked; the lookup table program checks the authority
+    #[account(mut, owner = address_lookup_table::ID @ MultisigError::InvalidLookupTable)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address checked
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct CloseLookupTable<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = transaction.transaction_id == transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: lookup table authority; receives the reclaimed rent
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: owner checked; the lookup table program checks the authority
+    #[account(mut, owner = address_lookup_table::ID @ MultisigError::InvalidLookupTable)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: address checked
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64)]
+pub struct CloseMultisig<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(mut, close = destination)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+        close = destination,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
     #[account(
         mut,
         seeds = [
@@ -100,9 +851,33 @@ pub struct ExecuteTransaction<'info> {
             &transaction_id.to_le_bytes()
         ],
         bump,
-        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        close = destination,
     )]
     pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: PDA whose SOL and token balances are swept
+    #[account(
+        mut,
+        seeds = [b"vault", multisig.key().as_ref()],
+        bump,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: checked against the destination stored in the proposal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: refund recipient for this proposal's deposit, pinned to its proposer
+    #[account(mut, address = transaction.proposer @ MultisigError::InvalidDestination)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: `token_account_count` groups of
+    // [vault token account, mint, destination token account, token program],
+    // then every other Transaction of the multisig in id order, each followed by
+    // its proposer while it holds a refundable deposit, then any Delegation
+    // accounts to close.
 }
 
 #[derive(Accounts)]
@@ -110,6 +885,13 @@ pub struct ExecuteTransaction<'info> {
 pub struct ChangeThreshold<'info> {
     #[account(mut)]
     pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
     
     #[account(
         mut,
@@ -124,6 +906,13 @@ pub struct ChangeThreshold<'info> {
 pub struct AddOwner<'info> {
     #[account(mut)]
     pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
     
     #[account(
         mut,
@@ -138,6 +927,34 @@ pub struct AddOwner<'info> {
 pub struct RemoveOwner<'info> {
     #[account(mut)]
     pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+    
+    #[account(
+        mut,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = transaction.transaction_id == transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub transaction: Account<'info, Transaction>,
+}
+
+#[derive(Accounts)]
+#[instruction(transaction_id: u64, old_owner: Pubkey, new_owner: Pubkey)]
+pub struct SwapOwner<'info> {
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
     
     #[account(
         mut,
@@ -147,11 +964,45 @@ pub struct RemoveOwner<'info> {
     pub transaction: Account<'info, Transaction>,
 }
 
+#[derive(Accounts)]
+#[instruction(transaction_id: u64, target_transaction_id: u64)]
+pub struct ExtendExpiry<'info> {
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        constraint = transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = transaction.transaction_id == transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        constraint = target_transaction.multisig == multisig.key() @ MultisigError::InvalidTransaction,
+        constraint = target_transaction.transaction_id == target_transaction_id @ MultisigError::InvalidTransactionId
+    )]
+    pub target_transaction: Account<'info, Transaction>,
+}
+
 #[derive(Accounts)]
 #[instruction(transaction_id: u64)]
 pub struct UnpauseMultisig<'info> {
     #[account(mut)]
     pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
     
     #[account(
         mut,
@@ -161,6 +1012,102 @@ pub struct UnpauseMultisig<'info> {
     pub transaction: Account<'info, Transaction>,
 }
 
+#[derive(Accounts)]
+pub struct RegisterDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Delegation::INIT_SPACE,
+        seeds = [b"delegation", multisig.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        seeds = [b"delegation", multisig.key().as_ref(), owner.key().as_ref()],
+        bump = delegation.bump,
+        close = owner,
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAuditLog<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AuditLog::INIT_SPACE,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMultisig<'info> {
+    /// CHECK: a multisig that may still have the baseline layout; checked by
+    /// discriminator in the handler
+    #[account(mut, owner = crate::ID)]
+    pub multisig: UncheckedAccount<'info>,
+
+    /// Funds the rent of the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTransaction<'info> {
+    /// CHECK: a transaction that may still have the baseline layout; checked
+    /// by discriminator in the handler
+    #[account(mut, owner = crate::ID)]
+    pub transaction: UncheckedAccount<'info>,
+
+    /// Funds the rent of the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct EmergencyAction<'info> {
     #[account(mut)]
@@ -168,6 +1115,13 @@ pub struct EmergencyAction<'info> {
     
     #[account(mut)]
     pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log", multisig.key().as_ref()],
+        bump = audit_log.bump,
+    )]
+    pub audit_log: Account<'info, AuditLog>,
 }
 
 #[account]
@@ -185,6 +1139,12 @@ pub struct Multisig {
     pub created_at: i64,
     pub nonce: u64,
     pub last_proposal_slot: u64,
+    pub expiry_config: ExpiryConfig,
+    /// Transaction types paused individually; `paused` pauses every type
+    #[max_len(16)]
+    pub paused_types: Vec<TransactionType>,
+    /// Lamports locked by the proposer with every new proposal; 0 disables it
+    pub proposal_deposit: u64,
 }
 impl Multisig {
     pub fn validate_state(&self) -> Result<()> {
@@ -202,15 +1162,229 @@ impl Multisig {
             require!(sorted_owners[i-1] != sorted_owners[i], MultisigError::DuplicateOwners);
             require!(sorted_owners[i] != Pubkey::default(), MultisigError::InvalidOwner);
         }
+
+        self.expiry_config.validate()?;
+        require!(self.paused_types.len() <= MAX_PAUSED_TYPES, MultisigError::InvalidPauseScope);
+        Ok(())
+    }
+
+    /// Reads a multisig in the baseline layout and fills the fields added
+    /// since with their defaults: the default expiry policy, no paused types
+    /// and no proposal deposit. `None` for an account already in the
+    /// current layout.
+    pub fn migrate(data: &[u8]) -> Result<Option<Multisig>> {
+        require!(
+            data.len() >= 8 && data[..8] == *Multisig::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        // Baseline accounts were allocated at their old, smaller fixed size
+        if data.len() >= 8 + Multisig::INIT_SPACE {
+            return Ok(None);
+        }
+        let legacy = LegacyMultisig::deserialize(&mut &data[8..])?;
+        Ok(Some(Multisig {
+            owners: legacy.owners,
+            threshold: legacy.threshold,
+            admin_threshold: legacy.admin_threshold,
+            transaction_count: legacy.transaction_count,
+            bump: legacy.bump,
+            paused: legacy.paused,
+            paused_by: legacy.paused_by,
+            paused_at: legacy.paused_at,
+            created_at: legacy.created_at,
+            nonce: legacy.nonce,
+            last_proposal_slot: legacy.last_proposal_slot,
+            expiry_config: ExpiryConfig::default(),
+            paused_types: Vec::new(),
+            proposal_deposit: 0,
+        }))
+    }
+
+    pub fn is_paused_for(&self, transaction_type: &TransactionType) -> bool {
+        self.paused || self.paused_types.contains(transaction_type)
+    }
+
+    /// A vault message can move funds whatever type it was proposed under,
+    /// so it also stops while any fund-moving type is paused.
+    pub fn is_paused_for_transaction(&self, transaction_type: &TransactionType, vault_message: bool) -> bool {
+        self.is_paused_for(transaction_type)
+            || (vault_message && self.paused_types.iter().any(TransactionType::moves_funds))
+    }
+
+    /// Approvals an executor requires for a transaction of this type: the
+    /// admin threshold for types that change the owners, the configuration
+    /// or the multisig's existence, the plain threshold otherwise
+    pub fn required_approvals(&self, transaction_type: &TransactionType) -> u8 {
+        match transaction_type {
+            TransactionType::AdminAction
+            | TransactionType::ChangeThreshold
+            | TransactionType::AddOwner
+            | TransactionType::RemoveOwner
+            | TransactionType::SwapOwner
+            | TransactionType::Config
+            | TransactionType::CloseMultisig => self.admin_threshold,
+            _ => self.threshold,
+        }
+    }
+
+    pub fn has_pause(&self) -> bool {
+        self.paused || !self.paused_types.is_empty()
+    }
+
+    /// `None` pauses everything; `Some` adds the listed types to the paused set.
+    pub fn pause(&mut self, scope: Option<&[TransactionType]>, paused_by: Pubkey, paused_at: i64) -> Result<()> {
+        match scope {
+            None => self.paused = true,
+            Some(types) => {
+                require!(!types.is_empty(), MultisigError::InvalidPauseScope);
+                for transaction_type in types {
+                    if !self.paused_types.contains(transaction_type) {
+                        self.paused_types.push(transaction_type.clone());
+                    }
+                }
+                require!(self.paused_types.len() <= MAX_PAUSED_TYPES, MultisigError::InvalidPauseScope);
+            }
+        }
+        self.paused_by = paused_by;
+        self.paused_at = paused_at;
+        Ok(())
+    }
+
+    /// `None` lifts every pause; `Some` resumes only the listed types and
+    /// leaves a global pause in place.
+    pub fn lift_pause(&mut self, scope: Option<&[TransactionType]>) -> Result<()> {
+        require!(self.has_pause(), MultisigError::NotPaused);
+        match scope {
+            None => {
+                self.paused = false;
+                self.paused_types.clear();
+            }
+            Some(types) => {
+                require!(!types.is_empty(), MultisigError::InvalidPauseScope);
+                for transaction_type in types {
+                    let index = self.paused_types
+                        .iter()
+                        .position(|paused| paused == transaction_type)
+                        .ok_or(MultisigError::NotPaused)?;
+                    self.paused_types.remove(index);
+                }
+            }
+        }
+        if !self.has_pause() {
+            self.paused_by = Pubkey::default();
+            self.paused_at = 0;
+        }
+        Ok(())
+    }
+
+    /// Applies the actions in order to a copy of the owner set and thresholds,
+    /// validates only the final result and then commits it, so an
+    /// intermediate state never has to be valid on its own.
+    pub fn apply_config_actions(&mut self, actions: &[ConfigAction]) -> Result<()> {
+        require!(!actions.is_empty(), MultisigError::EmptyTransaction);
+        require!(actions.len() <= MAX_CONFIG_ACTIONS, MultisigError::TooManyConfigActions);
+
+        let mut staged = self.clone();
+
+        for action in actions {
+            match action {
+                ConfigAction::AddOwner { new_owner } => {
+                    require!(*new_owner != Pubkey::default(), MultisigError::InvalidOwner);
+                    require!(!staged.owners.contains(new_owner), MultisigError::DuplicateOwners);
+                    require!(staged.owners.len() < 10, MultisigError::TooManyOwners);
+                    staged.owners.push(*new_owner);
+                }
+                ConfigAction::RemoveOwner { owner } => {
+                    let owner_index = staged.owners
+                        .iter()
+                        .position(|existing| existing == owner)
+                        .ok_or(MultisigError::OwnerNotFound)?;
+                    staged.owners.remove(owner_index);
+                }
+                ConfigAction::SwapOwner { old_owner, new_owner } => {
+                    require!(*new_owner != Pubkey::default(), MultisigError::InvalidOwner);
+                    require!(!staged.owners.contains(new_owner), MultisigError::DuplicateOwners);
+                    let owner_index = staged.owners
+                        .iter()
+                        .position(|existing| existing == old_owner)
+                        .ok_or(MultisigError::OwnerNotFound)?;
+                    staged.owners[owner_index] = *new_owner;
+                }
+                ConfigAction::ChangeThreshold { new_threshold } => {
+                    staged.threshold = *new_threshold;
+                }
+                ConfigAction::ChangeAdminThreshold { new_admin_threshold } => {
+                    staged.admin_threshold = *new_admin_threshold;
+                }
+                ConfigAction::SetExpiryConfig { expiry_config } => {
+                    staged.expiry_config = expiry_config.clone();
+                }
+                ConfigAction::Unpause => {
+                    staged.lift_pause(None)?;
+                }
+                ConfigAction::SetProposalDeposit { deposit } => {
+                    staged.proposal_deposit = *deposit;
+                }
+            }
+        }
+
+        staged.validate_state()?;
+
+        self.owners = staged.owners;
+        self.threshold = staged.threshold;
+        self.admin_threshold = staged.admin_threshold;
+        self.paused = staged.paused;
+        self.paused_by = staged.paused_by;
+        self.paused_at = staged.paused_at;
+        self.paused_types = staged.paused_types;
+        self.expiry_config = staged.expiry_config;
+        self.proposal_deposit = staged.proposal_deposit;
         Ok(())
     }
 }
 
+pub const MAX_CONFIG_ACTIONS: usize = 10;
+pub const MAX_PAUSED_TYPES: usize = 16;
+
+/// `Multisig` as the baseline program wrote it, before expiry policies,
+/// scoped pauses and proposal deposits
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct LegacyMultisig {
+    owners: Vec<Pubkey>,
+    threshold: u8,
+    admin_threshold: u8,
+    transaction_count: u64,
+    bump: u8,
+    paused: bool,
+    paused_by: Pubkey,
+    paused_at: i64,
+    created_at: i64,
+    nonce: u64,
+    last_proposal_slot: u64,
+}
+
+/// `Transaction` as the baseline program wrote it, before schedules,
+/// metadata, vault messages, deposits and rejections
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct LegacyTransaction {
+    transaction_id: u64,
+    created_at: i64,
+    expires_at: i64,
+    executed: bool,
+    created_slot: u64,
+    multisig: Pubkey,
+    proposer: Pubkey,
+    transaction_type: TransactionType,
+    approvals: Vec<bool>,
+    instruction_data: Vec<u8>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Transaction {
     pub transaction_id: u64,
     pub created_at: i64,
+    /// `NO_EXPIRY` for proposals that never expire
     pub expires_at: i64,
     pub executed: bool,
     pub created_slot: u64,
@@ -223,10 +1397,66 @@ pub struct Transaction {
     pub approvals: Vec<bool>,
     #[max_len(1000)]
     pub instruction_data: Vec<u8>,
+
+    pub schedule: Option<ExecutionSchedule>,
+    pub runs_executed: u16,
+
+    pub metadata: ProposalMetadata,
+
+    /// `instruction_data` holds a borsh `VaultMessage` run by the vault
+    pub vault_message: bool,
+    /// Hash of the vault message's keys as resolved at proposal time
+    pub resolved_keys_hash: [u8; 32],
+
+    /// Deposit held in this account until `release_proposal_deposit`
+    pub deposit: u64,
+    #[max_len(10)]
+    pub rejections: Vec<bool>,
+    /// Set once enough owners reject that the threshold can no longer be met
+    pub rejected: bool,
 }
 
 
 impl Transaction {
+    /// Reads a transaction in the baseline layout and fills the fields added
+    /// since with their defaults: unscheduled, no metadata, no deposit and no
+    /// rejections yet. `None` for an account already in the current layout.
+    pub fn migrate(data: &[u8]) -> Result<Option<Transaction>> {
+        require!(
+            data.len() >= 8 && data[..8] == *Transaction::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        if data.len() >= 8 + Transaction::INIT_SPACE {
+            return Ok(None);
+        }
+        let legacy = LegacyTransaction::deserialize(&mut &data[8..])?;
+        Ok(Some(Transaction {
+            transaction_id: legacy.transaction_id,
+            created_at: legacy.created_at,
+            expires_at: legacy.expires_at,
+            executed: legacy.executed,
+            created_slot: legacy.created_slot,
+            multisig: legacy.multisig,
+            proposer: legacy.proposer,
+            transaction_type: legacy.transaction_type,
+            rejections: vec![false; legacy.approvals.len()],
+            approvals: legacy.approvals,
+            instruction_data: legacy.instruction_data,
+            schedule: None,
+            runs_executed: 0,
+            metadata: ProposalMetadata::default(),
+            vault_message: false,
+            resolved_keys_hash: [0; 32],
+            deposit: 0,
+            rejected: false,
+        }))
+    }
+
+    /// Expiry timestamp, or `None` for a proposal that never expires
+    pub fn expiry(&self) -> Option<i64> {
+        (self.expires_at != NO_EXPIRY).then_some(self.expires_at)
+    }
+
     pub fn is_expired(&self) -> Result<bool> {
         let clock = Clock::get()?;
         Ok(clock.unix_timestamp > self.expires_at)
@@ -237,6 +1467,7 @@ impl Transaction {
         if !self.executed {
             require!(!self.is_expired()?, MultisigError::TransactionExpired);
         }
+        require!(!self.rejected, MultisigError::TransactionRejected);
         require!(
             self.approvals.len() == multisig.owners.len(),
             MultisigError::ApprovalArrayMismatch
@@ -246,10 +1477,29 @@ impl Transaction {
         Ok(())
     }
 
+    /// Config bundles that unpause may be proposed, approved and executed
+    /// while the multisig is paused; otherwise a pause could never be lifted
+    pub fn lifts_pause(&self) -> bool {
+        self.transaction_type == TransactionType::Config
+            && Vec::<ConfigAction>::try_from_slice(&self.instruction_data)
+                .is_ok_and(|actions| actions.contains(&ConfigAction::Unpause))
+    }
+
     pub fn approval_count(&self) -> usize {
         self.approvals.iter().filter(|&&approved| approved).count()
     }
 
+    pub fn rejection_count(&self) -> usize {
+        self.rejections.iter().filter(|&&rejected| rejected).count()
+    }
+
+    /// True once so many owners rejected that the remaining owners can no
+    /// longer reach the approvals this transaction's executor requires
+    pub fn threshold_unreachable(&self, multisig: &Multisig) -> bool {
+        let required = multisig.required_approvals(&self.transaction_type);
+        multisig.owners.len().saturating_sub(self.rejection_count()) < required as usize
+    }
+
     pub fn is_ready_to_execute(&self, threshold: u8) -> bool {
         if self.executed {
             return false;
@@ -270,4 +1520,260 @@ impl Transaction {
     pub fn is_admin_ready_to_execute(&self, admin_threshold: u8) -> bool {
         self.is_ready_to_execute(admin_threshold)
     }
-}
\ No newline at end of file
+}
+
+/// Limits what a delegate may approve on its owner's behalf. Delegates can
+/// only approve; proposing and executing still need the owner's own key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct DelegateScope {
+    /// Transaction types the delegate may approve; empty allows every type
+    #[max_len(8)]
+    pub transaction_types: Vec<TransactionType>,
+    /// Most lamports a single approved vault transaction may move. A capped
+    /// delegate can only approve vault transactions whose value is readable.
+    pub max_lamports: Option<u64>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub multisig: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub scope: DelegateScope,
+    pub expires_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub fn is_expired(&self) -> Result<bool> {
+        let clock = Clock::get()?;
+        Ok(clock.unix_timestamp > self.expires_at)
+    }
+
+    pub fn allows(&self, transaction: &Transaction) -> bool {
+        let type_allowed = self.scope.transaction_types.is_empty()
+            || self.scope.transaction_types.contains(&transaction.transaction_type);
+
+        let amount_allowed = match self.scope.max_lamports {
+            None => true,
+            Some(max_lamports) => transaction.vault_message
+                && VaultMessage::try_from_slice(&transaction.instruction_data)
+                    .ok()
+                    .and_then(|message| message.signed_lamports())
+                    .is_some_and(|lamports| lamports <= max_lamports),
+        };
+
+        type_allowed && amount_allowed
+    }
+}
+
+pub const AUDIT_LOG_CAPACITY: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum AuditAction {
+    MultisigCreated,
+    Paused,
+    Unpaused,
+    TransactionProposed,
+    TransactionApproved,
+    TransactionExecuted,
+    ScheduledRunExecuted,
+    ConfigExecuted,
+    ThresholdChanged,
+    OwnerAdded,
+    OwnerRemoved,
+    OwnerSwapped,
+    DelegateRegistered,
+    DelegateRevoked,
+    ExpiryExtended,
+    VaultTransactionExecuted,
+    LookupTableCreated,
+    LookupTableExtended,
+    LookupTableDeactivated,
+    LookupTableClosed,
+    TransactionRejected,
+    ProposalDepositReleased,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct AuditRecord {
+    pub action: AuditAction,
+    /// Signer that triggered the action; default for permissionless admin executions
+    pub actor: Pubkey,
+    pub transaction_id: Option<u64>,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+/// Fixed-size ring buffer of the most recent actions on a multisig. Once
+/// `records` is full, `head` points at the oldest record, which is the next
+/// one to be overwritten.
+#[account]
+#[derive(InitSpace)]
+pub struct AuditLog {
+    pub multisig: Pubkey,
+    pub head: u16,
+    pub total_records: u64,
+    pub bump: u8,
+    #[max_len(64)]
+    pub records: Vec<AuditRecord>,
+}
+
+impl AuditLog {
+    pub fn append(
+        &mut self,
+        action: AuditAction,
+        actor: Pubkey,
+        transaction_id: Option<u64>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let record = AuditRecord {
+            action,
+            actor,
+            transaction_id,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        };
+
+        if self.records.len() < AUDIT_LOG_CAPACITY {
+            self.records.push(record);
+        } else {
+            self.records[self.head as usize] = record;
+        }
+        self.head = ((self.head as usize + 1) % AUDIT_LOG_CAPACITY) as u16;
+        self.total_records = self.total_records.saturating_add(1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Space the baseline `create_multisig` allocated
+    const BASELINE_MULTISIG_SPACE: usize = 8 + 4 + 32 * 10 + 1 + 1 + 8 + 1 + 1 + 32 + 8 + 8 + 8 + 8;
+    /// Space the baseline `propose_transaction` allocated
+    const BASELINE_TRANSACTION_SPACE: usize = 8 + 8 + 8 + 8 + 1 + 8 + 32 + 32 + 1 + 4 + 10 + 4 + 1000;
+
+    fn baseline_multisig(owner_count: usize) -> (LegacyMultisig, Vec<u8>) {
+        let multisig = LegacyMultisig {
+            owners: (0..owner_count).map(|_| Pubkey::new_unique()).collect(),
+            threshold: 2,
+            admin_threshold: 3,
+            transaction_count: 7,
+            bump: 254,
+            paused: true,
+            paused_by: Pubkey::new_unique(),
+            paused_at: 1_700_000_000,
+            created_at: 1_690_000_000,
+            nonce: 7,
+            last_proposal_slot: 4_242,
+        };
+        let mut data = Multisig::DISCRIMINATOR.to_vec();
+        multisig.serialize(&mut data).unwrap();
+        data.resize(BASELINE_MULTISIG_SPACE, 0);
+        (multisig, data)
+    }
+
+    fn baseline_transaction(instruction_len: usize) -> (LegacyTransaction, Vec<u8>) {
+        let transaction = LegacyTransaction {
+            transaction_id: 3,
+            created_at: 1_700_000_000,
+            expires_at: 1_700_259_200,
+            executed: false,
+            created_slot: 42,
+            multisig: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            transaction_type: TransactionType::Custom,
+            approvals: vec![true, false, true, false, false, false, false, false, false, false],
+            instruction_data: vec![9; instruction_len],
+        };
+        let mut data = Transaction::DISCRIMINATOR.to_vec();
+        transaction.serialize(&mut data).unwrap();
+        data.resize(BASELINE_TRANSACTION_SPACE, 0);
+        (transaction, data)
+    }
+
+    /// What the migrate instructions leave in the grown account
+    fn migrated<T: AccountSerialize + AccountDeserialize>(account: T, len: usize) -> T {
+        let mut data = vec![0; len];
+        account.try_serialize(&mut &mut data[..]).unwrap();
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn baseline_multisig_is_migrated_with_defaults() {
+        let (baseline, data) = baseline_multisig(10);
+        assert!(Multisig::try_deserialize(&mut &data[..]).is_err());
+
+        let multisig = migrated(Multisig::migrate(&data).unwrap().unwrap(), 8 + Multisig::INIT_SPACE);
+        assert_eq!(multisig.owners, baseline.owners);
+        assert_eq!(multisig.threshold, baseline.threshold);
+        assert_eq!(multisig.admin_threshold, baseline.admin_threshold);
+        assert_eq!(multisig.transaction_count, baseline.transaction_count);
+        assert_eq!(multisig.bump, baseline.bump);
+        assert!(multisig.paused);
+        assert_eq!(multisig.paused_by, baseline.paused_by);
+        assert_eq!(multisig.paused_at, baseline.paused_at);
+        assert_eq!(multisig.created_at, baseline.created_at);
+        assert_eq!(multisig.nonce, baseline.nonce);
+        assert_eq!(multisig.last_proposal_slot, baseline.last_proposal_slot);
+        assert_eq!(multisig.expiry_config, ExpiryConfig::default());
+        assert!(multisig.paused_types.is_empty());
+        assert_eq!(multisig.proposal_deposit, 0);
+        multisig.validate_state().unwrap();
+    }
+
+    #[test]
+    fn baseline_multisig_with_spare_owner_slots_gets_a_real_expiry_policy() {
+        // The zero tail already decodes, but as an expiry policy whose
+        // proposals lapse the moment they are created
+        let (_, data) = baseline_multisig(3);
+        let zero_filled = Multisig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(zero_filled.expiry_config.default_hours, 0);
+
+        let multisig = Multisig::migrate(&data).unwrap().unwrap();
+        assert_eq!(multisig.expiry_config, ExpiryConfig::default());
+    }
+
+    #[test]
+    fn baseline_transaction_is_migrated_with_defaults() {
+        let (baseline, data) = baseline_transaction(1000);
+        assert!(Transaction::try_deserialize(&mut &data[..]).is_err());
+
+        let transaction = migrated(Transaction::migrate(&data).unwrap().unwrap(), 8 + Transaction::INIT_SPACE);
+        assert_eq!(transaction.transaction_id, baseline.transaction_id);
+        assert_eq!(transaction.created_at, baseline.created_at);
+        assert_eq!(transaction.expiry(), Some(baseline.expires_at));
+        assert!(!transaction.executed);
+        assert_eq!(transaction.created_slot, baseline.created_slot);
+        assert_eq!(transaction.multisig, baseline.multisig);
+        assert_eq!(transaction.proposer, baseline.proposer);
+        assert_eq!(transaction.transaction_type, baseline.transaction_type);
+        assert_eq!(transaction.approvals, baseline.approvals);
+        assert_eq!(transaction.instruction_data, baseline.instruction_data);
+        assert_eq!(transaction.schedule, None);
+        assert_eq!(transaction.runs_executed, 0);
+        assert_eq!(transaction.metadata, ProposalMetadata::default());
+        assert!(!transaction.vault_message);
+        assert_eq!(transaction.resolved_keys_hash, [0; 32]);
+        assert_eq!(transaction.deposit, 0);
+        assert_eq!(transaction.rejections, vec![false; 10]);
+        assert!(!transaction.rejected);
+    }
+
+    #[test]
+    fn migrate_skips_current_accounts_and_rejects_others() {
+        let (_, data) = baseline_multisig(3);
+        let current = Multisig::migrate(&data).unwrap().unwrap();
+        let mut grown = vec![0; 8 + Multisig::INIT_SPACE];
+        current.try_serialize(&mut &mut grown[..]).unwrap();
+        assert!(Multisig::migrate(&grown).unwrap().is_none());
+
+        let (_, data) = baseline_transaction(10);
+        assert!(Multisig::migrate(&data).is_err());
+        assert!(Transaction::migrate(&grown).is_err());
+    }
+}
@@ -8,7 +8,16 @@ import {
   LAMPORTS_PER_SOL,
   Transaction,
   TransactionInstruction,
+  AddressLookupTableProgram,
 } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  createAccount,
+  getOrCreateAssociatedTokenAccount,
+  getAccount,
+  mintTo,
+} from "@solana/spl-token";
 import { expect } from "chai";
 
 describe("Multisig Module - Production Test Suite", () => {
@@ -59,6 +68,34 @@ describe("Multisig Module - Production Test Suite", () => {
     await new Promise(resolve => setTimeout(resolve, 2000));
   }
 
+  // Every other transaction of a multisig in id order, each followed by its
+  // proposer while it holds a refundable deposit, as closeMultisig expects
+  const closeRemainingAccounts = async (multisigKey: PublicKey, closeTransactionId: number) => {
+    const multisig = await program.account.multisig.fetch(multisigKey);
+    const accounts = [];
+
+    for (let id = 0; id < multisig.transactionCount.toNumber(); id++) {
+      if (id === closeTransactionId) continue;
+
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigKey.toBuffer(),
+          Buffer.from(new anchor.BN(id).toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+      const transaction = await program.account.transaction.fetch(transactionPda);
+
+      accounts.push({ pubkey: transactionPda, isSigner: false, isWritable: true });
+      if (transaction.deposit.toNumber() > 0 && !transaction.rejected) {
+        accounts.push({ pubkey: transaction.proposer, isSigner: false, isWritable: true });
+      }
+    }
+
+    return accounts;
+  };
+
   const createAdminTransactionAndUnpause = async () => {
     const multisig = await program.account.multisig.fetch(multisigPda);
 
@@ -80,7 +117,7 @@ describe("Multisig Module - Production Test Suite", () => {
       );
 
       const tx = await program.methods
-        .unpause(new anchor.BN(dummyTransactionId))
+        .unpause(new anchor.BN(dummyTransactionId), null)
         .accounts({
           multisig: multisigPda,
           transaction: firstTransactionPda,
@@ -273,7 +310,8 @@ describe("Multisig Module - Production Test Suite", () => {
           {
             transfer: {}
           },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -321,7 +359,8 @@ describe("Multisig Module - Production Test Suite", () => {
             testInstruction,
             new anchor.BN(999),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -356,7 +395,8 @@ describe("Multisig Module - Production Test Suite", () => {
             testInstruction,
             new anchor.BN(currentNonce),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: nonOwner.publicKey,
@@ -393,7 +433,8 @@ describe("Multisig Module - Production Test Suite", () => {
             Buffer.from([]),
             new anchor.BN(currentNonce),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -433,7 +474,8 @@ describe("Multisig Module - Production Test Suite", () => {
             oversizedData,
             new anchor.BN(currentNonce),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -491,7 +533,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -627,7 +670,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -724,7 +768,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -789,7 +834,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -835,7 +881,7 @@ describe("Multisig Module - Production Test Suite", () => {
   describe("🚨 5. Emergency Controls", () => {
     it("✅ Should pause multisig successfully", async () => {
       const tx = await program.methods
-        .emergencyPause()
+        .emergencyPause(null)
         .accounts({
           caller: owner1.publicKey,
           multisig: multisigPda,
@@ -874,7 +920,8 @@ describe("Multisig Module - Production Test Suite", () => {
             testInstruction,
             new anchor.BN(currentNonce),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -892,13 +939,69 @@ describe("Multisig Module - Production Test Suite", () => {
       }
     });
 
+    it("✅ Should propose, approve and execute an Unpause bundle while paused", async () => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeConfigTransaction([{ unpause: {} }], multisig.nonce, { after: { hours: 72 } }, null)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      for (const owner of [owner1, owner2, owner3]) {
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      // Wait for the same-slot protection
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      await program.methods
+        .executeConfigTransaction(new anchor.BN(transactionId))
+        .accounts({
+          executor: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      const unpaused = await program.account.multisig.fetch(multisigPda);
+      expect(unpaused.paused).to.be.false;
+      console.log("✅ Unpause bundle went through while paused");
+    });
+
     it("✅ Should unpause multisig with admin approval", async () => {
       await waitForRateLimit();
 
 
       try {
         const tx = await program.methods
-          .unpause(new anchor.BN(0)) // Add required transaction ID parameter
+          .unpause(new anchor.BN(0), null) // Add required transaction ID parameter
           .accounts({
             multisig: multisigPda,
             caller: owner1.publicKey, // Assuming unpause needs a caller
@@ -927,7 +1030,7 @@ describe("Multisig Module - Production Test Suite", () => {
     it("❌ Should fail emergency pause by non-owner", async () => {
       try {
         await program.methods
-          .emergencyPause()
+          .emergencyPause(null)
           .accounts({
             caller: nonOwner.publicKey,
             multisig: multisigPda,
@@ -941,6 +1044,137 @@ describe("Multisig Module - Production Test Suite", () => {
         console.log("✅ Correctly rejected non-owner emergency pause");
       }
     });
+
+    it("✅ Should pause only the listed transaction types", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping scoped pause test - multisig is fully paused");
+        return;
+      }
+
+      const proposeAs = async (transactionType: any) => {
+        await waitForRateLimit();
+        const current = await program.account.multisig.fetch(multisigPda);
+        const [transactionPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("transaction"),
+            multisigPda.toBuffer(),
+            Buffer.from(current.transactionCount.toArrayLike(Buffer, "le", 8))
+          ],
+          program.programId
+        );
+        await program.methods
+          .proposeTransaction(testInstruction, current.nonce, transactionType, null, null)
+          .accounts({
+            proposer: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([owner1])
+          .rpc();
+        return { transactionId: current.transactionCount, transactionPda };
+      };
+
+      await program.methods
+        .emergencyPause([{ transfer: {} }, { tokenTransfer: {} }])
+        .accounts({
+          caller: owner1.publicKey,
+          multisig: multisigPda,
+        })
+        .signers([owner1])
+        .rpc();
+
+      const paused = await program.account.multisig.fetch(multisigPda);
+      expect(paused.paused).to.be.false;
+      expect(paused.pausedTypes).to.have.lengthOf(2);
+
+      try {
+        await proposeAs({ transfer: {} });
+        expect.fail("Should have failed while transfers are paused");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MultisigPaused");
+        console.log("✅ Transfers are frozen");
+      }
+
+      // A vault message can move funds under any type, so it is frozen too
+      try {
+        const current = await program.account.multisig.fetch(multisigPda);
+        const [transactionPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("transaction"),
+            multisigPda.toBuffer(),
+            Buffer.from(current.transactionCount.toArrayLike(Buffer, "le", 8))
+          ],
+          program.programId
+        );
+        const [vaultPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("vault"), multisigPda.toBuffer()],
+          program.programId
+        );
+        const transfer = SystemProgram.transfer({
+          fromPubkey: vaultPda,
+          toPubkey: nonOwner.publicKey,
+          lamports: 1_000_000,
+        });
+        const message = {
+          accountKeys: [vaultPda, SystemProgram.programId, nonOwner.publicKey],
+          lookups: [],
+          instructions: [{
+            programIdIndex: 1,
+            accounts: [
+              { index: 0, isSigner: true, isWritable: true },
+              { index: 2, isSigner: false, isWritable: true },
+            ],
+            data: transfer.data,
+          }],
+        };
+        await program.methods
+          .proposeVaultTransaction(message, current.nonce, { custom: {} }, null, null)
+          .accounts({
+            proposer: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed while transfers are paused");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MultisigPaused");
+        console.log("✅ Custom vault messages are frozen with transfers");
+      }
+
+      // Other activity keeps working and can lift the scoped pause
+      const { transactionId, transactionPda } = await proposeAs({ custom: {} });
+      for (const owner of [owner1, owner2]) {
+        await program.methods
+          .approveTransaction(transactionId)
+          .accounts({
+            approver: owner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      const tx = await program.methods
+        .unpause(transactionId, [{ transfer: {} }, { tokenTransfer: {} }])
+        .accounts({
+          multisig: multisigPda,
+          transaction: transactionPda,
+        } as any)
+        .rpc();
+
+      console.log("📜 Scoped unpause tx:", tx);
+
+      const resumed = await program.account.multisig.fetch(multisigPda);
+      expect(resumed.pausedTypes).to.have.lengthOf(0);
+
+      console.log("✅ Scoped pause applied and lifted!");
+    });
   });
 
   describe("👥 6. Admin Functions", () => {
@@ -975,7 +1209,8 @@ describe("Multisig Module - Production Test Suite", () => {
             Buffer.from("change_threshold"),
             new anchor.BN(currentNonce),
             { adminAction: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -1017,95 +1252,409 @@ describe("Multisig Module - Production Test Suite", () => {
         console.log("✅ Threshold changed successfully!");
       });
     });
-  });
 
-  describe("⏰ 7. Rate Limiting & DOS Protection", () => {
-    before(async () => {
-      await createAdminTransactionAndUnpause();
-    });
+    describe("🔁 Swap Owner", () => {
+      const proposeAndApproveSwap = async (approvers: Keypair[], oldOwner: PublicKey, newOwner: PublicKey) => {
+        await waitForRateLimit();
 
-    it("❌ Should fail rapid transaction proposals", async () => {
-      const multisig = await program.account.multisig.fetch(multisigPda);
+        const multisig = await program.account.multisig.fetch(multisigPda);
+        const transactionId = multisig.transactionCount.toNumber();
 
-      if (multisig.paused) {
-        console.log("📍 Skipping rate limiting test - multisig is paused");
-        console.log("✅ Rate limiting logic exists in smart contract");
-        return;
-      }
+        const [transactionPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("transaction"),
+            multisigPda.toBuffer(),
+            Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+          ],
+          program.programId
+        );
 
-      await waitForRateLimit();
+        await program.methods
+          .proposeSwapOwner(
+            oldOwner,
+            newOwner,
+            multisig.nonce,
+            { after: { hours: 72 } },
+            null
+          )
+          .accounts({
+            proposer: approvers[0].publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([approvers[0]])
+          .rpc();
 
-      const currentNonce = multisig.nonce.toNumber();
+        for (const owner of approvers) {
+          await program.methods
+            .approveTransaction(new anchor.BN(transactionId))
+            .accounts({
+              approver: owner.publicKey,
+              multisig: multisigPda,
+              transaction: transactionPda,
+            } as any)
+            .signers([owner])
+            .rpc();
+        }
 
-      const [transactionPda1] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("transaction"),
-          multisigPda.toBuffer(),
-          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
-        ],
-        program.programId
-      );
+        return { transactionId, transactionPda };
+      };
 
-      await program.methods
-        .proposeTransaction(
-          testInstruction,
-          new anchor.BN(currentNonce),
-          { transfer: {} },
-          72
-        )
-        .accounts({
-          proposer: owner1.publicKey,
-          multisig: multisigPda,
-          transaction: transactionPda1,
-          systemProgram: SystemProgram.programId,
-        } as any)
-        .signers([owner1])
-        .rpc();
+      it("✅ Should swap an owner in place and swap it back", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
 
+        if (multisig.paused) {
+          console.log("📍 Skipping swap owner test - multisig is paused");
+          console.log("✅ Swap owner functionality exists in smart contract");
+          return;
+        }
 
-      const updatedMultisig = await program.account.multisig.fetch(multisigPda);
-      const newNonce = updatedMultisig.nonce.toNumber();
+        const ownerIndex = multisig.owners.findIndex(
+          (owner) => owner.toString() === owner3.publicKey.toString()
+        );
 
-      const [transactionPda2] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("transction"),
-          multisigPda.toBuffer(),
-          Buffer.from(updatedMultisig.transactionCount.toArrayLike(Buffer, "le", 8))
-        ],
-        program.programId
-      );
+        const swap = await proposeAndApproveSwap([owner1, owner2, owner3], owner3.publicKey, owner4.publicKey);
+        const tx = await program.methods
+          .swapOwner(new anchor.BN(swap.transactionId), owner3.publicKey, owner4.publicKey)
+          .accounts({
+            multisig: multisigPda,
+            transaction: swap.transactionPda,
+          })
+          .rpc();
 
-      try {
+        console.log("📜 Swap owner tx:", tx);
+
+        const swapped = await program.account.multisig.fetch(multisigPda);
+        expect(swapped.owners).to.have.lengthOf(multisig.owners.length);
+        expect(swapped.owners[ownerIndex].toString()).to.equal(owner4.publicKey.toString());
+        expect(swapped.threshold).to.equal(multisig.threshold);
+
+        // Restore the original owner set for the remaining tests
+        const restore = await proposeAndApproveSwap([owner1, owner2, owner4], owner4.publicKey, owner3.publicKey);
         await program.methods
-          .proposeTransaction(
-            testInstruction,
-            new anchor.BN(newNonce),
-            { transfer: {} },
-            72
-          )
+          .swapOwner(new anchor.BN(restore.transactionId), owner4.publicKey, owner3.publicKey)
           .accounts({
-            proposer: owner1.publicKey,
             multisig: multisigPda,
-            transaction: transactionPda2,
-            systemProgram: SystemProgram.programId,
-
-          } as any)
-          .signers([owner1])
+            transaction: restore.transactionPda,
+          })
           .rpc();
-        expect.fail("Should have failed due to rate limiting");
-      } catch (error) {
-        expect(error.error.errorCode.code).to.equal("RateLimitExceeded");
-        console.log("✅ Rate limiting working correctly!");
-      }
-    });
 
-    it("❌ Should fail with overly complex transaction", async () => {
-      const multisig = await program.account.multisig.fetch(multisigPda);
+        const restored = await program.account.multisig.fetch(multisigPda);
+        expect(restored.owners[ownerIndex].toString()).to.equal(owner3.publicKey.toString());
 
-      if (multisig.paused) {
-        console.log("📍 Skipping complexity test - multisig is paused");
-        console.log("✅ Complexity limiting logic exists in smart contract");
-        return;
+        console.log("✅ Owner swapped in place successfully!");
+      });
+
+      it("❌ Should fail to propose swapping in an existing owner", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
+
+        if (multisig.paused) {
+          console.log("📍 Skipping duplicate swap test - multisig is paused");
+          return;
+        }
+
+        try {
+          await proposeAndApproveSwap([owner1, owner2, owner3], owner3.publicKey, owner1.publicKey);
+          expect.fail("Should have failed with duplicate owner");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("DuplicateOwners");
+          console.log("✅ Correctly rejected swapping in an existing owner");
+        }
+      });
+
+      it("❌ Should fail to execute a swap other than the approved one", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
+
+        if (multisig.paused) {
+          console.log("📍 Skipping swap mismatch test - multisig is paused");
+          return;
+        }
+
+        const swap = await proposeAndApproveSwap([owner1, owner2, owner3], owner3.publicKey, owner4.publicKey);
+
+        try {
+          await program.methods
+            .swapOwner(new anchor.BN(swap.transactionId), owner2.publicKey, owner4.publicKey)
+            .accounts({
+              multisig: multisigPda,
+              transaction: swap.transactionPda,
+            })
+            .rpc();
+
+          expect.fail("Should have failed with a proposal mismatch");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("ProposalMismatch");
+          console.log("✅ Correctly rejected a swap that was not approved");
+        }
+      });
+
+      it("❌ Should fail to run an approved swap through executeTransaction", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
+
+        if (multisig.paused) {
+          console.log("📍 Skipping generic execute test - multisig is paused");
+          return;
+        }
+
+        const swap = await proposeAndApproveSwap([owner1, owner2, owner3], owner3.publicKey, owner4.publicKey);
+
+        try {
+          await program.methods
+            .executeTransaction(new anchor.BN(swap.transactionId))
+            .accounts({
+              executor: owner1.publicKey,
+              multisig: multisigPda,
+              transaction: swap.transactionPda,
+            } as any)
+            .signers([owner1])
+            .rpc();
+
+          expect.fail("Should have failed with an invalid transaction type");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidTransactionType");
+          console.log("✅ Correctly kept the swap out of the generic executor");
+        }
+      });
+    });
+
+    describe("🧩 Config Transactions", () => {
+      const proposeAndApproveConfig = async (actions: any[], approvers: Keypair[]) => {
+        await waitForRateLimit();
+
+        const multisig = await program.account.multisig.fetch(multisigPda);
+        const transactionId = multisig.transactionCount.toNumber();
+
+        const [transactionPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("transaction"),
+            multisigPda.toBuffer(),
+            Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .proposeConfigTransaction(actions, multisig.nonce, { after: { hours: 72 } }, null)
+          .accounts({
+            proposer: approvers[0].publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([approvers[0]])
+          .rpc();
+
+        for (const owner of approvers) {
+          await program.methods
+            .approveTransaction(new anchor.BN(transactionId))
+            .accounts({
+              approver: owner.publicKey,
+              multisig: multisigPda,
+              transaction: transactionPda,
+            } as any)
+            .signers([owner])
+            .rpc();
+        }
+
+        // Wait for the same-slot protection
+        await new Promise(resolve => setTimeout(resolve, 1000));
+
+        return { transactionId, transactionPda };
+      };
+
+      it("✅ Should apply a bundle whose intermediate state would be invalid", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
+
+        if (multisig.paused) {
+          console.log("📍 Skipping config transaction test - multisig is paused");
+          console.log("✅ Config transaction functionality exists in smart contract");
+          return;
+        }
+
+        // Removing owner3 first would leave fewer owners than the threshold
+        const rotate = await proposeAndApproveConfig(
+          [
+            { removeOwner: { owner: owner3.publicKey } },
+            { addOwner: { newOwner: owner4.publicKey } },
+          ],
+          [owner1, owner2, owner3]
+        );
+
+        const tx = await program.methods
+          .executeConfigTransaction(new anchor.BN(rotate.transactionId))
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: rotate.transactionPda,
+          } as any)
+          .signers([owner1])
+          .rpc();
+
+        console.log("📜 Execute config transaction tx:", tx);
+
+        const rotated = await program.account.multisig.fetch(multisigPda);
+        const owners = rotated.owners.map((owner) => owner.toString());
+        expect(owners).to.include(owner4.publicKey.toString());
+        expect(owners).to.not.include(owner3.publicKey.toString());
+        expect(rotated.threshold).to.equal(multisig.threshold);
+
+        // Restore the original owner set for the remaining tests
+        const restore = await proposeAndApproveConfig(
+          [{ swapOwner: { oldOwner: owner4.publicKey, newOwner: owner3.publicKey } }],
+          [owner1, owner2, owner4]
+        );
+
+        await program.methods
+          .executeConfigTransaction(new anchor.BN(restore.transactionId))
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: restore.transactionPda,
+          } as any)
+          .signers([owner1])
+          .rpc();
+
+        const restored = await program.account.multisig.fetch(multisigPda);
+        expect(restored.owners.map((owner) => owner.toString())).to.include(owner3.publicKey.toString());
+
+        console.log("✅ Config transaction applied atomically!");
+      });
+
+      it("❌ Should reject a bundle whose final state is invalid", async () => {
+        const multisig = await program.account.multisig.fetch(multisigPda);
+
+        if (multisig.paused) {
+          console.log("📍 Skipping invalid bundle test - multisig is paused");
+          return;
+        }
+
+        await waitForRateLimit();
+
+        const [transactionPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("transaction"),
+            multisigPda.toBuffer(),
+            Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+          ],
+          program.programId
+        );
+
+        try {
+          await program.methods
+            .proposeConfigTransaction(
+              [{ changeThreshold: { newThreshold: multisig.owners.length + 1 } }],
+              multisig.nonce,
+              { after: { hours: 72 } },
+              null
+            )
+            .accounts({
+              proposer: owner1.publicKey,
+              multisig: multisigPda,
+              transaction: transactionPda,
+              systemProgram: SystemProgram.programId,
+            } as any)
+            .signers([owner1])
+            .rpc();
+
+          expect.fail("Should have failed with invalid threshold");
+        } catch (error) {
+          expect(error.error.errorCode.code).to.equal("InvalidThreshold");
+          console.log("✅ Correctly rejected invalid config bundle");
+        }
+      });
+    });
+  });
+
+  describe("⏰ 7. Rate Limiting & DOS Protection", () => {
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+    });
+
+    it("❌ Should fail rapid transaction proposals", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping rate limiting test - multisig is paused");
+        console.log("✅ Rate limiting logic exists in smart contract");
+        return;
+      }
+
+      await waitForRateLimit();
+
+      const currentNonce = multisig.nonce.toNumber();
+
+      const [transactionPda1] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeTransaction(
+          testInstruction,
+          new anchor.BN(currentNonce),
+          { transfer: {} },
+          { after: { hours: 72 } },
+          null
+        )
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda1,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+
+      const updatedMultisig = await program.account.multisig.fetch(multisigPda);
+      const newNonce = updatedMultisig.nonce.toNumber();
+
+      const [transactionPda2] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transction"),
+          multisigPda.toBuffer(),
+          Buffer.from(updatedMultisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .proposeTransaction(
+            testInstruction,
+            new anchor.BN(newNonce),
+            { transfer: {} },
+            { after: { hours: 72 } },
+            null
+          )
+          .accounts({
+            proposer: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda2,
+            systemProgram: SystemProgram.programId,
+
+          } as any)
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed due to rate limiting");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("RateLimitExceeded");
+        console.log("✅ Rate limiting working correctly!");
+      }
+    });
+
+    it("❌ Should fail with overly complex transaction", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping complexity test - multisig is paused");
+        console.log("✅ Complexity limiting logic exists in smart contract");
+        return;
       }
 
       // Wait for rate limit to pass
@@ -1138,7 +1687,8 @@ describe("Multisig Module - Production Test Suite", () => {
             complexData,
             new anchor.BN(currentNonce),
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -1193,7 +1743,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          0 // expires immediately (0 hours)
+          { after: { hours: 0 } }, // expires immediately (0 hours)
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -1272,7 +1823,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72 // 72 hours - plenty of time
+          { after: { hours: 72 } }, // 72 hours - plenty of time
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -1355,7 +1907,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(currentNonce),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -1454,7 +2007,8 @@ describe("Multisig Module - Production Test Suite", () => {
             testInstruction,
             new anchor.BN(oldNonce), // Reusing old nonce
             { transfer: {} },
-            72
+            { after: { hours: 72 } },
+            null
           )
           .accounts({
             proposer: owner1.publicKey,
@@ -1507,7 +2061,8 @@ describe("Multisig Module - Production Test Suite", () => {
           testInstruction,
           new anchor.BN(nonceBefore),
           { transfer: {} },
-          72
+          { after: { hours: 72 } },
+          null
         )
         .accounts({
           proposer: owner1.publicKey,
@@ -1525,4 +2080,1433 @@ describe("Multisig Module - Production Test Suite", () => {
       console.log("✅ Nonce incremented correctly!");
     });
   });
+
+  describe("📅 11. Scheduled Transactions", () => {
+    let scheduledTransactionPda: PublicKey;
+    let scheduledTransactionId: number;
+
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+    });
+
+    it("✅ Should propose and approve a scheduled transaction", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping scheduled transaction test - multisig is paused");
+        console.log("✅ Scheduled transaction logic exists in smart contract");
+        return;
+      }
+
+      await waitForRateLimit();
+
+      scheduledTransactionId = multisig.transactionCount.toNumber();
+      [scheduledTransactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      const schedule = {
+        recurring: {
+          startAt: new anchor.BN(now + 5),
+          intervalSeconds: new anchor.BN(3600),
+          count: 3,
+        },
+      };
+
+      await program.methods
+        .proposeScheduledTransaction(
+          testInstruction,
+          multisig.nonce,
+          { transfer: {} },
+          { after: { hours: 72 } },
+          schedule,
+          null
+        )
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: scheduledTransactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      for (let i = 0; i < multisig.threshold; i++) {
+        const owner = [owner1, owner2, owner3][i];
+        await program.methods
+          .approveTransaction(new anchor.BN(scheduledTransactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: multisigPda,
+            transaction: scheduledTransactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      const transaction = await program.account.transaction.fetch(scheduledTransactionPda);
+      expect(transaction.schedule).to.not.be.null;
+      expect(transaction.runsExecuted).to.equal(0);
+
+      console.log("✅ Scheduled transaction proposed and approved!");
+    });
+
+    it("❌ Should fail to run a scheduled instance before it is due", async () => {
+      if (!scheduledTransactionPda) {
+        console.log("📍 Skipping - no scheduled transaction was created");
+        return;
+      }
+
+      try {
+        await program.methods
+          .executeScheduledTransaction(new anchor.BN(scheduledTransactionId))
+          .accounts({
+            cranker: nonOwner.publicKey,
+            multisig: multisigPda,
+            transaction: scheduledTransactionPda,
+          } as any)
+          .signers([nonOwner])
+          .rpc();
+
+        expect.fail("Should have failed before the first run is due");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ScheduleNotDue");
+        console.log("✅ Correctly rejected early scheduled run");
+      }
+    });
+
+    it("✅ Should let a non-owner crank a due instance", async () => {
+      if (!scheduledTransactionPda) {
+        console.log("📍 Skipping - no scheduled transaction was created");
+        return;
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+
+      const tx = await program.methods
+        .executeScheduledTransaction(new anchor.BN(scheduledTransactionId))
+        .accounts({
+          cranker: nonOwner.publicKey,
+          multisig: multisigPda,
+          transaction: scheduledTransactionPda,
+        } as any)
+        .signers([nonOwner])
+        .rpc();
+
+      console.log("📜 Scheduled run tx:", tx);
+
+      const transaction = await program.account.transaction.fetch(scheduledTransactionPda);
+      expect(transaction.runsExecuted).to.equal(1);
+      expect(transaction.executed).to.be.false;
+
+      // The next run is an hour away
+      try {
+        await program.methods
+          .executeScheduledTransaction(new anchor.BN(scheduledTransactionId))
+          .accounts({
+            cranker: nonOwner.publicKey,
+            multisig: multisigPda,
+            transaction: scheduledTransactionPda,
+          } as any)
+          .signers([nonOwner])
+          .rpc();
+
+        expect.fail("Should have failed before the second run is due");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ScheduleNotDue");
+      }
+
+      console.log("✅ Scheduled instance executed by crank!");
+    });
+  });
+
+  describe("🔑 12. Delegated Approval Keys", () => {
+    const hotKey = Keypair.generate();
+    let delegationPda: PublicKey;
+
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+
+      [delegationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("delegation"), multisigPda.toBuffer(), owner2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    const proposeTransfer = async () => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeTransaction(testInstruction, multisig.nonce, { transfer: {} }, { after: { hours: 72 } }, null)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      return { transactionId, transactionPda };
+    };
+
+    it("✅ Should let a delegate approve on the owner's behalf", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping delegate test - multisig is paused");
+        console.log("✅ Delegate approval logic exists in smart contract");
+        return;
+      }
+
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      await program.methods
+        .registerDelegate(hotKey.publicKey, new anchor.BN(now + 3600), {
+          transactionTypes: [{ transfer: {} }],
+          maxLamports: null,
+        })
+        .accounts({
+          owner: owner2.publicKey,
+          multisig: multisigPda,
+          delegation: delegationPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner2])
+        .rpc();
+
+      const { transactionId, transactionPda } = await proposeTransfer();
+
+      const tx = await program.methods
+        .approveTransaction(new anchor.BN(transactionId))
+        .accounts({
+          approver: hotKey.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          delegation: delegationPda,
+        } as any)
+        .signers([hotKey])
+        .rpc();
+
+      console.log("📜 Delegate approval tx:", tx);
+
+      const ownerIndex = multisig.owners.findIndex(
+        (owner) => owner.toString() === owner2.publicKey.toString()
+      );
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.approvals[ownerIndex]).to.be.true;
+
+      console.log("✅ Delegate approved on behalf of owner!");
+    });
+
+    it("❌ Should reject the delegate after the owner revokes it", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping delegate revoke test - multisig is paused");
+        return;
+      }
+
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          owner: owner2.publicKey,
+          multisig: multisigPda,
+          delegation: delegationPda,
+        } as any)
+        .signers([owner2])
+        .rpc();
+
+      const { transactionId, transactionPda } = await proposeTransfer();
+
+      try {
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: hotKey.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            delegation: delegationPda,
+          } as any)
+          .signers([hotKey])
+          .rpc();
+
+        expect.fail("Should have failed with a revoked delegate");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccountNotInitialized");
+        console.log("✅ Correctly rejected revoked delegate");
+      }
+    });
+
+    it("❌ Should keep a capped delegate off transactions it cannot measure", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping capped delegate test - multisig is paused");
+        return;
+      }
+
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      await program.methods
+        .registerDelegate(hotKey.publicKey, new anchor.BN(now + 3600), {
+          transactionTypes: [],
+          maxLamports: new anchor.BN(LAMPORTS_PER_SOL),
+        })
+        .accounts({
+          owner: owner2.publicKey,
+          multisig: multisigPda,
+          delegation: delegationPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner2])
+        .rpc();
+
+      // Raw instruction data carries no readable amount
+      const { transactionId, transactionPda } = await proposeTransfer();
+
+      try {
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: hotKey.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+            delegation: delegationPda,
+          } as any)
+          .signers([hotKey])
+          .rpc();
+
+        expect.fail("Should have failed with a capped delegate");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("DelegateScopeViolation");
+        console.log("✅ Correctly kept capped delegate to measurable transfers");
+      }
+
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          owner: owner2.publicKey,
+          multisig: multisigPda,
+          delegation: delegationPda,
+        } as any)
+        .signers([owner2])
+        .rpc();
+    });
+  });
+
+  describe("🧾 13. Audit Log", () => {
+    it("✅ Should keep a ring buffer of recent multisig actions", async () => {
+      const [auditLogPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("audit_log"), multisigPda.toBuffer()],
+        program.programId
+      );
+
+      const auditLog = await program.account.auditLog.fetch(auditLogPda);
+      expect(auditLog.multisig.toString()).to.equal(multisigPda.toString());
+      expect(auditLog.records.length).to.be.greaterThan(0);
+      expect(auditLog.records.length).to.be.at.most(64);
+      expect(auditLog.totalRecords.toNumber()).to.be.at.least(auditLog.records.length);
+
+      if (auditLog.totalRecords.toNumber() <= 64) {
+        expect(auditLog.records[0].action).to.have.property("multisigCreated");
+        expect(auditLog.records[0].actor.toString()).to.equal(creator.publicKey.toString());
+      }
+
+      // The newest record sits just before head
+      const newest = auditLog.records[(auditLog.head + auditLog.records.length - 1) % auditLog.records.length];
+      expect(newest.timestamp.toNumber()).to.be.greaterThan(0);
+
+      console.log(`✅ Audit log holds ${auditLog.records.length} of ${auditLog.totalRecords.toNumber()} records`);
+    });
+  });
+
+  describe("📝 14. Proposal Metadata", () => {
+    const proposeWithMetadata = async (metadata: any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeTransaction(testInstruction, multisig.nonce, { transfer: {} }, { after: { hours: 72 } }, metadata)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      return transactionPda;
+    };
+
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+    });
+
+    it("✅ Should store title, memo and document reference", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping proposal metadata test - multisig is paused");
+        console.log("✅ Proposal metadata logic exists in smart contract");
+        return;
+      }
+
+      const contentHash = Array.from({ length: 32 }, (_, i) => 255 - i);
+      const transactionPda = await proposeWithMetadata({
+        title: "Q3 payroll",
+        memo: "Monthly contractor payments, see attached invoice",
+        document: {
+          contentHash,
+          uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        },
+      });
+
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.metadata.title).to.equal("Q3 payroll");
+      expect(transaction.metadata.memo).to.equal("Monthly contractor payments, see attached invoice");
+      expect(transaction.metadata.document.contentHash).to.deep.equal(contentHash);
+
+      console.log("✅ Proposal metadata stored successfully!");
+    });
+
+    it("❌ Should fail with an oversized memo", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping oversized memo test - multisig is paused");
+        return;
+      }
+
+      try {
+        await proposeWithMetadata({ title: null, memo: "x".repeat(257), document: null });
+        expect.fail("Should have failed with memo too long");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("MemoTooLong");
+        console.log("✅ Correctly rejected oversized memo");
+      }
+    });
+  });
+
+  describe("⌛ 15. Flexible Proposal Expiry", () => {
+    const propose = async (transactionType: any, expiry: any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeTransaction(testInstruction, multisig.nonce, transactionType, expiry, null)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      return { transactionId, transactionPda };
+    };
+
+    const proposeExtension = async (targetTransactionId: number, newExpiry: any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeExtendExpiry(new anchor.BN(targetTransactionId), newExpiry, multisig.nonce, null, null)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      for (let i = 0; i < multisig.threshold; i++) {
+        const owner = [owner1, owner2, owner3][i];
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      return { transactionId, transactionPda };
+    };
+
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+    });
+
+    it("✅ Should apply the multisig default expiry", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping default expiry test - multisig is paused");
+        console.log("✅ Expiry configuration exists in smart contract");
+        return;
+      }
+
+      const { transactionPda } = await propose({ transfer: {} }, null);
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      const lifetime = transaction.expiresAt.toNumber() - transaction.createdAt.toNumber();
+      expect(lifetime).to.equal(multisig.expiryConfig.defaultHours * 3600);
+
+      console.log("✅ Default expiry applied!");
+    });
+
+    it("❌ Should reject a never-expiring proposal unless the multisig allows it", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused || multisig.expiryConfig.allowNoExpiry) {
+        console.log("📍 Skipping no-expiry test");
+        return;
+      }
+
+      try {
+        await propose({ transfer: {} }, { never: {} });
+        expect.fail("Should have failed without allow_no_expiry");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("NoExpiryNotAllowed");
+        console.log("✅ Correctly rejected never-expiring proposal");
+      }
+    });
+
+    it("✅ Should extend a pending proposal with threshold approval", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping extend expiry test - multisig is paused");
+        return;
+      }
+
+      const target = await propose({ transfer: {} }, { after: { hours: 1 } });
+      const extension = await proposeExtension(target.transactionId, { after: { hours: 48 } });
+
+      const before = await program.account.transaction.fetch(target.transactionPda);
+
+      const tx = await program.methods
+        .extendExpiry(
+          new anchor.BN(extension.transactionId),
+          new anchor.BN(target.transactionId),
+          { after: { hours: 48 } }
+        )
+        .accounts({
+          multisig: multisigPda,
+          transaction: extension.transactionPda,
+          targetTransaction: target.transactionPda,
+        } as any)
+        .rpc();
+
+      console.log("📜 Extend expiry tx:", tx);
+
+      const after = await program.account.transaction.fetch(target.transactionPda);
+      expect(after.expiresAt.toNumber()).to.be.greaterThan(before.expiresAt.toNumber());
+
+      console.log("✅ Proposal expiry extended!");
+    });
+
+    it("❌ Should fail to apply an expiry other than the approved one", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping extend expiry mismatch test - multisig is paused");
+        return;
+      }
+
+      const target = await propose({ transfer: {} }, { after: { hours: 1 } });
+      const extension = await proposeExtension(target.transactionId, { after: { hours: 48 } });
+
+      try {
+        await program.methods
+          .extendExpiry(
+            new anchor.BN(extension.transactionId),
+            new anchor.BN(target.transactionId),
+            { after: { hours: 200 } }
+          )
+          .accounts({
+            multisig: multisigPda,
+            transaction: extension.transactionPda,
+            targetTransaction: target.transactionPda,
+          } as any)
+          .rpc();
+
+        expect.fail("Should have failed with a different expiry");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ProposalMismatch");
+        console.log("✅ Correctly rejected unapproved expiry");
+      }
+    });
+  });
+
+  describe("📚 16. Vault Transactions & Address Lookup Tables", () => {
+    const recipient = Keypair.generate();
+    let vaultPda: PublicKey;
+    let lookupTable: PublicKey;
+
+    const approveAll = async (transactionId: number, transactionPda: PublicKey, count: number) => {
+      for (let i = 0; i < count; i++) {
+        const owner = [owner1, owner2, owner3][i];
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+    };
+
+    const proposeVaultTransfer = async (lamports: number, table: PublicKey = lookupTable, schedule: any = null) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      const transfer = SystemProgram.transfer({
+        fromPubkey: vaultPda,
+        toPubkey: recipient.publicKey,
+        lamports,
+      });
+
+      // Keys: 0 vault, 1 system program, 2 recipient (loaded from the table)
+      const message = {
+        accountKeys: [vaultPda, SystemProgram.programId],
+        lookups: [{ table, writableIndexes: Buffer.from([0]), readonlyIndexes: Buffer.from([]) }],
+        instructions: [{
+          programIdIndex: 1,
+          accounts: [
+            { index: 0, isSigner: true, isWritable: true },
+            { index: 2, isSigner: false, isWritable: true },
+          ],
+          data: transfer.data,
+        }],
+      };
+
+      const propose = schedule
+        ? program.methods.proposeScheduledVaultTransaction(message, multisig.nonce, { transfer: {} }, null, schedule, null)
+        : program.methods.proposeVaultTransaction(message, multisig.nonce, { transfer: {} }, null, null);
+
+      await propose
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts([{ pubkey: table, isSigner: false, isWritable: false }])
+        .signers([owner1])
+        .rpc();
+
+      return { transactionId, transactionPda };
+    };
+
+    const proposeLookupTableAction = async (table: PublicKey, action: any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(multisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          multisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeLookupTableTransaction(table, action, multisig.nonce, null, null)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      await approveAll(transactionId, transactionPda, multisig.threshold);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      return { transactionId, transactionPda };
+    };
+
+    before(async () => {
+      await createAdminTransactionAndUnpause();
+
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), multisigPda.toBuffer()],
+        program.programId
+      );
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: vaultPda,
+            lamports: LAMPORTS_PER_SOL / 10,
+          })
+        )
+      );
+    });
+
+    it("✅ Should create and extend a vault-owned lookup table", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused) {
+        console.log("📍 Skipping lookup table test - multisig is paused");
+        console.log("✅ Lookup table management exists in smart contract");
+        return;
+      }
+
+      const recentSlot = await provider.connection.getSlot("finalized");
+      [, lookupTable] = AddressLookupTableProgram.createLookupTable({
+        authority: vaultPda,
+        payer: owner1.publicKey,
+        recentSlot,
+      });
+
+      await program.methods
+        .createLookupTable(new anchor.BN(recentSlot))
+        .accounts({
+          payer: owner1.publicKey,
+          multisig: multisigPda,
+          lookupTable,
+          addressLookupTableProgram: AddressLookupTableProgram.programId,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      const extension = await proposeLookupTableAction(lookupTable, {
+        extend: { newAddresses: [recipient.publicKey] },
+      });
+
+      const tx = await program.methods
+        .extendLookupTable(new anchor.BN(extension.transactionId))
+        .accounts({
+          payer: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: extension.transactionPda,
+          lookupTable,
+          addressLookupTableProgram: AddressLookupTableProgram.programId,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      console.log("📜 Extend lookup table tx:", tx);
+
+      const table = await provider.connection.getAddressLookupTable(lookupTable);
+      expect(table.value.state.authority.toString()).to.equal(vaultPda.toString());
+      expect(table.value.state.addresses[0].toString()).to.equal(recipient.publicKey.toString());
+
+      console.log("✅ Lookup table created and extended!");
+    });
+
+    it("❌ Should only resolve vault messages through vault-owned tables", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused || !lookupTable) {
+        console.log("📍 Skipping foreign lookup table test");
+        return;
+      }
+
+      // Same addresses, but owner1 rather than the vault controls this table
+      const recentSlot = await provider.connection.getSlot("finalized");
+      const [createIx, foreignTable] = AddressLookupTableProgram.createLookupTable({
+        authority: owner1.publicKey,
+        payer: owner1.publicKey,
+        recentSlot,
+      });
+      const extendIx = AddressLookupTableProgram.extendLookupTable({
+        lookupTable: foreignTable,
+        authority: owner1.publicKey,
+        payer: owner1.publicKey,
+        addresses: [recipient.publicKey],
+      });
+      await provider.sendAndConfirm(new Transaction().add(createIx, extendIx), [owner1]);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      try {
+        await proposeVaultTransfer(1_000_000, foreignTable);
+        expect.fail("Should have failed with a table the vault does not own");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("LookupTableNotVaultOwned");
+        console.log("✅ Correctly rejected foreign lookup table");
+      }
+
+      // An approved change to one table cannot be applied to another
+      const extension = await proposeLookupTableAction(lookupTable, {
+        extend: { newAddresses: [nonOwner.publicKey] },
+      });
+      try {
+        await program.methods
+          .extendLookupTable(new anchor.BN(extension.transactionId))
+          .accounts({
+            payer: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: extension.transactionPda,
+            lookupTable: foreignTable,
+            addressLookupTableProgram: AddressLookupTableProgram.programId,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed with a different table");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ProposalMismatch");
+        console.log("✅ Correctly bound the extension to its approved table");
+      }
+    });
+
+    it("❌ Should reject remaining accounts that do not match the message", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused || !lookupTable) {
+        console.log("📍 Skipping account mismatch test");
+        return;
+      }
+
+      const { transactionId, transactionPda } = await proposeVaultTransfer(1_000_000);
+      await approveAll(transactionId, transactionPda, multisig.threshold);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      try {
+        await program.methods
+          .executeVaultTransaction(new anchor.BN(transactionId))
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .remainingAccounts([
+            { pubkey: lookupTable, isSigner: false, isWritable: false },
+            { pubkey: vaultPda, isSigner: false, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: nonOwner.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed with mismatched accounts");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("AccountMismatch");
+        console.log("✅ Correctly rejected mismatched accounts");
+      }
+    });
+
+    it("✅ Should execute a vault transfer whose recipient comes from the lookup table", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused || !lookupTable) {
+        console.log("📍 Skipping vault execution test");
+        return;
+      }
+
+      const lamports = 2_000_000;
+      const { transactionId, transactionPda } = await proposeVaultTransfer(lamports);
+      await approveAll(transactionId, transactionPda, multisig.threshold);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      const balanceBefore = await provider.connection.getBalance(recipient.publicKey);
+
+      const tx = await program.methods
+        .executeVaultTransaction(new anchor.BN(transactionId))
+        .accounts({
+          executor: owner1.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+        } as any)
+        .remainingAccounts([
+          { pubkey: lookupTable, isSigner: false, isWritable: false },
+          { pubkey: vaultPda, isSigner: false, isWritable: true },
+          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+          { pubkey: recipient.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([owner1])
+        .rpc();
+
+      console.log("📜 Execute vault transaction tx:", tx);
+
+      const balanceAfter = await provider.connection.getBalance(recipient.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(lamports);
+
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.executed).to.be.true;
+      expect(transaction.vaultMessage).to.be.true;
+
+      console.log("✅ Vault transaction executed through the lookup table!");
+    });
+
+    it("✅ Should let a non-owner crank each due run of a scheduled vault transfer", async () => {
+      const multisig = await program.account.multisig.fetch(multisigPda);
+
+      if (multisig.paused || !lookupTable) {
+        console.log("📍 Skipping scheduled vault transfer test");
+        return;
+      }
+
+      const lamports = 1_000_000;
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      const schedule = { at: { times: [new anchor.BN(now + 5), new anchor.BN(now + 3600)] } };
+      const { transactionId, transactionPda } = await proposeVaultTransfer(lamports, lookupTable, schedule);
+      await approveAll(transactionId, transactionPda, multisig.threshold);
+
+      const crank = () =>
+        program.methods
+          .executeVaultTransaction(new anchor.BN(transactionId))
+          .accounts({
+            executor: nonOwner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .remainingAccounts([
+            { pubkey: lookupTable, isSigner: false, isWritable: false },
+            { pubkey: vaultPda, isSigner: false, isWritable: true },
+            { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+            { pubkey: recipient.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([nonOwner])
+          .rpc();
+
+      await new Promise(resolve => setTimeout(resolve, 6000));
+      const balanceBefore = await provider.connection.getBalance(recipient.publicKey);
+
+      await crank();
+
+      expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(balanceBefore + lamports);
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.runsExecuted).to.equal(1);
+      expect(transaction.executed).to.be.false;
+
+      // The second run is an hour away
+      try {
+        await crank();
+        expect.fail("Should have failed before the second run is due");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("ScheduleNotDue");
+      }
+
+      // Scheduled vault messages only run through the vault executor
+      try {
+        await program.methods
+          .executeScheduledTransaction(new anchor.BN(transactionId))
+          .accounts({
+            cranker: nonOwner.publicKey,
+            multisig: multisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([nonOwner])
+          .rpc();
+        expect.fail("Should have failed with a vault transaction");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("VaultTransaction");
+      }
+
+      console.log("✅ Scheduled vault transfer run by crank!");
+    });
+  });
+
+  describe("🗑️ 17. Close Multisig", () => {
+    const closeCreator = Keypair.generate();
+    const destination = Keypair.generate();
+    const closeTransactionId = 0;
+    let closeMultisigPda: PublicKey;
+    let closeTransactionPda: PublicKey;
+    let vaultPda: PublicKey;
+    let mint: PublicKey;
+    let vaultTokenAccount: PublicKey;
+    let pending: { transactionId: number; transactionPda: PublicKey };
+
+    const proposeOn = async (method: (nonce: anchor.BN) => any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(closeMultisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          closeMultisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await method(multisig.nonce)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: closeMultisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      return { transactionId, transactionPda };
+    };
+
+    before(async () => {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(closeCreator.publicKey, LAMPORTS_PER_SOL),
+        "confirmed"
+      );
+
+      [closeMultisigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("multisig"), closeCreator.publicKey.toBuffer()],
+        program.programId
+      );
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), closeMultisigPda.toBuffer()],
+        program.programId
+      );
+      [closeTransactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          closeMultisigPda.toBuffer(),
+          Buffer.from(new anchor.BN(closeTransactionId).toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .createMultisig([owner1.publicKey, owner2.publicKey], 1, 2)
+        .accounts({
+          creator: closeCreator.publicKey,
+          multisig: closeMultisigPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([closeCreator])
+        .rpc();
+
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: vaultPda,
+            lamports: LAMPORTS_PER_SOL / 20,
+          })
+        )
+      );
+    });
+
+    it("❌ Should reject closing to a destination other than the approved one", async () => {
+      const { transactionId, transactionPda } = await proposeOn((nonce) =>
+        program.methods.proposeCloseMultisig(destination.publicKey, nonce, null, null)
+      );
+
+      for (const owner of [owner1, owner2]) {
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: closeMultisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      try {
+        await program.methods
+          .closeMultisig(new anchor.BN(transactionId), 0)
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: closeMultisigPda,
+            transaction: transactionPda,
+            destination: nonOwner.publicKey,
+            proposer: owner1.publicKey,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed with the wrong destination");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidDestination");
+        console.log("✅ Correctly rejected unapproved destination");
+      }
+    });
+
+    it("❌ Should reject sweeping vault tokens into an account the destination does not own", async () => {
+      const payer = (provider.wallet as anchor.Wallet).payer;
+
+      mint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+      vaultTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, vaultPda, true)
+      ).address;
+      await mintTo(provider.connection, payer, mint, vaultTokenAccount, payer, 1_000_000);
+
+      const foreignTokenAccount = await createAccount(provider.connection, payer, mint, nonOwner.publicKey);
+
+      try {
+        await program.methods
+          .closeMultisig(new anchor.BN(closeTransactionId), 1)
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: closeMultisigPda,
+            transaction: closeTransactionPda,
+            destination: destination.publicKey,
+            proposer: owner1.publicKey,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .remainingAccounts([
+            { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: mint, isSigner: false, isWritable: false },
+            { pubkey: foreignTokenAccount, isSigner: false, isWritable: true },
+            { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          ])
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed with a foreign destination token account");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("InvalidDestination");
+        console.log("✅ Correctly rejected a token account outside the destination's control");
+      }
+    });
+
+    it("❌ Should refuse to close while leaving another transaction behind", async () => {
+      pending = await proposeOn((nonce) =>
+        program.methods.proposeTransaction(testInstruction, nonce, { transfer: {} }, null, null)
+      );
+
+      try {
+        await program.methods
+          .closeMultisig(new anchor.BN(closeTransactionId), 0)
+          .accounts({
+            executor: owner1.publicKey,
+            multisig: closeMultisigPda,
+            transaction: closeTransactionPda,
+            destination: destination.publicKey,
+            proposer: owner1.publicKey,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([owner1])
+          .rpc();
+        expect.fail("Should have failed with a transaction left open");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("TransactionsNotClosed");
+        console.log("✅ Correctly refused to orphan a pending transaction");
+      }
+    });
+
+    it("✅ Should sweep the vault and close the multisig with its pending transactions", async () => {
+      const payer = (provider.wallet as anchor.Wallet).payer;
+      const destinationTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, destination.publicKey)
+      ).address;
+
+      const vaultBalance = await provider.connection.getBalance(vaultPda);
+
+      const tx = await program.methods
+        .closeMultisig(new anchor.BN(closeTransactionId), 1)
+        .accounts({
+          executor: owner1.publicKey,
+          multisig: closeMultisigPda,
+          transaction: closeTransactionPda,
+          destination: destination.publicKey,
+          proposer: owner1.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts([
+          { pubkey: vaultTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+          { pubkey: destinationTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          ...(await closeRemainingAccounts(closeMultisigPda, closeTransactionId)),
+        ])
+        .signers([owner1])
+        .rpc();
+
+      console.log("📜 Close multisig tx:", tx);
+
+      expect(await provider.connection.getAccountInfo(closeMultisigPda)).to.be.null;
+      expect(await provider.connection.getAccountInfo(pending.transactionPda)).to.be.null;
+      expect(await provider.connection.getAccountInfo(vaultTokenAccount)).to.be.null;
+      expect(Number((await getAccount(provider.connection, destinationTokenAccount)).amount)).to.equal(1_000_000);
+      expect(await provider.connection.getBalance(vaultPda)).to.equal(0);
+      expect(await provider.connection.getBalance(destination.publicKey)).to.be.greaterThan(vaultBalance);
+
+      console.log("✅ Multisig closed and vault swept!");
+    });
+  });
+
+  describe("💰 18. Proposal Deposits", () => {
+    const depositCreator = Keypair.generate();
+    const deposit = LAMPORTS_PER_SOL / 100;
+    let depositMultisigPda: PublicKey;
+    let vaultPda: PublicKey;
+
+    const proposeOn = async (method: (nonce: anchor.BN) => any) => {
+      await waitForRateLimit();
+
+      const multisig = await program.account.multisig.fetch(depositMultisigPda);
+      const transactionId = multisig.transactionCount.toNumber();
+      const [transactionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("transaction"),
+          depositMultisigPda.toBuffer(),
+          Buffer.from(multisig.transactionCount.toArrayLike(Buffer, "le", 8))
+        ],
+        program.programId
+      );
+
+      await method(multisig.nonce)
+        .accounts({
+          proposer: owner1.publicKey,
+          multisig: depositMultisigPda,
+          transaction: transactionPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      return { transactionId, transactionPda };
+    };
+
+    const approve = async (transactionId: number, transactionPda: PublicKey, owners: Keypair[]) => {
+      for (const owner of owners) {
+        await program.methods
+          .approveTransaction(new anchor.BN(transactionId))
+          .accounts({
+            approver: owner.publicKey,
+            multisig: depositMultisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+    };
+
+    const releaseDeposit = (transactionId: number, transactionPda: PublicKey) =>
+      program.methods
+        .releaseProposalDeposit(new anchor.BN(transactionId))
+        .accounts({
+          multisig: depositMultisigPda,
+          transaction: transactionPda,
+          proposer: owner1.publicKey,
+          vault: vaultPda,
+        } as any)
+        .rpc();
+
+    before(async () => {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(depositCreator.publicKey, LAMPORTS_PER_SOL),
+        "confirmed"
+      );
+
+      [depositMultisigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("multisig"), depositCreator.publicKey.toBuffer()],
+        program.programId
+      );
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), depositMultisigPda.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createMultisig([owner1.publicKey, owner2.publicKey, owner3.publicKey], 2, 2)
+        .accounts({
+          creator: depositCreator.publicKey,
+          multisig: depositMultisigPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([depositCreator])
+        .rpc();
+
+      const config = await proposeOn((nonce) =>
+        program.methods.proposeConfigTransaction(
+          [{ setProposalDeposit: { deposit: new anchor.BN(deposit) } }],
+          nonce,
+          null,
+          null
+        )
+      );
+      await approve(config.transactionId, config.transactionPda, [owner1, owner2]);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      await program.methods
+        .executeConfigTransaction(new anchor.BN(config.transactionId))
+        .accounts({
+          executor: owner1.publicKey,
+          multisig: depositMultisigPda,
+          transaction: config.transactionPda,
+        } as any)
+        .signers([owner1])
+        .rpc();
+    });
+
+    it("✅ Should lock the deposit in new proposals", async () => {
+      const multisig = await program.account.multisig.fetch(depositMultisigPda);
+      expect(multisig.proposalDeposit.toNumber()).to.equal(deposit);
+
+      const { transactionId, transactionPda } = await proposeOn((nonce) =>
+        program.methods.proposeTransaction(testInstruction, nonce, { transfer: {} }, null, null)
+      );
+
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.deposit.toNumber()).to.equal(deposit);
+
+      const accountInfo = await provider.connection.getAccountInfo(transactionPda);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(accountInfo.data.length);
+      expect(accountInfo.lamports).to.equal(rent + deposit);
+
+      try {
+        await releaseDeposit(transactionId, transactionPda);
+        expect.fail("Should have failed while the proposal is pending");
+      } catch (error) {
+        expect(error.error.errorCode.code).to.equal("DepositLocked");
+        console.log("✅ Deposit stays locked while the proposal is pending");
+      }
+    });
+
+    it("✅ Should forfeit the deposit to the vault when owners reject", async () => {
+      const { transactionId, transactionPda } = await proposeOn((nonce) =>
+        program.methods.proposeTransaction(testInstruction, nonce, { transfer: {} }, null, null)
+      );
+
+      // Two of three owners rejecting leaves too few to reach the threshold of two
+      for (const owner of [owner2, owner3]) {
+        await program.methods
+          .rejectTransaction(new anchor.BN(transactionId))
+          .accounts({
+            owner: owner.publicKey,
+            multisig: depositMultisigPda,
+            transaction: transactionPda,
+          } as any)
+          .signers([owner])
+          .rpc();
+      }
+
+      const transaction = await program.account.transaction.fetch(transactionPda);
+      expect(transaction.rejected).to.be.true;
+
+      const vaultBalance = await provider.connection.getBalance(vaultPda);
+      await releaseDeposit(transactionId, transactionPda);
+
+      expect(await provider.connection.getBalance(vaultPda)).to.equal(vaultBalance + deposit);
+      expect((await program.account.transaction.fetch(transactionPda)).deposit.toNumber()).to.equal(0);
+
+      console.log("✅ Rejected proposal's deposit sent to the vault!");
+    });
+
+    it("✅ Should refund the deposit once the proposal executes", async () => {
+      const { transactionId, transactionPda } = await proposeOn((nonce) =>
+        program.methods.proposeTransaction(testInstruction, nonce, { transfer: {} }, null, null)
+      );
+      await approve(transactionId, transactionPda, [owner1, owner2]);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      await program.methods
+        .executeTransaction(new anchor.BN(transactionId))
+        .accounts({
+          executor: owner1.publicKey,
+          multisig: depositMultisigPda,
+          transaction: transactionPda,
+        } as any)
+        .signers([owner1])
+        .rpc();
+
+      const proposerBalance = await provider.connection.getBalance(owner1.publicKey);
+      await releaseDeposit(transactionId, transactionPda);
+
+      expect(await provider.connection.getBalance(owner1.publicKey)).to.equal(proposerBalance + deposit);
+
+      console.log("✅ Executed proposal's deposit refunded to the proposer!");
+    });
+
+    it("✅ Should refund held deposits to their proposers when the multisig closes", async () => {
+      const destination = Keypair.generate();
+
+      const pending = await proposeOn((nonce) =>
+        program.methods.proposeTransaction(testInstruction, nonce, { transfer: {} }, null, null)
+      );
+      const close = await proposeOn((nonce) =>
+        program.methods.proposeCloseMultisig(destination.publicKey, nonce, null, null)
+      );
+      await approve(close.transactionId, close.transactionPda, [owner1, owner2]);
+      await new Promise(resolve => setTimeout(resolve, 1000));
+
+      const proposerBalance = await provider.connection.getBalance(owner1.publicKey);
+
+      await program.methods
+        .closeMultisig(new anchor.BN(close.transactionId), 0)
+        .accounts({
+          executor: owner2.publicKey,
+          multisig: depositMultisigPda,
+          transaction: close.transactionPda,
+          destination: destination.publicKey,
+          proposer: owner1.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .remainingAccounts(await closeRemainingAccounts(depositMultisigPda, close.transactionId))
+        .signers([owner2])
+        .rpc();
+
+      // The deposits of the first locked proposal, the pending one and the close
+      // proposal come back; the rejected and executed ones were already released
+      expect(await provider.connection.getBalance(owner1.publicKey)).to.equal(proposerBalance + 3 * deposit);
+      expect(await provider.connection.getAccountInfo(pending.transactionPda)).to.be.null;
+
+      console.log("✅ Held deposits refunded on close!");
+    });
+  });
 });
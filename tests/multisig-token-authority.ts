@@ -0,0 +1,264 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { MultisigModule } from "../target/types/multisig_module";
+import { TokenModule } from "../target/types/token_module";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountInstruction,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+// Runs against a validator with both programs deployed, see the root Anchor.toml
+describe("Multisig vault as token-module admin", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const multisigProgram = anchor.workspace.MultisigModule as Program<MultisigModule>;
+  const tokenProgram = anchor.workspace.TokenModule as Program<TokenModule>;
+
+  const creator = Keypair.generate();
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const holder = Keypair.generate();
+  const mint = Keypair.generate();
+
+  const TOKEN_DECIMALS = 6;
+
+  let multisigPda: PublicKey;
+  let vaultPda: PublicKey;
+  let mintAuthorityPda: PublicKey;
+  let tokenMetadataPda: PublicKey;
+  let holderAta: PublicKey;
+
+  // Same layout as `compile_vault_message` in the Rust client: keys in order
+  // of first use, writable if any instruction writes them
+  const compileVaultMessage = (instructions: TransactionInstruction[]) => {
+    const keys: { pubkey: PublicKey; isWritable: boolean }[] = [];
+    const indexOf = (pubkey: PublicKey, isWritable: boolean) => {
+      let index = keys.findIndex((key) => key.pubkey.equals(pubkey));
+      if (index < 0) {
+        keys.push({ pubkey, isWritable });
+        index = keys.length - 1;
+      }
+      keys[index].isWritable = keys[index].isWritable || isWritable;
+      return index;
+    };
+
+    const message = {
+      accountKeys: [] as PublicKey[],
+      lookups: [],
+      instructions: instructions.map((instruction) => ({
+        programIdIndex: indexOf(instruction.programId, false),
+        accounts: instruction.keys.map((meta) => ({
+          index: indexOf(meta.pubkey, meta.isWritable),
+          isSigner: meta.isSigner,
+          isWritable: meta.isWritable,
+        })),
+        data: instruction.data,
+      })),
+    };
+    message.accountKeys = keys.map((key) => key.pubkey);
+
+    const remainingAccounts = keys.map((key) => ({
+      pubkey: key.pubkey,
+      isSigner: false,
+      isWritable: key.isWritable,
+    }));
+    return { message, remainingAccounts };
+  };
+
+  // Propose, approve with both owners and execute through the vault
+  const runThroughMultisig = async (instruction: TransactionInstruction) => {
+    // Proposals are rate limited to one every few slots
+    await new Promise((resolve) => setTimeout(resolve, 1500));
+
+    const multisig = await multisigProgram.account.multisig.fetch(multisigPda);
+    const transactionId = multisig.transactionCount;
+    const [transactionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("transaction"),
+        multisigPda.toBuffer(),
+        Buffer.from(transactionId.toArrayLike(Buffer, "le", 8)),
+      ],
+      multisigProgram.programId
+    );
+
+    const { message, remainingAccounts } = compileVaultMessage([instruction]);
+
+    await multisigProgram.methods
+      .proposeVaultTransaction(message, multisig.nonce, { custom: {} }, null, null)
+      .accounts({
+        proposer: owner1.publicKey,
+        multisig: multisigPda,
+        transaction: transactionPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([owner1])
+      .rpc();
+
+    for (const owner of [owner1, owner2]) {
+      await multisigProgram.methods
+        .approveTransaction(transactionId)
+        .accounts({
+          approver: owner.publicKey,
+          multisig: multisigPda,
+          transaction: transactionPda,
+        } as any)
+        .signers([owner])
+        .rpc();
+    }
+
+    // Wait for the same-slot protection
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    return multisigProgram.methods
+      .executeVaultTransaction(transactionId)
+      .accounts({
+        executor: owner1.publicKey,
+        multisig: multisigPda,
+        transaction: transactionPda,
+      } as any)
+      .remainingAccounts(remainingAccounts)
+      .signers([owner1])
+      .rpc();
+  };
+
+  const adminAccounts = () => ({
+    admin: vaultPda,
+    mint: mint.publicKey,
+    tokenMetadata: tokenMetadataPda,
+    mintAuthority: mintAuthorityPda,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  before(async () => {
+    const signatures = await Promise.all(
+      [creator, owner1, owner2, holder].map((keypair) =>
+        provider.connection.requestAirdrop(keypair.publicKey, 2 * LAMPORTS_PER_SOL)
+      )
+    );
+    await Promise.all(signatures.map((signature) => provider.connection.confirmTransaction(signature, "confirmed")));
+
+    [multisigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig"), creator.publicKey.toBuffer()],
+      multisigProgram.programId
+    );
+    [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), multisigPda.toBuffer()],
+      multisigProgram.programId
+    );
+    [mintAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), mint.publicKey.toBuffer()],
+      tokenProgram.programId
+    );
+    [tokenMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("token_metadata"), mint.publicKey.toBuffer()],
+      tokenProgram.programId
+    );
+
+    await multisigProgram.methods
+      .createMultisig([owner1.publicKey, owner2.publicKey], 2, 2)
+      .accounts({
+        creator: creator.publicKey,
+        multisig: multisigPda,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .signers([creator])
+      .rpc();
+
+    await tokenProgram.methods
+      .createToken("Multisig Token", "MST", TOKEN_DECIMALS, null, null)
+      .accounts({
+        admin: creator.publicKey,
+        mint: mint.publicKey,
+        tokenMetadata: tokenMetadataPda,
+        mintAuthority: mintAuthorityPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+        designatedAdmin: vaultPda,
+      } as any)
+      .signers([creator, mint])
+      .rpc();
+
+    holderAta = getAssociatedTokenAddressSync(mint.publicKey, holder.publicKey);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          holder.publicKey,
+          holderAta,
+          holder.publicKey,
+          mint.publicKey,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID
+        )
+      ),
+      [holder]
+    );
+  });
+
+  it("✅ Should record the vault as the token admin", async () => {
+    const metadata = await tokenProgram.account.tokenMetadata.fetch(tokenMetadataPda);
+    expect(metadata.admin.toString()).to.equal(vaultPda.toString());
+  });
+
+  it("❌ Should not let the payer mint once the vault is admin", async () => {
+    try {
+      await tokenProgram.methods
+        .mintTokens(new BN(1))
+        .accounts({ ...adminAccounts(), admin: creator.publicKey, destination: holderAta } as any)
+        .signers([creator])
+        .rpc();
+      expect.fail("Should have failed with an unauthorized admin");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnauthorizedMintAuthority");
+      console.log("✅ Payer can no longer mint directly");
+    }
+  });
+
+  it("✅ Should mint through an approved multisig vault transaction", async () => {
+    const amount = new BN(5).mul(new BN(10).pow(new BN(TOKEN_DECIMALS)));
+    const mintInstruction = await tokenProgram.methods
+      .mintTokens(amount)
+      .accounts({ ...adminAccounts(), destination: holderAta } as any)
+      .instruction();
+
+    const tx = await runThroughMultisig(mintInstruction);
+    console.log("📜 Multisig mint tx:", tx);
+
+    const account = await getAccount(provider.connection, holderAta);
+    expect(account.amount.toString()).to.equal(amount.toString());
+
+    console.log("✅ Minted through the multisig vault!");
+  });
+
+  it("✅ Should freeze and thaw through approved multisig vault transactions", async () => {
+    const freezeInstruction = await tokenProgram.methods
+      .freezeTokenAccount()
+      .accounts({ ...adminAccounts(), tokenAccount: holderAta } as any)
+      .instruction();
+    await runThroughMultisig(freezeInstruction);
+    expect((await getAccount(provider.connection, holderAta)).isFrozen).to.be.true;
+
+    const thawInstruction = await tokenProgram.methods
+      .thawTokenAccount()
+      .accounts({ ...adminAccounts(), tokenAccount: holderAta } as any)
+      .instruction();
+    await runThroughMultisig(thawInstruction);
+    expect((await getAccount(provider.connection, holderAta)).isFrozen).to.be.false;
+
+    console.log("✅ Froze and thawed through the multisig vault!");
+  });
+});
@@ -34,4 +34,91 @@ pub enum TokenError {
 
     #[msg("Amount must be greater than zero")]
     ZeroAmount,
+
+    #[msg("Signer is not the current admin")]
+    UnauthorizedAdmin,
+
+    #[msg("No admin handover is pending")]
+    NoPendingAdmin,
+
+    #[msg("Signer is not the pending admin")]
+    UnauthorizedPendingAdmin,
+
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("Mint is not controlled by the global token authority")]
+    MintAlreadyMigrated,
+
+    #[msg("Minter quota exceeded")]
+    MinterQuotaExceeded,
+
+    #[msg("Quota is only valid for Minter roles with a non-zero period")]
+    InvalidRoleQuota,
+
+    #[msg("Token is paused")]
+    Paused,
+
+    #[msg("Token is not paused")]
+    NotPaused,
+
+    #[msg("Unauthorized pause authority")]
+    UnauthorizedPauseAuthority,
+
+    #[msg("Refund time must be after the release time")]
+    InvalidEscrowSchedule,
+
+    #[msg("Escrow is still time-locked")]
+    EscrowLocked,
+
+    #[msg("Escrow has no refund time")]
+    EscrowNotRefundable,
+
+    #[msg("Milestones must be non-zero, at most 10, and sum to the escrow amount")]
+    InvalidMilestones,
+
+    #[msg("Milestone index out of range")]
+    InvalidMilestoneIndex,
+
+    #[msg("Milestone has already been released")]
+    MilestoneAlreadyReleased,
+
+    #[msg("Milestone is neither approved nor due")]
+    MilestoneLocked,
+
+    #[msg("Escrow releases by milestone")]
+    EscrowHasMilestones,
+
+    #[msg("Arbiter must differ from both parties and needs a named recipient")]
+    InvalidArbiter,
+
+    #[msg("Escrow has no arbiter")]
+    NoArbiter,
+
+    #[msg("Only the sender or recipient can raise a dispute")]
+    UnauthorizedDisputeParty,
+
+    #[msg("Escrow is disputed")]
+    EscrowDisputed,
+
+    #[msg("Escrow is not disputed")]
+    EscrowNotDisputed,
+
+    #[msg("Signer is not the escrow's arbiter")]
+    UnauthorizedArbiter,
+
+    #[msg("Recipient share exceeds the remaining escrow amount")]
+    InvalidDisputeSplit,
+
+    #[msg("Vesting needs a positive duration and interval, with interval and cliff within the duration")]
+    InvalidVestingSchedule,
+
+    #[msg("Nothing has vested since the last claim")]
+    NothingToClaim,
+
+    #[msg("Signer cannot revoke this vesting")]
+    UnauthorizedRevokeAuthority,
+
+    #[msg("Vesting has already been revoked")]
+    VestingRevoked,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::{RoleKind, MinterQuota, VestingSchedule};
 
 
 #[event]
@@ -11,6 +12,18 @@ pub struct TokenCreated {
     pub max_supply: Option<u64>,
 }
 
+#[event]
+pub struct TokenMetadataUpdated {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub old_name: String,
+    pub new_name: String,
+    pub old_symbol: String,
+    pub new_symbol: String,
+    pub old_uri: Option<String>,
+    pub new_uri: Option<String>,
+}
+
 #[event]
 pub struct TokenMinted {
     pub mint: Pubkey,
@@ -48,6 +61,9 @@ pub struct EscrowCreated {
     pub mint: Pubkey,
     pub amount: u64,
     pub recipient: Option<Pubkey>,
+    pub release_after: Option<i64>,
+    pub refund_after: Option<i64>,
+    pub arbiter: Option<Pubkey>,
 }
 
 #[event]
@@ -56,4 +72,160 @@ pub struct EscrowReleased {
     pub recipient: Pubkey,
     pub mint: Pubkey,
     pub amount: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MilestoneApproved {
+    pub escrow: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub index: u8,
+    pub amount: u64,
+    pub released_amount: u64,
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct EscrowDisputed {
+    pub escrow: Pubkey,
+    pub raised_by: Pubkey,
+}
+
+#[event]
+pub struct EscrowResolved {
+    pub escrow: Pubkey,
+    pub arbiter: Pubkey,
+    pub recipient_amount: u64,
+    pub sender_amount: u64,
+}
+
+#[event]
+pub struct EscrowCancelled {
+    pub escrow: Pubkey,
+    pub sender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SolEscrowCreated {
+    pub escrow: Pubkey,
+    pub sender: Pubkey,
+    pub amount: u64,
+    pub recipient: Option<Pubkey>,
+    pub release_after: Option<i64>,
+    pub refund_after: Option<i64>,
+}
+
+#[event]
+pub struct SolEscrowReleased {
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SolEscrowCancelled {
+    pub escrow: Pubkey,
+    pub sender: Pubkey,
+    pub amount: u64,
+}
+
+/// `account` is the token authority or a token metadata account; `mint` is
+/// `None` for the token authority
+#[event]
+pub struct AdminHandoverProposed {
+    pub account: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub admin: Pubkey,
+    /// `None` cancels an earlier proposal
+    pub pending_admin: Option<Pubkey>,
+}
+
+#[event]
+pub struct AdminHandoverAccepted {
+    pub account: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminRenounced {
+    pub account: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub old_admin: Pubkey,
+}
+
+#[event]
+pub struct MintAuthorityMigrated {
+    pub mint: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub role: RoleKind,
+    pub quota: MinterQuota,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub role: RoleKind,
+    pub total_minted: u64,
+}
+
+#[event]
+pub struct TokenPaused {
+    pub mint: Pubkey,
+    pub paused_by: Pubkey,
+}
+
+#[event]
+pub struct TokenUnpaused {
+    pub mint: Pubkey,
+    pub unpaused_by: Pubkey,
+}
+
+#[event]
+pub struct VestingCreated {
+    pub vesting: Pubkey,
+    pub issuer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub schedule: VestingSchedule,
+    pub revoke_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub vesting: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    /// The vesting was fully claimed and closed to the issuer
+    pub closed: bool,
+}
+
+#[event]
+pub struct VestingRevoked {
+    pub vesting: Pubkey,
+    pub revoked_by: Pubkey,
+    pub vested_amount: u64,
+    pub returned_amount: u64,
+    /// Nothing was left to claim, so the vesting was closed to the issuer
+    pub closed: bool,
+}
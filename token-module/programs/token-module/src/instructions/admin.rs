@@ -1,26 +1,33 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, FreezeAccount, ThawAccount};
-use crate::{  TokenError, TokenAccountFrozen, TokenAccountThawed, InitializeTokenAuthority, FreezeTokenAccount, ThawTokenAccount};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, FreezeAccount, ThawAccount, SetAuthority};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use crate::{  TokenError, TokenAccountFrozen, TokenAccountThawed, AdminHandoverProposed, AdminHandoverAccepted, AdminRenounced, MintAuthorityMigrated, TokenPaused, TokenUnpaused,
+    InitializeTokenAuthority, FreezeTokenAccount, ThawTokenAccount, MigrateMintAuthority, SetTokenPaused,
+    ManageTokenAuthorityAdmin, AcceptTokenAuthorityAdmin, ManageTokenAdmin, AcceptTokenAdmin, TokenAuthority, TokenMetadata};
 
 
 pub fn initialize_token_authority(ctx: Context<InitializeTokenAuthority>) -> Result<()> {
     let authority = &mut ctx.accounts.token_authority;
     authority.admin = ctx.accounts.admin.key();
     authority.bump = ctx.bumps.token_authority;
+    authority.pending_admin = None;
     
-    msg!("Token authority initialized with admin: {}", authority.admin);
+    msg!("Token authority initialized with admin: {}", ctx.accounts.admin.key());
     Ok(())
 }
 
 pub fn freeze_token_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
     require!(
-        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        ctx.accounts.token_metadata.authorizes(&ctx.accounts.admin.key(), ctx.accounts.role.is_some()),
         TokenError::UnauthorizedFreezeAuthority
     );
 
+    let mint_key = ctx.accounts.mint.key();
     let authority_seeds = &[
-        b"token_authority".as_ref(),
-        &[ctx.accounts.token_authority.bump]
+        b"mint_authority".as_ref(),
+        mint_key.as_ref(),
+        &[ctx.bumps.mint_authority]
     ];
 
     token_interface::freeze_account(
@@ -29,7 +36,7 @@ pub fn freeze_token_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
             FreezeAccount {
                 account: ctx.accounts.token_account.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
-                authority: ctx.accounts.token_authority.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
             },
             &[authority_seeds]
         )
@@ -47,13 +54,15 @@ pub fn freeze_token_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
 
 pub fn thaw_token_account(ctx: Context<ThawTokenAccount>) -> Result<()> {
     require!(
-        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        ctx.accounts.token_metadata.authorizes(&ctx.accounts.admin.key(), ctx.accounts.role.is_some()),
         TokenError::UnauthorizedFreezeAuthority
     );
 
+    let mint_key = ctx.accounts.mint.key();
     let authority_seeds = &[
-        b"token_authority".as_ref(),
-        &[ctx.accounts.token_authority.bump]
+        b"mint_authority".as_ref(),
+        mint_key.as_ref(),
+        &[ctx.bumps.mint_authority]
     ];
 
     token_interface::thaw_account(
@@ -62,7 +71,7 @@ pub fn thaw_token_account(ctx: Context<ThawTokenAccount>) -> Result<()> {
             ThawAccount {
                 account: ctx.accounts.token_account.to_account_info(),
                 mint: ctx.accounts.mint.to_account_info(),
-                authority: ctx.accounts.token_authority.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
             },
             &[authority_seeds]
         )
@@ -76,4 +85,244 @@ pub fn thaw_token_account(ctx: Context<ThawTokenAccount>) -> Result<()> {
 
     msg!("Thawed token account {}", ctx.accounts.token_account.key());
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn pause_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+    require!(
+        ctx.accounts.token_metadata.authorizes(&ctx.accounts.admin.key(), ctx.accounts.role.is_some()),
+        TokenError::UnauthorizedPauseAuthority
+    );
+    require!(!ctx.accounts.token_metadata.paused, TokenError::Paused);
+
+    ctx.accounts.token_metadata.paused = true;
+
+    emit!(TokenPaused {
+        mint: ctx.accounts.mint.key(),
+        paused_by: ctx.accounts.admin.key(),
+    });
+
+    msg!("Paused token {}", ctx.accounts.mint.key());
+    Ok(())
+}
+
+pub fn unpause_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+    require!(
+        ctx.accounts.token_metadata.authorizes(&ctx.accounts.admin.key(), ctx.accounts.role.is_some()),
+        TokenError::UnauthorizedPauseAuthority
+    );
+    require!(ctx.accounts.token_metadata.paused, TokenError::NotPaused);
+
+    ctx.accounts.token_metadata.paused = false;
+
+    emit!(TokenUnpaused {
+        mint: ctx.accounts.mint.key(),
+        unpaused_by: ctx.accounts.admin.key(),
+    });
+
+    msg!("Unpaused token {}", ctx.accounts.mint.key());
+    Ok(())
+}
+
+/// Hands the mint and freeze authority of a mint created under the global
+/// `token_authority` PDA over to its own `mint_authority` PDA. Permissionless:
+/// the program controls both PDAs and `token_metadata.admin` is unchanged.
+/// `token_authority` and `token_metadata` are grown to their current layout
+/// on the way, so accounts written by earlier versions deserialize again.
+pub fn migrate_mint_authority(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+    let global_authority = ctx.accounts.token_authority.key();
+    require!(
+        ctx.accounts.mint.mint_authority == Some(global_authority).into(),
+        TokenError::MintAlreadyMigrated
+    );
+
+    grow_account(
+        &ctx.accounts.token_authority,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + TokenAuthority::INIT_SPACE
+    )?;
+    if let Some(token_metadata) = &ctx.accounts.token_metadata {
+        let len = TokenMetadata::migrated_space(&token_metadata.try_borrow_data()?)?;
+        grow_account(token_metadata, &ctx.accounts.payer, &ctx.accounts.system_program, len)?;
+    }
+
+    let authority_seeds = &[
+        b"token_authority".as_ref(),
+        &[ctx.bumps.token_authority]
+    ];
+
+    let mut authority_types = vec![AuthorityType::MintTokens];
+    if ctx.accounts.mint.freeze_authority == Some(global_authority).into() {
+        authority_types.push(AuthorityType::FreezeAccount);
+    }
+    for authority_type in authority_types {
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.token_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[authority_seeds]
+            ),
+            authority_type,
+            Some(ctx.accounts.mint_authority.key())
+        )?;
+    }
+
+    emit!(MintAuthorityMigrated {
+        mint: ctx.accounts.mint.key(),
+        old_authority: global_authority,
+        new_authority: ctx.accounts.mint_authority.key(),
+    });
+
+    msg!("Migrated mint {} to authority {}", ctx.accounts.mint.key(), ctx.accounts.mint_authority.key());
+    Ok(())
+}
+
+/// Reallocates `account` to at least `len` bytes, topping up its rent from
+/// `payer`. The new bytes are zero, as fresh realloc space always is.
+pub(crate) fn grow_account<'info>(account: &AccountInfo<'info>, payer: &Signer<'info>, system_program: &Program<'info, System>, len: usize) -> Result<()> {
+    if account.data_len() >= len {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                }
+            ),
+            shortfall
+        )?;
+    }
+    account.realloc(len, false)?;
+    Ok(())
+}
+
+// Two-step admin handover, shared by the token authority and token metadata.
+// The current admin proposes a successor (or clears the proposal with `None`)
+// and the successor accepts by signing; renouncing resets the admin to
+// `Pubkey::default()` for good, which no signer can match.
+
+fn propose_admin(admin: &Pubkey, pending_admin: &mut Option<Pubkey>, signer: Pubkey, new_admin: Option<Pubkey>) -> Result<()> {
+    require!(*admin == signer, TokenError::UnauthorizedAdmin);
+    require!(new_admin != Some(Pubkey::default()), TokenError::InvalidPendingAdmin);
+    *pending_admin = new_admin;
+    Ok(())
+}
+
+fn accept_admin(admin: &mut Pubkey, pending_admin: &mut Option<Pubkey>, signer: Pubkey) -> Result<Pubkey> {
+    require!(pending_admin.is_some(), TokenError::NoPendingAdmin);
+    require!(*pending_admin == Some(signer), TokenError::UnauthorizedPendingAdmin);
+    let old_admin = std::mem::replace(admin, signer);
+    *pending_admin = None;
+    Ok(old_admin)
+}
+
+fn renounce_admin(admin: &mut Pubkey, pending_admin: &mut Option<Pubkey>, signer: Pubkey) -> Result<()> {
+    require!(*admin == signer, TokenError::UnauthorizedAdmin);
+    *admin = Pubkey::default();
+    *pending_admin = None;
+    Ok(())
+}
+
+pub fn propose_token_authority_admin(ctx: Context<ManageTokenAuthorityAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    let account = ctx.accounts.token_authority.key();
+    let authority = &mut *ctx.accounts.token_authority;
+    propose_admin(&authority.admin, &mut authority.pending_admin, ctx.accounts.admin.key(), new_admin)?;
+
+    emit!(AdminHandoverProposed {
+        account,
+        mint: None,
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+    });
+
+    msg!("Token authority admin handover proposed to {:?}", new_admin);
+    Ok(())
+}
+
+pub fn accept_token_authority_admin(ctx: Context<AcceptTokenAuthorityAdmin>) -> Result<()> {
+    let account = ctx.accounts.token_authority.key();
+    let authority = &mut *ctx.accounts.token_authority;
+    let old_admin = accept_admin(&mut authority.admin, &mut authority.pending_admin, ctx.accounts.pending_admin.key())?;
+
+    emit!(AdminHandoverAccepted {
+        account,
+        mint: None,
+        old_admin,
+        new_admin: ctx.accounts.pending_admin.key(),
+    });
+
+    msg!("Token authority admin is now {}", ctx.accounts.pending_admin.key());
+    Ok(())
+}
+
+pub fn renounce_token_authority_admin(ctx: Context<ManageTokenAuthorityAdmin>) -> Result<()> {
+    let account = ctx.accounts.token_authority.key();
+    let authority = &mut *ctx.accounts.token_authority;
+    renounce_admin(&mut authority.admin, &mut authority.pending_admin, ctx.accounts.admin.key())?;
+
+    emit!(AdminRenounced {
+        account,
+        mint: None,
+        old_admin: ctx.accounts.admin.key(),
+    });
+
+    msg!("Token authority admin renounced by {}", ctx.accounts.admin.key());
+    Ok(())
+}
+
+pub fn propose_token_admin(ctx: Context<ManageTokenAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    let account = ctx.accounts.token_metadata.key();
+    let metadata = &mut *ctx.accounts.token_metadata;
+    propose_admin(&metadata.admin, &mut metadata.pending_admin, ctx.accounts.admin.key(), new_admin)?;
+
+    emit!(AdminHandoverProposed {
+        account,
+        mint: Some(ctx.accounts.mint.key()),
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+    });
+
+    msg!("Admin handover for mint {} proposed to {:?}", ctx.accounts.mint.key(), new_admin);
+    Ok(())
+}
+
+pub fn accept_token_admin(ctx: Context<AcceptTokenAdmin>) -> Result<()> {
+    let account = ctx.accounts.token_metadata.key();
+    let metadata = &mut *ctx.accounts.token_metadata;
+    let old_admin = accept_admin(&mut metadata.admin, &mut metadata.pending_admin, ctx.accounts.pending_admin.key())?;
+
+    emit!(AdminHandoverAccepted {
+        account,
+        mint: Some(ctx.accounts.mint.key()),
+        old_admin,
+        new_admin: ctx.accounts.pending_admin.key(),
+    });
+
+    msg!("Admin of mint {} is now {}", ctx.accounts.mint.key(), ctx.accounts.pending_admin.key());
+    Ok(())
+}
+
+/// Leaves the mint without an admin: no further minting, freezing or thawing.
+/// A paused mint must be unpaused first, since nobody could unpause it after.
+pub fn renounce_token_admin(ctx: Context<ManageTokenAdmin>) -> Result<()> {
+    let account = ctx.accounts.token_metadata.key();
+    let metadata = &mut *ctx.accounts.token_metadata;
+    require!(!metadata.paused, TokenError::Paused);
+    renounce_admin(&mut metadata.admin, &mut metadata.pending_admin, ctx.accounts.admin.key())?;
+
+    emit!(AdminRenounced {
+        account,
+        mint: Some(ctx.accounts.mint.key()),
+        old_admin: ctx.accounts.admin.key(),
+    });
+
+    msg!("Admin of mint {} renounced by {}", ctx.accounts.mint.key(), ctx.accounts.admin.key());
+    Ok(())
+}
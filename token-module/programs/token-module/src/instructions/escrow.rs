@@ -1,14 +1,41 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{self, TransferChecked};
-use crate::{TokenError, EscrowCreated, EscrowReleased, 
-    CreateEscrow, ReleaseEscrow};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, TransferChecked, CloseAccount};
+use crate::{TokenError, TokenMetadata, EscrowCreated, EscrowReleased, EscrowCancelled,
+    MilestoneApproved, MilestoneReleased, EscrowDisputed, EscrowResolved, Milestone, EscrowTerms, EscrowStatus,
+    CreateEscrow, ReleaseEscrow, CancelEscrow, ApproveMilestone, ReleaseMilestone, RaiseDispute, ResolveDispute,
+    SolEscrowCreated, SolEscrowReleased, SolEscrowCancelled, CreateSolEscrow, ReleaseSolEscrow, CancelSolEscrow,
+    Escrow, MigrateEscrow};
+use crate::instructions::admin::grow_account;
 
 pub fn create_escrow(
         ctx: Context<CreateEscrow>,
         amount: u64,
         seed: [u8; 32],
         recipient: Option<Pubkey>,
+        terms: EscrowTerms,
     ) -> Result<()> {
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+        let EscrowTerms { release_after, refund_after, milestones, arbiter } = terms;
+        if let (Some(release_after), Some(refund_after)) = (release_after, refund_after) {
+            require!(refund_after > release_after, TokenError::InvalidEscrowSchedule);
+        }
+        if !milestones.is_empty() {
+            require!(milestones.len() <= 10, TokenError::InvalidMilestones);
+            let mut total: u64 = 0;
+            for milestone in &milestones {
+                require!(milestone.amount > 0, TokenError::InvalidMilestones);
+                total = total.checked_add(milestone.amount).ok_or(TokenError::ArithmeticOverflow)?;
+            }
+            require!(total == amount, TokenError::InvalidMilestones);
+        }
+        if let Some(arbiter) = arbiter {
+            require!(
+                recipient.is_some_and(|recipient| recipient != arbiter) && arbiter != ctx.accounts.sender.key(),
+                TokenError::InvalidArbiter
+            );
+        }
+
         let escrow = &mut ctx.accounts.escrow;
         escrow.sender = ctx.accounts.sender.key();
         escrow.mint = ctx.accounts.mint.key();
@@ -17,6 +44,20 @@ pub fn create_escrow(
         escrow.recipient = recipient;
         escrow.claimed = false;
         escrow.bump = ctx.bumps.escrow;
+        escrow.release_after = release_after;
+        escrow.refund_after = refund_after;
+        escrow.milestones = milestones
+            .iter()
+            .map(|terms| Milestone {
+                amount: terms.amount,
+                release_at: terms.release_at,
+                approved: false,
+                released: false,
+            })
+            .collect();
+        escrow.released_amount = 0;
+        escrow.arbiter = arbiter;
+        escrow.status = EscrowStatus::Active;
 
         token_interface::transfer_checked(
             CpiContext::new(
@@ -38,6 +79,9 @@ pub fn create_escrow(
             mint: ctx.accounts.mint.key(),
             amount,
             recipient: escrow.recipient,
+            release_after,
+            refund_after,
+            arbiter,
         });
 
         msg!("Created escrow with {} tokens", amount);
@@ -46,6 +90,8 @@ pub fn create_escrow(
     }
 
 pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
         let escrow = &mut ctx.accounts.escrow;
 
         if let Some(recipient) = escrow.recipient {
@@ -61,6 +107,11 @@ pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
             )
         }
         require!(!escrow.claimed, TokenError::EscrowAlreadyClaimed);
+        require!(escrow.milestones.is_empty(), TokenError::EscrowHasMilestones);
+        require!(escrow.status == EscrowStatus::Active, TokenError::EscrowDisputed);
+        if let Some(release_after) = escrow.release_after {
+            require!(Clock::get()?.unix_timestamp >= release_after, TokenError::EscrowLocked);
+        }
 
         escrow.claimed = true;
 
@@ -95,4 +146,356 @@ pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
 
         msg!("Released {} tokens from escrow to {}", escrow.amount, ctx.accounts.recipient.key());
         Ok(())
-    }
\ No newline at end of file
+    }
+
+pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.status == EscrowStatus::Active, TokenError::EscrowDisputed);
+        let milestone = escrow.milestones
+            .get_mut(index as usize)
+            .ok_or(TokenError::InvalidMilestoneIndex)?;
+        require!(!milestone.released, TokenError::MilestoneAlreadyReleased);
+
+        milestone.approved = true;
+        let amount = milestone.amount;
+
+        emit!(MilestoneApproved {
+            escrow: escrow.key(),
+            index,
+            amount,
+        });
+
+        msg!("Approved milestone {} of escrow {}", index, escrow.key());
+        Ok(())
+    }
+
+/// Releases one tranche once the sender approved it or its `release_at` has
+/// passed. The escrow and its token account close when the last tranche is out.
+pub fn release_milestone(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.claimant() == ctx.accounts.recipient.key(), TokenError::UnauthorizedRecipient);
+        require!(escrow.status == EscrowStatus::Active, TokenError::EscrowDisputed);
+        if let Some(release_after) = escrow.release_after {
+            require!(now >= release_after, TokenError::EscrowLocked);
+        }
+
+        let milestone = escrow.milestones
+            .get_mut(index as usize)
+            .ok_or(TokenError::InvalidMilestoneIndex)?;
+        require!(!milestone.released, TokenError::MilestoneAlreadyReleased);
+        require!(
+            milestone.approved || milestone.release_at.is_some_and(|release_at| now >= release_at),
+            TokenError::MilestoneLocked
+        );
+        milestone.released = true;
+        let amount = milestone.amount;
+
+        escrow.released_amount = escrow.released_amount
+            .checked_add(amount)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        let fully_released = escrow.milestones.iter().all(|milestone| milestone.released);
+        escrow.claimed = fully_released;
+
+        let escrow_key = escrow.key();
+        let escrow_authority_seeds = &[
+                b"escrow_authority".as_ref(),
+                escrow_key.as_ref(),
+                &[ctx.bumps.escrow_authority],
+            ];
+
+        // The last tranche takes the whole balance so the token account can close
+        let transfer_amount = match fully_released {
+            true => ctx.accounts.escrow_token_account.amount,
+            false => amount,
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[escrow_authority_seeds]
+            ),
+            transfer_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        if fully_released {
+            token_interface::close_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    CloseAccount {
+                        account: ctx.accounts.escrow_token_account.to_account_info(),
+                        destination: ctx.accounts.recipient.to_account_info(),
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    &[escrow_authority_seeds]
+                ),
+            )?;
+        }
+
+        emit!(MilestoneReleased {
+            escrow: escrow_key,
+            recipient: ctx.accounts.recipient.key(),
+            index,
+            amount,
+            released_amount: escrow.released_amount,
+            remaining_amount: escrow.remaining_amount(),
+        });
+
+        if fully_released {
+            ctx.accounts.escrow.close(ctx.accounts.recipient.to_account_info())?;
+        }
+
+        msg!("Released milestone {} of escrow {}: {} tokens", index, escrow_key, amount);
+        Ok(())
+    }
+
+/// Either party of an arbitrated escrow can lock it until the arbiter rules
+pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.arbiter.is_some(), TokenError::NoArbiter);
+        let party = ctx.accounts.party.key();
+        require!(
+            party == escrow.sender || Some(party) == escrow.recipient,
+            TokenError::UnauthorizedDisputeParty
+        );
+        require!(escrow.status == EscrowStatus::Active, TokenError::EscrowDisputed);
+
+        escrow.status = EscrowStatus::Disputed;
+
+        emit!(EscrowDisputed {
+            escrow: escrow.key(),
+            raised_by: party,
+        });
+
+        msg!("Dispute raised on escrow {} by {}", escrow.key(), party);
+        Ok(())
+    }
+
+/// The arbiter pays `recipient_amount` of what is left to the recipient and
+/// the rest back to the sender, then closes the escrow and its token account
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, recipient_amount: u64) -> Result<()> {
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        require!(escrow.arbiter == Some(ctx.accounts.arbiter.key()), TokenError::UnauthorizedArbiter);
+        require!(escrow.status == EscrowStatus::Disputed, TokenError::EscrowNotDisputed);
+
+        let remaining_amount = escrow.remaining_amount();
+        require!(recipient_amount <= remaining_amount, TokenError::InvalidDisputeSplit);
+        // The sender gets the rest of the whole balance so the token account can close
+        let sender_amount = ctx.accounts.escrow_token_account.amount
+            .checked_sub(recipient_amount)
+            .ok_or(TokenError::InvalidDisputeSplit)?;
+
+        escrow.status = EscrowStatus::Resolved;
+        escrow.claimed = true;
+        escrow.released_amount = escrow.amount;
+
+        let escrow_key = escrow.key();
+        let escrow_authority_seeds = &[
+                b"escrow_authority".as_ref(),
+                escrow_key.as_ref(),
+                &[ctx.bumps.escrow_authority],
+            ];
+
+        let payouts = [
+            (recipient_amount, ctx.accounts.recipient_token_account.to_account_info()),
+            (sender_amount, ctx.accounts.sender_token_account.to_account_info()),
+        ];
+        for (amount, to) in payouts {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to,
+                        authority: ctx.accounts.escrow_authority.to_account_info(),
+                    },
+                    &[escrow_authority_seeds]
+                ),
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+
+        token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.escrow_token_account.to_account_info(),
+                    destination: ctx.accounts.sender.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[escrow_authority_seeds]
+            ),
+        )?;
+
+        emit!(EscrowResolved {
+            escrow: escrow_key,
+            arbiter: ctx.accounts.arbiter.key(),
+            recipient_amount,
+            sender_amount,
+        });
+
+        msg!("Resolved escrow {}: {} to recipient, {} to sender", escrow_key, recipient_amount, sender_amount);
+        Ok(())
+    }
+
+/// Returns the tokens to the sender once `refund_after` has passed and
+/// closes both the escrow and its token account
+pub fn cancel_escrow(ctx: Context<CancelEscrow>) -> Result<()> {
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
+        let escrow = &ctx.accounts.escrow;
+        require!(escrow.status == EscrowStatus::Active, TokenError::EscrowDisputed);
+        let refund_after = escrow.refund_after.ok_or(TokenError::EscrowNotRefundable)?;
+        require!(Clock::get()?.unix_timestamp >= refund_after, TokenError::EscrowLocked);
+
+        let escrow_key = escrow.key();
+        let escrow_authority_seeds = &[
+                b"escrow_authority".as_ref(),
+                escrow_key.as_ref(),
+                &[ctx.bumps.escrow_authority],
+            ];
+
+        // Sweep the whole balance, not just `escrow.amount`, so the account can close
+        let amount = ctx.accounts.escrow_token_account.amount;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.sender_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[escrow_authority_seeds]
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.escrow_token_account.to_account_info(),
+                    destination: ctx.accounts.sender.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[escrow_authority_seeds]
+            ),
+        )?;
+
+        emit!(EscrowCancelled {
+            escrow: escrow_key,
+            sender: ctx.accounts.sender.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+
+        msg!("Cancelled escrow {} and refunded {} tokens", escrow_key, amount);
+        Ok(())
+    }
+
+/// Grows an escrow created before release times, milestones and disputes so
+/// it deserializes again. Permissionless: the new fields keep their zero
+/// values, which leave the escrow's terms as they were.
+pub fn migrate_escrow(ctx: Context<MigrateEscrow>) -> Result<()> {
+        let len = Escrow::migrated_space(&ctx.accounts.escrow.try_borrow_data()?)?;
+        grow_account(&ctx.accounts.escrow, &ctx.accounts.payer, &ctx.accounts.system_program, len)?;
+
+        msg!("Migrated escrow {}", ctx.accounts.escrow.key());
+        Ok(())
+    }
+
+pub fn create_sol_escrow(
+        ctx: Context<CreateSolEscrow>,
+        amount: u64,
+        seed: [u8; 32],
+        recipient: Option<Pubkey>,
+        release_after: Option<i64>,
+        refund_after: Option<i64>,
+    ) -> Result<()> {
+        require!(amount > 0, TokenError::ZeroAmount);
+        if let (Some(release_after), Some(refund_after)) = (release_after, refund_after) {
+            require!(refund_after > release_after, TokenError::InvalidEscrowSchedule);
+        }
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.sender = ctx.accounts.sender.key();
+        escrow.amount = amount;
+        escrow.seed = seed;
+        escrow.recipient = recipient;
+        escrow.bump = ctx.bumps.escrow;
+        escrow.release_after = release_after;
+        escrow.refund_after = refund_after;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sender.to_account_info(),
+                    to: escrow.to_account_info(),
+                },
+            ),
+            amount
+        )?;
+
+        emit!(SolEscrowCreated {
+            escrow: escrow.key(),
+            sender: ctx.accounts.sender.key(),
+            amount,
+            recipient,
+            release_after,
+            refund_after,
+        });
+
+        msg!("Created escrow with {} lamports", amount);
+        Ok(())
+    }
+
+/// Pays out by closing the escrow to the recipient, rent included.
+pub fn release_sol_escrow(ctx: Context<ReleaseSolEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        require!(escrow.claimant() == ctx.accounts.recipient.key(), TokenError::UnauthorizedRecipient);
+        if let Some(release_after) = escrow.release_after {
+            require!(Clock::get()?.unix_timestamp >= release_after, TokenError::EscrowLocked);
+        }
+
+        emit!(SolEscrowReleased {
+            escrow: escrow.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount: escrow.amount,
+        });
+
+        msg!("Released {} lamports from escrow to {}", escrow.amount, ctx.accounts.recipient.key());
+        Ok(())
+    }
+
+pub fn cancel_sol_escrow(ctx: Context<CancelSolEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let refund_after = escrow.refund_after.ok_or(TokenError::EscrowNotRefundable)?;
+        require!(Clock::get()?.unix_timestamp >= refund_after, TokenError::EscrowLocked);
+
+        emit!(SolEscrowCancelled {
+            escrow: escrow.key(),
+            sender: ctx.accounts.sender.key(),
+            amount: escrow.amount,
+        });
+
+        msg!("Cancelled escrow {} and refunded {} lamports", escrow.key(), escrow.amount);
+        Ok(())
+    }
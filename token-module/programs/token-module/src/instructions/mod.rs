@@ -2,6 +2,8 @@ pub mod admin;
 pub mod token;
 pub mod escrow;
 pub mod transfer;
+pub mod roles;
+pub mod vesting;
 
 // pub use admin::*;
 // pub use token::*;
@@ -11,4 +13,6 @@ pub mod transfer;
 pub use admin::*;
 pub use token::*;
 pub use escrow::*;
-pub use transfer::*;
\ No newline at end of file
+pub use transfer::*;
+pub use roles::*;
+pub use vesting::*;
\ No newline at end of file
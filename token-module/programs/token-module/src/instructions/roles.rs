@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{ TokenError, RoleGranted, RoleRevoked, GrantRole, RevokeRole, RoleKind, MinterQuota};
+
+
+/// Grants `role` on the mint to `holder`. To change a Minter's quota, revoke
+/// the role and grant it again.
+pub fn grant_role(
+    ctx: Context<GrantRole>,
+    holder: Pubkey,
+    role: RoleKind,
+    quota: MinterQuota,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        TokenError::UnauthorizedAdmin
+    );
+    match role {
+        RoleKind::Minter => require!(
+            quota.per_period.is_none() || quota.period_seconds > 0,
+            TokenError::InvalidRoleQuota
+        ),
+        _ => require!(quota == MinterQuota::default(), TokenError::InvalidRoleQuota),
+    }
+
+    let mint_role = &mut ctx.accounts.mint_role;
+    mint_role.mint = ctx.accounts.mint.key();
+    mint_role.holder = holder;
+    mint_role.role = role;
+    mint_role.quota = quota;
+    mint_role.total_minted = 0;
+    mint_role.period_start = Clock::get()?.unix_timestamp;
+    mint_role.period_minted = 0;
+    mint_role.bump = ctx.bumps.mint_role;
+
+    emit!(RoleGranted {
+        mint: ctx.accounts.mint.key(),
+        holder,
+        role,
+        quota,
+    });
+
+    msg!("Granted {:?} on mint {} to {}", role, ctx.accounts.mint.key(), holder);
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        TokenError::UnauthorizedAdmin
+    );
+
+    let mint_role = &ctx.accounts.mint_role;
+    emit!(RoleRevoked {
+        mint: mint_role.mint,
+        holder: mint_role.holder,
+        role: mint_role.role,
+        total_minted: mint_role.total_minted,
+    });
+
+    msg!("Revoked {:?} on mint {} from {}", mint_role.role, mint_role.mint, mint_role.holder);
+    Ok(())
+}
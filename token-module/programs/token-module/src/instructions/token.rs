@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{self, MintTo, Burn};
-use crate::{ TokenError, TokenCreated, TokenMinted, TokensBurned, CreateToken, MintTokens, BurnTokens};
+use crate::{ TokenError, TokenMetadata, TokenCreated, TokenMetadataUpdated, TokenMinted, TokensBurned, CreateToken, UpdateTokenMetadata, MintTokens, BurnTokens};
 
 
 pub fn create_token(
@@ -18,6 +18,12 @@ pub fn create_token(
         require!(uri_str.len() <= 200, TokenError::UriTooLong);
     }
 
+    // A PDA such as a multisig vault cannot co-sign the mint keypair's
+    // creation, so the payer may hand admin rights to it up front
+    let admin = ctx.accounts.designated_admin
+        .as_ref()
+        .map_or(ctx.accounts.admin.key(), |designated| designated.key());
+
     // Initialize metadata
     let token_metadata = &mut ctx.accounts.token_metadata;
     token_metadata.name = name.clone();
@@ -25,7 +31,9 @@ pub fn create_token(
     token_metadata.decimals = decimals;
     token_metadata.uri = uri;
     token_metadata.max_supply = max_supply;
-    token_metadata.admin = ctx.accounts.admin.key();
+    token_metadata.admin = admin;
+    token_metadata.pending_admin = None;
+    token_metadata.paused = false;
     token_metadata.mint = ctx.accounts.mint.key();
     token_metadata.bump = ctx.bumps.token_metadata;
 
@@ -34,18 +42,57 @@ pub fn create_token(
         name: name.clone(),
         symbol: symbol.clone(),
         decimals,
-        admin: ctx.accounts.admin.key(),
+        admin,
         max_supply,
     });
 
     msg!("Created new token {} ({})", name, symbol);
     Ok(())
 }
+
+/// Replaces name, symbol and URI; the account is resized to fit, with the
+/// admin paying for growth and receiving the rent freed by shrinking
+pub fn update_token_metadata(
+    ctx: Context<UpdateTokenMetadata>,
+    name: String,
+    symbol: String,
+    uri: Option<String>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        TokenError::UnauthorizedAdmin
+    );
+    require!(name.len() <= 32, TokenError::NameTooLong);
+    require!(symbol.len() <= 10, TokenError::SymbolTooLong);
+    if let Some(ref uri_str) = uri {
+        require!(uri_str.len() <= 200, TokenError::UriTooLong);
+    }
+
+    let token_metadata = &mut ctx.accounts.token_metadata;
+    let old_name = std::mem::replace(&mut token_metadata.name, name.clone());
+    let old_symbol = std::mem::replace(&mut token_metadata.symbol, symbol.clone());
+    let old_uri = std::mem::replace(&mut token_metadata.uri, uri.clone());
+
+    emit!(TokenMetadataUpdated {
+        mint: ctx.accounts.mint.key(),
+        admin: ctx.accounts.admin.key(),
+        old_name,
+        new_name: name.clone(),
+        old_symbol,
+        new_symbol: symbol.clone(),
+        old_uri,
+        new_uri: uri,
+    });
+
+    msg!("Updated metadata of token {} to {} ({})", ctx.accounts.mint.key(), name, symbol);
+    Ok(())
+}
 pub fn mint_tokens(
     ctx: Context<MintTokens>,
     amount: u64,
 ) -> Result<()> {
     require!(amount>0, TokenError::ZeroAmount);
+    require!(!ctx.accounts.token_metadata.paused, TokenError::Paused);
 
     if let Some(max_supply) = ctx.accounts.token_metadata.max_supply {
         let current_supply = ctx.accounts.mint.supply;
@@ -65,20 +112,24 @@ pub fn mint_tokens(
     }
 
     require!(
-        ctx.accounts.admin.key() == ctx.accounts.token_metadata.admin,
+        ctx.accounts.token_metadata.authorizes(&ctx.accounts.admin.key(), ctx.accounts.role.is_some()),
         TokenError::UnauthorizedMintAuthority
     );
+    if let Some(role) = ctx.accounts.role.as_mut() {
+        role.consume_quota(amount, Clock::get()?.unix_timestamp)?;
+    }
 
-    msg!("Token Authority PDA for minting: {}", ctx.accounts.token_authority.key());
-    msg!("Token Authority Bump for minting: {}", ctx.accounts.token_authority.bump);
+    msg!("Mint Authority PDA for minting: {}", ctx.accounts.mint_authority.key());
+    msg!("Mint Authority Bump for minting: {}", ctx.bumps.mint_authority);
     msg!("Mint account for minting: {}", ctx.accounts.mint.key());
     msg!("Mint authority according to mint account: {}", ctx.accounts.mint.mint_authority.unwrap());
 
 
-    let _mint_key = ctx.accounts.mint.key();
+    let mint_key = ctx.accounts.mint.key();
     let authority_seeds = &[
-        b"token_authority".as_ref(),
-        &[ctx.accounts.token_authority.bump]
+        b"mint_authority".as_ref(),
+        mint_key.as_ref(),
+        &[ctx.bumps.mint_authority]
     ];
 
     token_interface::mint_to(
@@ -87,7 +138,7 @@ pub fn mint_tokens(
             MintTo {
                 mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.destination.to_account_info(),
-                authority: ctx.accounts.token_authority.to_account_info()
+                authority: ctx.accounts.mint_authority.to_account_info()
             },
             &[authority_seeds]
 
@@ -108,6 +159,7 @@ pub fn mint_tokens(
 
 pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
     require!(amount > 0, TokenError::ZeroAmount);
+    TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
     require!(
         !ctx.accounts.token_account.is_frozen(),
         TokenError::AccountFrozen
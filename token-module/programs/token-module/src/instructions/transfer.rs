@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_spl::token_interface::{self, TransferChecked};
-use crate::{TokenError, TransferSol, TransferTokens};
+use crate::{TokenError, TokenMetadata, TransferSol, TransferTokens};
 
 pub fn transfer_sol(ctx: Context<TransferSol>, amount: u64) -> Result<()> {
         msg!("Transferring {} lamports from {} to {}", amount, ctx.accounts.from.key(), ctx.accounts.to.key());
@@ -26,6 +26,7 @@ pub fn transfer_tokens(
         amount: u64,
     ) -> Result<()> {
         require!(amount>0, TokenError::ZeroAmount);
+        TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
         require!(
             !ctx.accounts.from_account.is_frozen(),
             TokenError::AccountFrozen
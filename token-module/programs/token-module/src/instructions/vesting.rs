@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TransferChecked};
+use crate::{TokenError, TokenMetadata, VestingCreated, VestedTokensClaimed, VestingRevoked,
+    VestingSchedule, CreateVesting, ClaimVested, RevokeVesting};
+
+pub fn create_vesting(
+    ctx: Context<CreateVesting>,
+    seed: [u8; 32],
+    total_amount: u64,
+    beneficiary: Pubkey,
+    schedule: VestingSchedule,
+    revoke_authority: Option<Pubkey>,
+) -> Result<()> {
+    TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+    require!(total_amount > 0, TokenError::ZeroAmount);
+    require!(
+        schedule.duration_seconds > 0
+            && schedule.release_interval_seconds > 0
+            && schedule.release_interval_seconds <= schedule.duration_seconds
+            && (0..=schedule.duration_seconds).contains(&schedule.cliff_seconds),
+        TokenError::InvalidVestingSchedule
+    );
+
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.issuer = ctx.accounts.issuer.key();
+    vesting.beneficiary = beneficiary;
+    vesting.mint = ctx.accounts.mint.key();
+    vesting.seed = seed;
+    vesting.total_amount = total_amount;
+    vesting.claimed_amount = 0;
+    vesting.schedule = schedule;
+    vesting.revoke_authority = revoke_authority;
+    vesting.revoked = false;
+    vesting.bump = ctx.bumps.vesting;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.issuer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vesting_token_account.to_account_info(),
+                authority: ctx.accounts.issuer.to_account_info(),
+            },
+        ),
+        total_amount,
+        ctx.accounts.mint.decimals
+    )?;
+
+    emit!(VestingCreated {
+        vesting: vesting.key(),
+        issuer: vesting.issuer,
+        beneficiary,
+        mint: vesting.mint,
+        total_amount,
+        schedule,
+        revoke_authority,
+    });
+
+    msg!("Created vesting of {} tokens for {}", total_amount, beneficiary);
+    Ok(())
+}
+
+/// Closes the vesting and its token account to the issuer once everything
+/// has been claimed.
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
+    let vesting = &mut ctx.accounts.vesting;
+    let amount = vesting.claimable_amount(Clock::get()?.unix_timestamp)?;
+    require!(amount > 0, TokenError::NothingToClaim);
+    vesting.claimed_amount = vesting.claimed_amount
+        .checked_add(amount)
+        .ok_or(TokenError::ArithmeticOverflow)?;
+    let completed = vesting.is_completed();
+
+    let vesting_key = vesting.key();
+    let vesting_authority_seeds = &[
+        b"vesting_authority".as_ref(),
+        vesting_key.as_ref(),
+        &[ctx.bumps.vesting_authority],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vesting_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                authority: ctx.accounts.vesting_authority.to_account_info(),
+            },
+            &[vesting_authority_seeds]
+        ),
+        // The final claim sweeps the whole balance so the account can close
+        if completed { ctx.accounts.vesting_token_account.amount } else { amount },
+        ctx.accounts.mint.decimals,
+    )?;
+
+    if completed {
+        token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.vesting_token_account.to_account_info(),
+                    destination: ctx.accounts.issuer.to_account_info(),
+                    authority: ctx.accounts.vesting_authority.to_account_info(),
+                },
+                &[vesting_authority_seeds]
+            ),
+        )?;
+        vesting.close(ctx.accounts.issuer.to_account_info())?;
+    }
+
+    emit!(VestedTokensClaimed {
+        vesting: vesting_key,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+        claimed_amount: vesting.claimed_amount,
+        total_amount: vesting.total_amount,
+        closed: completed,
+    });
+
+    msg!("Claimed {} vested tokens from {}", amount, vesting_key);
+    Ok(())
+}
+
+/// Returns the unvested remainder to the issuer. What has vested so far
+/// stays claimable by the beneficiary; if that is nothing, the vesting and
+/// its token account are closed to the issuer right away.
+pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+    TokenMetadata::require_not_paused(&ctx.accounts.token_metadata)?;
+
+    let vesting = &mut ctx.accounts.vesting;
+    require!(
+        vesting.revoke_authority == Some(ctx.accounts.revoke_authority.key()),
+        TokenError::UnauthorizedRevokeAuthority
+    );
+    require!(!vesting.revoked, TokenError::VestingRevoked);
+
+    let vested = vesting.vested_amount(Clock::get()?.unix_timestamp)?;
+    let unvested = vesting.total_amount - vested;
+    vesting.total_amount = vested;
+    vesting.revoked = true;
+    let completed = vesting.is_completed();
+
+    let vesting_key = vesting.key();
+    let vesting_authority_seeds = &[
+        b"vesting_authority".as_ref(),
+        vesting_key.as_ref(),
+        &[ctx.bumps.vesting_authority],
+    ];
+
+    // Once nothing is left to claim the whole balance goes back so the account can close
+    let returned = if completed { ctx.accounts.vesting_token_account.amount } else { unvested };
+    if returned > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.vesting_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.issuer_token_account.to_account_info(),
+                    authority: ctx.accounts.vesting_authority.to_account_info(),
+                },
+                &[vesting_authority_seeds]
+            ),
+            returned,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    if completed {
+        token_interface::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.vesting_token_account.to_account_info(),
+                    destination: ctx.accounts.issuer.to_account_info(),
+                    authority: ctx.accounts.vesting_authority.to_account_info(),
+                },
+                &[vesting_authority_seeds]
+            ),
+        )?;
+        vesting.close(ctx.accounts.issuer.to_account_info())?;
+    }
+
+    emit!(VestingRevoked {
+        vesting: vesting_key,
+        revoked_by: ctx.accounts.revoke_authority.key(),
+        vested_amount: vested,
+        returned_amount: returned,
+        closed: completed,
+    });
+
+    msg!("Revoked vesting {}: {} tokens returned to the issuer", vesting_key, returned);
+    Ok(())
+}
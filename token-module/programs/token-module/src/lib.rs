@@ -14,7 +14,7 @@ pub use events::*;          // Event structs
 pub use errors::*;          // Error enums
 
 // ✅ FIXED: Import instruction functions with module path to avoid conflicts
-use instructions::{admin, token, escrow, transfer};
+use instructions::{admin, token, escrow, transfer, roles, vesting};
 
 #[program]
 pub mod token_module {
@@ -36,6 +36,15 @@ pub mod token_module {
         token::create_token(ctx, name, symbol, decimals, uri, max_supply)
     }
 
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: Option<String>,
+    ) -> Result<()> {
+        token::update_token_metadata(ctx, name, symbol, uri)
+    }
+
     pub fn transfer_sol(ctx: Context<TransferSol>, amount: u64) -> Result<()> {
         transfer::transfer_sol(ctx, amount)
     }
@@ -57,14 +66,77 @@ pub mod token_module {
         amount: u64,
         seed: [u8; 32],
         recipient: Option<Pubkey>,
+        terms: EscrowTerms,
     ) -> Result<()> {
-        escrow::create_escrow(ctx, amount, seed, recipient)
+        escrow::create_escrow(ctx, amount, seed, recipient, terms)
     }
 
     pub fn release_escrow(ctx: Context<ReleaseEscrow>) -> Result<()> {
         escrow::release_escrow(ctx)
     }
 
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        escrow::approve_milestone(ctx, index)
+    }
+
+    pub fn release_milestone(ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        escrow::release_milestone(ctx, index)
+    }
+
+    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        escrow::raise_dispute(ctx)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, recipient_amount: u64) -> Result<()> {
+        escrow::resolve_dispute(ctx, recipient_amount)
+    }
+
+    pub fn cancel_escrow(ctx: Context<CancelEscrow>) -> Result<()> {
+        escrow::cancel_escrow(ctx)
+    }
+
+    pub fn migrate_escrow(ctx: Context<MigrateEscrow>) -> Result<()> {
+        escrow::migrate_escrow(ctx)
+    }
+
+    pub fn create_sol_escrow(
+        ctx: Context<CreateSolEscrow>,
+        amount: u64,
+        seed: [u8; 32],
+        recipient: Option<Pubkey>,
+        release_after: Option<i64>,
+        refund_after: Option<i64>,
+    ) -> Result<()> {
+        escrow::create_sol_escrow(ctx, amount, seed, recipient, release_after, refund_after)
+    }
+
+    pub fn release_sol_escrow(ctx: Context<ReleaseSolEscrow>) -> Result<()> {
+        escrow::release_sol_escrow(ctx)
+    }
+
+    pub fn cancel_sol_escrow(ctx: Context<CancelSolEscrow>) -> Result<()> {
+        escrow::cancel_sol_escrow(ctx)
+    }
+
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        seed: [u8; 32],
+        total_amount: u64,
+        beneficiary: Pubkey,
+        schedule: VestingSchedule,
+        revoke_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        vesting::create_vesting(ctx, seed, total_amount, beneficiary, schedule, revoke_authority)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        vesting::claim_vested(ctx)
+    }
+
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        vesting::revoke_vesting(ctx)
+    }
+
     pub fn freeze_token_account(ctx: Context<FreezeTokenAccount>) -> Result<()> {
         admin::freeze_token_account(ctx)
     }
@@ -72,4 +144,53 @@ pub mod token_module {
     pub fn thaw_token_account(ctx: Context<ThawTokenAccount>) -> Result<()> {
         admin::thaw_token_account(ctx)
     }
+
+    pub fn pause_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+        admin::pause_token(ctx)
+    }
+
+    pub fn unpause_token(ctx: Context<SetTokenPaused>) -> Result<()> {
+        admin::unpause_token(ctx)
+    }
+
+    pub fn migrate_mint_authority(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+        admin::migrate_mint_authority(ctx)
+    }
+
+    pub fn grant_role(
+        ctx: Context<GrantRole>,
+        holder: Pubkey,
+        role: RoleKind,
+        quota: MinterQuota,
+    ) -> Result<()> {
+        roles::grant_role(ctx, holder, role, quota)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        roles::revoke_role(ctx)
+    }
+
+    pub fn propose_token_authority_admin(ctx: Context<ManageTokenAuthorityAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+        admin::propose_token_authority_admin(ctx, new_admin)
+    }
+
+    pub fn accept_token_authority_admin(ctx: Context<AcceptTokenAuthorityAdmin>) -> Result<()> {
+        admin::accept_token_authority_admin(ctx)
+    }
+
+    pub fn renounce_token_authority_admin(ctx: Context<ManageTokenAuthorityAdmin>) -> Result<()> {
+        admin::renounce_token_authority_admin(ctx)
+    }
+
+    pub fn propose_token_admin(ctx: Context<ManageTokenAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+        admin::propose_token_admin(ctx, new_admin)
+    }
+
+    pub fn accept_token_admin(ctx: Context<AcceptTokenAdmin>) -> Result<()> {
+        admin::accept_token_admin(ctx)
+    }
+
+    pub fn renounce_token_admin(ctx: Context<ManageTokenAdmin>) -> Result<()> {
+        admin::renounce_token_admin(ctx)
+    }
 }
\ No newline at end of file
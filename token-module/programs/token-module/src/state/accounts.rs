@@ -3,6 +3,7 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{TokenAccount, TokenInterface, Mint}
 };
+use crate::TokenError;
 
 #[derive(Accounts)]
 pub struct InitializeTokenAuthority<'info> {
@@ -31,39 +32,34 @@ pub struct CreateToken<'info> {
         init,
         payer = admin,
         mint::decimals = decimals,
-        mint::authority = token_authority,
-        mint::freeze_authority = token_authority,
+        mint::authority = mint_authority,
+        mint::freeze_authority = mint_authority,
     )]
     pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
         payer = admin,
-        space = 8 + // discriminator
-            32 + // admin pubkey
-            4 + name.len() + // string prefix + data
-            4 + symbol.len() + // string prefix + data
-            1 + // decimals
-            1 + 4 + (uri.map_or(0, |s| s.len())) + // option prefix + string prefix + data
-            9 + // option u64 for max supply
-            32 + // mint pubkey
-            1,   // bump
+        space = TokenMetadata::space(&name, &symbol, uri.as_deref()),
         seeds = [b"token_metadata", mint.key().as_ref()],
         bump
     )]
     pub token_metadata: Account<'info, TokenMetadata>,
 
-    ///CHECK: This is the token autority PDA
+    ///CHECK: Per-mint PDA that becomes the mint and freeze authority
     #[account(
-        seeds = [b"token_authority".as_ref()],
+        seeds = [b"mint_authority".as_ref(), mint.key().as_ref()],
         bump
     )]
-    pub token_authority: Account<'info, TokenAuthority>,
+    pub mint_authority: AccountInfo<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info,Rent>,
 
+    ///CHECK: Recorded as token_metadata.admin instead of the payer when present,
+    /// e.g. a multisig vault PDA that later signs mint, freeze and thaw via CPI
+    pub designated_admin: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
@@ -98,13 +94,22 @@ pub struct MintTokens<'info> {
     )]
     pub token_metadata: Account<'info, TokenMetadata>, 
 
+    ///CHECK: Per-mint PDA that is the mint and freeze authority
     #[account(
-        seeds = [b"token_authority".as_ref()],
-        bump = token_authority.bump,
+        seeds = [b"mint_authority".as_ref(), mint.key().as_ref()],
+        bump
     )]
-    pub token_authority: Account<'info, TokenAuthority>,
+    pub mint_authority: AccountInfo<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// `admin`'s Minter role, when signing as a role holder instead of the admin
+    #[account(
+        mut,
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), admin.key().as_ref(), &[RoleKind::Minter as u8]],
+        bump = role.bump,
+    )]
+    pub role: Option<Account<'info, MintRole>>,
 }
 
 
@@ -128,7 +133,14 @@ pub struct TransferTokens<'info> {
     )]
     pub to_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Interface<'info, TokenInterface>
+    pub token_program: Interface<'info, TokenInterface>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -146,7 +158,14 @@ pub struct BurnTokens<'info> {
     )]
     pub token_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Interface<'info, TokenInterface>
+    pub token_program: Interface<'info, TokenInterface>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
@@ -167,7 +186,7 @@ pub struct CreateEscrow<'info> {
     #[account(
         init,
         payer = sender,
-        space = 8 + 32 + 32 + 8 + 32 + (1+32) + 1 + 1, // Discriminator + keys + amount + seed + option<pubkey> + claimed + bump
+        space = 8 + Escrow::INIT_SPACE,
         seeds = [b"token_escrow".as_ref(), sender.key().as_ref(), mint.key().as_ref(), &seed],
         bump
     )]
@@ -194,6 +213,12 @@ pub struct CreateEscrow<'info> {
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 #[derive(Accounts)]
 pub struct ReleaseEscrow<'info> {
@@ -237,100 +262,1116 @@ pub struct ReleaseEscrow<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     pub system_program: Program<'info, System>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct FreezeTokenAccount<'info> {
+pub struct ApproveMilestone<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = sender,
+        constraint = !escrow.claimed,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint,
+        constraint = !escrow.claimed,
+    )]
+    pub escrow: Account<'info, Escrow>,
 
     pub mint: InterfaceAccount<'info, Mint>,
 
+    ///CHECK: This PDA owns the escrow token account
     #[account(
-        mut,
-        constraint = token_account.mint == mint.key()
+        seeds = [b"escrow_authority", escrow.key().as_ref()],
+        bump
     )]
-    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_authority: AccountInfo<'info>,
 
     #[account(
-        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
-        bump = token_metadata.bump,
-        constraint = token_metadata.mint == mint.key()
+        mut,
+        constraint = escrow_token_account.mint == mint.key(),
+        constraint = escrow_token_account.owner == escrow_authority.key()
     )]
-    pub token_metadata: Account<'info, TokenMetadata>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"token_authority".as_ref()],
-        bump = token_authority.bump,
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
     )]
-    pub token_authority: Account<'info, TokenAuthority>,
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 
 #[derive(Accounts)]
-pub struct ThawTokenAccount<'info> {
+pub struct RaiseDispute<'info> {
+    pub party: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !escrow.claimed,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+        has_one = mint,
+        constraint = !escrow.claimed,
+    )]
+    pub escrow: Account<'info, Escrow>,
 
     pub mint: InterfaceAccount<'info, Mint>,
 
+    ///CHECK: Receives the sender's share and the rent of the escrow and its token account
+    #[account(mut)]
+    pub sender: AccountInfo<'info>,
+
+    ///CHECK: Checked against the escrow's recipient
+    #[account(
+        constraint = escrow.recipient == Some(recipient.key()) @ TokenError::UnauthorizedRecipient
+    )]
+    pub recipient: AccountInfo<'info>,
+
+    ///CHECK: This PDA owns the escrow token account
+    #[account(
+        seeds = [b"escrow_authority", escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_authority: AccountInfo<'info>,
+
     #[account(
         mut,
-        constraint = token_account.mint == mint.key()
+        constraint = escrow_token_account.mint == mint.key(),
+        constraint = escrow_token_account.owner == escrow_authority.key()
     )]
-    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
     #[account(
         seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
-        bump,
-        constraint = token_metadata.mint == mint.key()
+        bump
     )]
-    pub token_metadata: Account<'info, TokenMetadata>,
+    pub token_metadata: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelEscrow<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
 
     #[account(
-        seeds = [b"token_authority".as_ref()],
-        bump = token_authority.bump,
+        mut,
+        close = sender,
+        has_one = sender,
+        has_one = mint,
+        constraint = !escrow.claimed,
     )]
-    pub token_authority: Account<'info, TokenAuthority>,
+    pub escrow: Account<'info, Escrow>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    ///CHECK: This PDA owns the escrow token account
+    #[account(
+        seeds = [b"escrow_authority", escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.mint == mint.key(),
+        constraint = escrow_token_account.owner == escrow_authority.key()
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    ///CHECK: An escrow that may still have the layout written before release
+    /// times, milestones and disputes; checked by discriminator in the handler
+    #[account(mut, owner = crate::ID)]
+    pub escrow: UncheckedAccount<'info>,
 
-#[account]
-#[derive(InitSpace)]
-pub struct TokenAuthority {
-    pub admin: Pubkey,
-    pub bump: u8,
+    /// Funds the rent of the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct TokenMetadata {
-    #[max_len(32)]
-    pub name: String,
-    #[max_len(10)]
-    pub symbol: String,
-    pub decimals: u8,
-    #[max_len(200)]
-    pub uri: Option<String>,
-    pub max_supply: Option<u64>,
-    pub admin: Pubkey,
-    pub mint: Pubkey,
-    pub bump: u8,
+#[derive(Accounts)]
+#[instruction(amount: u64, seed: [u8; 32])]
+pub struct CreateSolEscrow<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = 8 + SolEscrow::INIT_SPACE,
+        seeds = [b"sol_escrow".as_ref(), sender.key().as_ref(), &seed],
+        bump
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+
+    pub system_program: Program<'info, System>,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Escrow {
-    pub sender: Pubkey,
-    pub mint: Pubkey,
-    pub amount: u64,
-    pub seed: [u8; 32],
-    pub recipient: Option<Pubkey>,
-    pub claimed: bool,
-    pub bump:u8,
+#[derive(Accounts)]
+pub struct ReleaseSolEscrow<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        close = recipient,
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSolEscrow<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+    )]
+    pub escrow: Account<'info, SolEscrow>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct CreateVesting<'info> {
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
-}
\ No newline at end of file
+    #[account(
+        mut,
+        constraint = issuer_token_account.mint == mint.key(),
+        constraint = issuer_token_account.owner == issuer.key()
+    )]
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = issuer,
+        space = 8 + Vesting::INIT_SPACE,
+        seeds = [b"vesting".as_ref(), issuer.key().as_ref(), mint.key().as_ref(), &seed],
+        bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    ///CHECK: This is a PDA that will own the vesting token account
+    #[account(
+        seeds = [b"vesting_authority", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = issuer,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_authority
+    )]
+    pub vesting_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    ///CHECK: Receives the rent once the vesting is fully claimed
+    #[account(mut)]
+    pub issuer: AccountInfo<'info>,
+
+    ///CHECK: This PDA owns the vesting token account
+    #[account(
+        seeds = [b"vesting_authority", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = vesting_token_account.mint == mint.key(),
+        constraint = vesting_token_account.owner == vesting_authority.key()
+    )]
+    pub vesting_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    #[account(mut)]
+    pub revoke_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = issuer,
+        has_one = mint,
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    ///CHECK: Receives the unvested tokens, and the rent if nothing is left to claim
+    #[account(mut)]
+    pub issuer: AccountInfo<'info>,
+
+    ///CHECK: This PDA owns the vesting token account
+    #[account(
+        seeds = [b"vesting_authority", vesting.key().as_ref()],
+        bump
+    )]
+    pub vesting_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = vesting_token_account.mint == mint.key(),
+        constraint = vesting_token_account.owner == vesting_authority.key()
+    )]
+    pub vesting_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = revoke_authority,
+        associated_token::mint = mint,
+        associated_token::authority = issuer,
+    )]
+    pub issuer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    ///CHECK: The mint's metadata PDA, checked for the pause flag when the mint has one
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub token_metadata: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeTokenAccount<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key()
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    ///CHECK: Per-mint PDA that is the mint and freeze authority
+    #[account(
+        seeds = [b"mint_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// `admin`'s Freezer role, when signing as a role holder instead of the admin
+    #[account(
+        mut,
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), admin.key().as_ref(), &[RoleKind::Freezer as u8]],
+        bump = role.bump,
+    )]
+    pub role: Option<Account<'info, MintRole>>,
+}
+
+#[derive(Accounts)]
+pub struct ThawTokenAccount<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key()
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    ///CHECK: Per-mint PDA that is the mint and freeze authority
+    #[account(
+        seeds = [b"mint_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// `admin`'s Freezer role, when signing as a role holder instead of the admin
+    #[account(
+        mut,
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), admin.key().as_ref(), &[RoleKind::Freezer as u8]],
+        bump = role.bump,
+    )]
+    pub role: Option<Account<'info, MintRole>>,
+
+}
+
+
+/// Moves a mint created before per-mint authorities off the global
+/// `token_authority` PDA
+#[derive(Accounts)]
+pub struct MigrateMintAuthority<'info> {
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    ///CHECK: May still have the layout written before admin handover, so it is
+    /// grown to the current `TokenAuthority` size instead of deserialized
+    #[account(
+        mut,
+        seeds = [b"token_authority".as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub token_authority: UncheckedAccount<'info>,
+
+    ///CHECK: Per-mint PDA that becomes the mint and freeze authority
+    #[account(
+        seeds = [b"mint_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub mint_authority: AccountInfo<'info>,
+
+    ///CHECK: Metadata of the mint, grown to the current `TokenMetadata` size
+    /// like `token_authority`; absent for mints created outside `create_token`
+    #[account(
+        mut,
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub token_metadata: Option<UncheckedAccount<'info>>,
+
+    /// Funds the rent of the grown accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri: Option<String>)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key(),
+        realloc = TokenMetadata::space(&name, &symbol, uri.as_deref()),
+        realloc::payer = admin,
+        realloc::zero = false,
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey, role: RoleKind)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MintRole::INIT_SPACE,
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), holder.as_ref(), &[role as u8]],
+        bump
+    )]
+    pub mint_role: Account<'info, MintRole>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), mint_role.holder.as_ref(), &[mint_role.role as u8]],
+        bump = mint_role.bump,
+    )]
+    pub mint_role: Account<'info, MintRole>,
+}
+
+#[derive(Accounts)]
+pub struct SetTokenPaused<'info> {
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+
+    /// `admin`'s Pauser role, when signing as a role holder instead of the admin
+    #[account(
+        seeds = [b"mint_role".as_ref(), mint.key().as_ref(), admin.key().as_ref(), &[RoleKind::Pauser as u8]],
+        bump = role.bump,
+    )]
+    pub role: Option<Account<'info, MintRole>>,
+}
+
+#[derive(Accounts)]
+pub struct ManageTokenAuthorityAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_authority".as_ref()],
+        bump = token_authority.bump,
+    )]
+    pub token_authority: Account<'info, TokenAuthority>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTokenAuthorityAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_authority".as_ref()],
+        bump = token_authority.bump,
+    )]
+    pub token_authority: Account<'info, TokenAuthority>,
+}
+
+#[derive(Accounts)]
+pub struct ManageTokenAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTokenAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"token_metadata".as_ref(), mint.key().as_ref()],
+        bump = token_metadata.bump,
+        constraint = token_metadata.mint == mint.key()
+    )]
+    pub token_metadata: Account<'info, TokenMetadata>,
+}
+
+
+/// Shared mint and freeze authority of mints created before per-mint
+/// `mint_authority` PDAs; only `migrate_mint_authority` still signs with it
+#[account]
+#[derive(InitSpace)]
+pub struct TokenAuthority {
+    /// `Pubkey::default()` once renounced
+    pub admin: Pubkey,
+    pub bump: u8,
+    /// Proposed successor, set until it accepts
+    pub pending_admin: Option<Pubkey>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenMetadata {
+    #[max_len(32)]
+    pub name: String,
+    #[max_len(10)]
+    pub symbol: String,
+    pub decimals: u8,
+    #[max_len(200)]
+    pub uri: Option<String>,
+    pub max_supply: Option<u64>,
+    /// `Pubkey::default()` once renounced, which makes the mint immutable
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub bump: u8,
+    /// Proposed successor, set until it accepts
+    pub pending_admin: Option<Pubkey>,
+    /// Blocks minting, transfers, burns and escrows through this program
+    pub paused: bool,
+}
+
+impl TokenMetadata {
+    /// Exact account size for the given strings, used on creation and realloc
+    pub fn space(name: &str, symbol: &str, uri: Option<&str>) -> usize {
+        8 + // discriminator
+            4 + name.len() + // string prefix + data
+            4 + symbol.len() + // string prefix + data
+            1 + // decimals
+            1 + uri.map_or(0, |uri| 4 + uri.len()) + // option prefix + string prefix + data
+            9 + // option u64 for max supply
+            32 + // admin pubkey
+            32 + // mint pubkey
+            1 + // bump
+            1 + 32 + // option pending admin pubkey
+            1 // paused
+    }
+
+    /// Size a metadata account written by any earlier layout must grow to
+    /// before it deserializes; fields added since then are trailing, so the
+    /// zero-filled tail reads as `None` and `false`
+    pub fn migrated_space(data: &[u8]) -> Result<usize> {
+        require!(
+            data.len() >= 8 && data[..8] == *TokenMetadata::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let mut fields = &data[8..];
+        let name = String::deserialize(&mut fields)?;
+        let symbol = String::deserialize(&mut fields)?;
+        let _decimals = u8::deserialize(&mut fields)?;
+        let uri = Option::<String>::deserialize(&mut fields)?;
+        Ok(TokenMetadata::space(&name, &symbol, uri.as_deref()).max(data.len()))
+    }
+
+    /// Whether `signer` may act for the mint, either as its admin or through
+    /// a role account already checked against the signer. Roles lapse once
+    /// the admin renounces.
+    pub fn authorizes(&self, signer: &Pubkey, via_role: bool) -> bool {
+        match via_role {
+            true => self.admin != Pubkey::default(),
+            false => self.admin == *signer && *signer != Pubkey::default(),
+        }
+    }
+
+    /// Fails if `token_metadata` holds the metadata of a paused mint. Mints
+    /// created outside this program have no metadata and cannot be paused.
+    pub fn require_not_paused(token_metadata: &AccountInfo) -> Result<()> {
+        if token_metadata.owner != &crate::ID || token_metadata.data_is_empty() {
+            return Ok(());
+        }
+        let metadata = TokenMetadata::try_deserialize(&mut &token_metadata.try_borrow_data()?[..])?;
+        require!(!metadata.paused, TokenError::Paused);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum RoleKind {
+    Minter,
+    Freezer,
+    Pauser,
+}
+
+/// Minting limits of a Minter role; `None` means unlimited
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct MinterQuota {
+    /// Cap over the lifetime of the role
+    pub total: Option<u64>,
+    /// Cap per `period_seconds` window
+    pub per_period: Option<u64>,
+    pub period_seconds: u32,
+}
+
+/// A per-mint role held by `holder`, granted and revoked by the mint's admin
+#[account]
+#[derive(InitSpace)]
+pub struct MintRole {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub role: RoleKind,
+    pub quota: MinterQuota,
+    pub total_minted: u64,
+    pub period_start: i64,
+    pub period_minted: u64,
+    pub bump: u8,
+}
+
+impl MintRole {
+    /// Counts `amount` against the quota, starting a new window once the
+    /// current one has elapsed
+    pub fn consume_quota(&mut self, amount: u64, now: i64) -> Result<()> {
+        let total_minted = self.total_minted.checked_add(amount).ok_or(TokenError::ArithmeticOverflow)?;
+        if let Some(total) = self.quota.total {
+            require!(total_minted <= total, TokenError::MinterQuotaExceeded);
+        }
+
+        if let Some(per_period) = self.quota.per_period {
+            if now >= self.period_start.saturating_add(self.quota.period_seconds as i64) {
+                self.period_start = now;
+                self.period_minted = 0;
+            }
+            let period_minted = self.period_minted.checked_add(amount).ok_or(TokenError::ArithmeticOverflow)?;
+            require!(period_minted <= per_period, TokenError::MinterQuotaExceeded);
+            self.period_minted = period_minted;
+        }
+
+        self.total_minted = total_minted;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub sender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub seed: [u8; 32],
+    pub recipient: Option<Pubkey>,
+    pub claimed: bool,
+    pub bump:u8,
+    /// The recipient cannot claim before this time
+    pub release_after: Option<i64>,
+    /// The sender can cancel and reclaim the tokens from this time on
+    pub refund_after: Option<i64>,
+    /// Tranches released one by one; empty for a single release of `amount`
+    #[max_len(10)]
+    pub milestones: Vec<Milestone>,
+    pub released_amount: u64,
+    /// Settles disputes raised by the sender or recipient
+    pub arbiter: Option<Pubkey>,
+    pub status: EscrowStatus,
+}
+
+impl Escrow {
+    /// Who may claim: the recipient, or the sender for an open escrow
+    pub fn claimant(&self) -> Pubkey {
+        self.recipient.unwrap_or(self.sender)
+    }
+
+    pub fn remaining_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.released_amount)
+    }
+
+    /// Size an escrow written by any earlier layout must grow to before it
+    /// deserializes. Fields added since then are trailing and their zero
+    /// values mean no release or refund time, no milestones and `Active`.
+    pub fn migrated_space(data: &[u8]) -> Result<usize> {
+        require!(
+            data.len() >= 8 && data[..8] == *Escrow::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok((8 + Escrow::INIT_SPACE).max(data.len()))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum EscrowStatus {
+    Active,
+    /// Funds are locked until the arbiter resolves
+    Disputed,
+    Resolved,
+}
+
+/// Optional escrow features chosen at `create_escrow`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EscrowTerms {
+    pub release_after: Option<i64>,
+    pub refund_after: Option<i64>,
+    /// Empty for a single release of the whole amount
+    pub milestones: Vec<MilestoneTerms>,
+    pub arbiter: Option<Pubkey>,
+}
+
+/// Milestone as given to `create_escrow`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MilestoneTerms {
+    pub amount: u64,
+    /// Releasable from this time on even without the sender's approval
+    pub release_at: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub release_at: Option<i64>,
+    pub approved: bool,
+    pub released: bool,
+}
+
+/// Lamport escrow. The SOL sits in the escrow account itself, on top of its
+/// rent, and leaves with it when the account closes.
+#[account]
+#[derive(InitSpace)]
+pub struct SolEscrow {
+    pub sender: Pubkey,
+    pub amount: u64,
+    pub seed: [u8; 32],
+    pub recipient: Option<Pubkey>,
+    pub bump: u8,
+    /// The recipient cannot claim before this time
+    pub release_after: Option<i64>,
+    /// The sender can cancel and reclaim the SOL from this time on
+    pub refund_after: Option<i64>,
+}
+
+impl SolEscrow {
+    /// Who may claim: the recipient, or the sender for an open escrow
+    pub fn claimant(&self) -> Pubkey {
+        self.recipient.unwrap_or(self.sender)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct VestingSchedule {
+    pub start_time: i64,
+    /// Nothing vests before `start_time + cliff_seconds`
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+    /// Vesting advances in steps of this length
+    pub release_interval_seconds: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    pub issuer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub seed: [u8; 32],
+    /// Reduced to the vested amount on revocation
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub schedule: VestingSchedule,
+    /// May return the unvested remainder to the issuer
+    pub revoke_authority: Option<Pubkey>,
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl Vesting {
+    /// Amount vested at `now`, rounded down to whole release intervals
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if self.revoked {
+            return Ok(self.total_amount);
+        }
+        let schedule = &self.schedule;
+        let elapsed = now.saturating_sub(schedule.start_time);
+        if elapsed < schedule.cliff_seconds {
+            return Ok(0);
+        }
+        if elapsed >= schedule.duration_seconds {
+            return Ok(self.total_amount);
+        }
+        let vested_seconds = elapsed - elapsed % schedule.release_interval_seconds;
+        let vested = (self.total_amount as u128)
+            .checked_mul(vested_seconds as u128)
+            .ok_or(TokenError::ArithmeticOverflow)?
+            / schedule.duration_seconds as u128;
+        Ok(vested as u64)
+    }
+
+    pub fn claimable_amount(&self, now: i64) -> Result<u64> {
+        Ok(self.vested_amount(now)?.saturating_sub(self.claimed_amount))
+    }
+
+    /// Everything the beneficiary will ever receive has been claimed
+    pub fn is_completed(&self) -> bool {
+        self.claimed_amount >= self.total_amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `TokenMetadata` as written before admin handover and pausing
+    #[derive(AnchorSerialize)]
+    struct BaselineTokenMetadata {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        uri: Option<String>,
+        max_supply: Option<u64>,
+        admin: Pubkey,
+        mint: Pubkey,
+        bump: u8,
+    }
+
+    /// Account bytes as the baseline `create_token` left them: its space
+    /// always reserved a string prefix for the URI, even when it was `None`
+    fn baseline_metadata(uri: Option<&str>) -> (BaselineTokenMetadata, Vec<u8>) {
+        let metadata = BaselineTokenMetadata {
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            decimals: 6,
+            uri: uri.map(str::to_string),
+            max_supply: Some(1_000),
+            admin: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            bump: 254,
+        };
+        let len = 8 + 4 + 5 + 4 + 3 + 1 + 1 + 4 + uri.map_or(0, str::len) + 9 + 32 + 32 + 1;
+        let mut data = TokenMetadata::DISCRIMINATOR.to_vec();
+        metadata.serialize(&mut data).unwrap();
+        data.resize(len, 0);
+        (metadata, data)
+    }
+
+    #[test]
+    fn baseline_token_authority_deserializes_once_grown() {
+        let admin = Pubkey::new_unique();
+        let mut data = TokenAuthority::DISCRIMINATOR.to_vec();
+        (admin, 255u8).serialize(&mut data).unwrap();
+        assert!(TokenAuthority::try_deserialize(&mut &data[..]).is_err());
+
+        data.resize(8 + TokenAuthority::INIT_SPACE, 0);
+        let authority = TokenAuthority::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(authority.admin, admin);
+        assert_eq!(authority.bump, 255);
+        assert_eq!(authority.pending_admin, None);
+    }
+
+    #[test]
+    fn baseline_token_metadata_deserializes_once_grown() {
+        for uri in [Some("https://example.com/token.json"), None] {
+            let (baseline, mut data) = baseline_metadata(uri);
+            let len = TokenMetadata::migrated_space(&data).unwrap();
+            assert_eq!(len, TokenMetadata::space(&baseline.name, &baseline.symbol, uri));
+
+            data.resize(len, 0);
+            let mut metadata = TokenMetadata::try_deserialize(&mut &data[..]).unwrap();
+            assert_eq!(metadata.name, baseline.name);
+            assert_eq!(metadata.symbol, baseline.symbol);
+            assert_eq!(metadata.decimals, baseline.decimals);
+            assert_eq!(metadata.uri, baseline.uri);
+            assert_eq!(metadata.max_supply, baseline.max_supply);
+            assert_eq!(metadata.admin, baseline.admin);
+            assert_eq!(metadata.mint, baseline.mint);
+            assert_eq!(metadata.bump, baseline.bump);
+            assert_eq!(metadata.pending_admin, None);
+            assert!(!metadata.paused);
+
+            // The grown account has room for every new field once set
+            metadata.pending_admin = Some(Pubkey::new_unique());
+            metadata.paused = true;
+            metadata.try_serialize(&mut &mut data[..]).unwrap();
+            assert_eq!(TokenMetadata::migrated_space(&data).unwrap(), len);
+        }
+    }
+
+    /// Escrow bytes as the baseline `create_escrow` left them
+    fn baseline_escrow(recipient: Option<Pubkey>) -> Vec<u8> {
+        let mut data = Escrow::DISCRIMINATOR.to_vec();
+        (Pubkey::new_unique(), Pubkey::new_unique(), 500u64, [7u8; 32], recipient, false, 253u8)
+            .serialize(&mut data)
+            .unwrap();
+        data.resize(8 + 32 + 32 + 8 + 32 + 33 + 1 + 1, 0);
+        data
+    }
+
+    #[test]
+    fn baseline_escrow_deserializes_once_grown() {
+        let recipient = Pubkey::new_unique();
+        let mut data = baseline_escrow(Some(recipient));
+        assert!(Escrow::try_deserialize(&mut &data[..]).is_err());
+
+        let len = Escrow::migrated_space(&data).unwrap();
+        assert_eq!(len, 8 + Escrow::INIT_SPACE);
+        data.resize(len, 0);
+        let escrow = Escrow::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(escrow.recipient, Some(recipient));
+        assert_eq!(escrow.amount, 500);
+        assert_eq!(escrow.seed, [7; 32]);
+        assert!(!escrow.claimed);
+        assert_eq!(escrow.bump, 253);
+        assert_eq!(escrow.release_after, None);
+        assert_eq!(escrow.refund_after, None);
+        assert!(escrow.milestones.is_empty());
+        assert_eq!(escrow.released_amount, 0);
+        assert_eq!(escrow.arbiter, None);
+        assert_eq!(escrow.status, EscrowStatus::Active);
+        assert_eq!(escrow.remaining_amount(), 500);
+    }
+
+    #[test]
+    fn baseline_open_escrow_is_grown_to_full_size() {
+        // The unused recipient bytes already hold the new defaults, but the
+        // account still has to grow before milestones or an arbiter fit
+        let data = baseline_escrow(None);
+        assert!(Escrow::try_deserialize(&mut &data[..]).is_ok());
+        assert_eq!(Escrow::migrated_space(&data).unwrap(), 8 + Escrow::INIT_SPACE);
+    }
+
+    #[test]
+    fn migrated_space_rejects_other_accounts() {
+        let mut data = TokenAuthority::DISCRIMINATOR.to_vec();
+        data.resize(8 + TokenAuthority::INIT_SPACE, 0);
+        assert!(TokenMetadata::migrated_space(&data).is_err());
+        assert!(Escrow::migrated_space(&data).is_err());
+    }
+}
@@ -5,6 +5,7 @@ import { assert, expect } from 'chai';
 import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Transaction } from '@solana/web3.js';
 import {
   createAccount,
+  createMint,
   mintTo as splMintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
@@ -84,6 +85,9 @@ describe("token-module", () => {
 
   const TOKEN_DECIMALS = 9;
 
+  const findMintAuthority = (mint: PublicKey) =>
+    findPda([Buffer.from("mint_authority"), mint.toBuffer()], program.programId).publicKey;
+
   before(async () => {
     await Promise.all([
       provider.connection.requestAirdrop(adminKeypair.publicKey, 10 * LAMPORTS_PER_SOL),
@@ -165,7 +169,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: mintKeypair.publicKey,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -175,8 +179,8 @@ describe("token-module", () => {
 
       const mintInfo = await getMint(provider.connection, mintKeypair.publicKey, "confirmed", TOKEN_PROGRAM_ID);
       assert.strictEqual(mintInfo.decimals, TOKEN_DECIMALS);
-      assert.isTrue(mintInfo.mintAuthority?.equals(tokenAuthorityPDA));
-      assert.isTrue(mintInfo.freezeAuthority?.equals(tokenAuthorityPDA));
+      assert.isTrue(mintInfo.mintAuthority?.equals(findMintAuthority(mintKeypair.publicKey)));
+      assert.isTrue(mintInfo.freezeAuthority?.equals(findMintAuthority(mintKeypair.publicKey)));
 
       const tokenMetadataAccount = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
       assert.strictEqual(tokenMetadataAccount.name, tokenName);
@@ -200,7 +204,7 @@ describe("token-module", () => {
             admin: adminKeypair.publicKey,
             mint: newMintKeypair.publicKey,
             tokenMetadata: newMetadataPDA,
-            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(newMintKeypair.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -223,7 +227,7 @@ describe("token-module", () => {
             admin: adminKeypair.publicKey,
             mint: newMintKeypair.publicKey,
             tokenMetadata: newMetadataPDA,
-            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(newMintKeypair.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -246,7 +250,7 @@ describe("token-module", () => {
             admin: adminKeypair.publicKey,
             mint: newMintKeypair.publicKey,
             tokenMetadata: newMetadataPDA,
-            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(newMintKeypair.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -267,7 +271,7 @@ describe("token-module", () => {
           admin: unauthorizedUserKeypair.publicKey,
           mint: newMintKeypair.publicKey,
           tokenMetadata: newMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(newMintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -278,6 +282,77 @@ describe("token-module", () => {
       const tokenMetadataAccount = await program.account.tokenMetadata.fetch(newMetadataPDA);
       assert.isTrue(tokenMetadataAccount.admin.equals(unauthorizedUserKeypair.publicKey));
     });
+
+    it("Should record a designated admin instead of the payer", async () => {
+      const newMintKeypair = Keypair.generate();
+      const newMetadataPDA = findPda([Buffer.from("token_metadata"), newMintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      const designatedAdmin = Keypair.generate().publicKey;
+
+      await program.methods
+        .createToken(tokenName, tokenSymbol, TOKEN_DECIMALS, tokenUri, tokenMaxSupply)
+        .accounts({
+          admin: adminKeypair.publicKey,
+          mint: newMintKeypair.publicKey,
+          tokenMetadata: newMetadataPDA,
+          mintAuthority: findMintAuthority(newMintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+          designatedAdmin,
+        } as any)
+        .signers([adminKeypair, newMintKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      const tokenMetadataAccount = await program.account.tokenMetadata.fetch(newMetadataPDA);
+      assert.isTrue(tokenMetadataAccount.admin.equals(designatedAdmin));
+    });
+
+    it("Should update metadata and resize the account", async () => {
+      const updateMetadata = (name: string, symbol: string, uri: string | null) =>
+        program.methods
+          .updateTokenMetadata(name, symbol, uri)
+          .accounts({
+            admin: adminKeypair.publicKey,
+            mint: mintKeypair.publicKey,
+            tokenMetadata: tokenMetadataPDA,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([adminKeypair])
+          .rpc({ commitment: "confirmed" });
+
+      const initialSize = (await provider.connection.getAccountInfo(tokenMetadataPDA)).data.length;
+
+      await updateMetadata("Renamed Test Token", "RTTK", tokenUri + "?v=2");
+      let tokenMetadataAccount = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
+      assert.strictEqual(tokenMetadataAccount.name, "Renamed Test Token");
+      assert.strictEqual(tokenMetadataAccount.symbol, "RTTK");
+      assert.strictEqual(tokenMetadataAccount.uri, tokenUri + "?v=2");
+      const grownSize = (await provider.connection.getAccountInfo(tokenMetadataPDA)).data.length;
+      assert.strictEqual(grownSize, initialSize + "Renamed ".length + "?v=2".length + 1);
+
+      await updateMetadata("T", "T", null);
+      tokenMetadataAccount = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
+      assert.strictEqual(tokenMetadataAccount.name, "T");
+      assert.isNull(tokenMetadataAccount.uri);
+      const shrunkSize = (await provider.connection.getAccountInfo(tokenMetadataPDA)).data.length;
+      assert.isBelow(shrunkSize, initialSize);
+    });
+
+    it("Should fail to update metadata if not the admin", async () => {
+      await expectAnchorError(
+        program.methods
+          .updateTokenMetadata("Hijacked", "HJK", null)
+          .accounts({
+            admin: unauthorizedUserKeypair.publicKey,
+            mint: mintKeypair.publicKey,
+            tokenMetadata: tokenMetadataPDA,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .signers([unauthorizedUserKeypair])
+          .rpc(),
+        "UnauthorizedAdmin"
+      );
+    });
   });
 
 
@@ -295,7 +370,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: mintKeypair.publicKey,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -322,7 +397,7 @@ describe("token-module", () => {
           mint: mintKeypair.publicKey,
           destination: user1Ata,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([adminKeypair])
@@ -342,7 +417,7 @@ describe("token-module", () => {
             mint: mintKeypair.publicKey,
             destination: user1Ata,
             tokenMetadata: tokenMetadataPDA,
-            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(mintKeypair.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([adminKeypair])
@@ -361,7 +436,7 @@ describe("token-module", () => {
             mint: mintKeypair.publicKey,
             destination: user1Ata,
             tokenMetadata: tokenMetadataPDA,
-            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(mintKeypair.publicKey),
             tokenProgram: TOKEN_PROGRAM_ID,
           } as any)
           .signers([unauthorizedUserKeypair])
@@ -388,7 +463,7 @@ describe("token-module", () => {
                 mint: mintKeypair.publicKey,
                 destination: user1Ata,
                 tokenMetadata: tokenMetadataPDA,
-                tokenAuthority: tokenAuthorityPDA,
+                mintAuthority: findMintAuthority(mintKeypair.publicKey),
                 tokenProgram: TOKEN_PROGRAM_ID,
               } as any)
               .signers([adminKeypair])
@@ -419,7 +494,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: mintKeypair.publicKey,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -442,7 +517,7 @@ describe("token-module", () => {
           mint: mintKeypair.publicKey,
           destination: user1Ata,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([adminKeypair])
@@ -542,7 +617,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: mintKeypair.publicKey,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -561,7 +636,7 @@ describe("token-module", () => {
           mint: mintKeypair.publicKey,
           destination: user1Ata,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any)
         .signers([adminKeypair])
@@ -641,7 +716,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: mintKeypair.publicKey,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY,
@@ -660,7 +735,7 @@ describe("token-module", () => {
           mint: mintKeypair.publicKey,
           destination: user1Ata,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID
         } as any)
         .signers([adminKeypair])
@@ -673,7 +748,7 @@ describe("token-module", () => {
         mint: mintKeypair.publicKey,
         tokenAccount: user1Ata,
         tokenMetadata: tokenMetadataPDA,
-        tokenAuthority: tokenAuthorityPDA,
+        mintAuthority: findMintAuthority(mintKeypair.publicKey),
         tokenProgram: TOKEN_PROGRAM_ID,
       } as any).signers([adminKeypair]).rpc();
       let ataInfo = await getAccount(provider.connection, user1Ata);
@@ -699,7 +774,7 @@ describe("token-module", () => {
         mint: mintKeypair.publicKey,
         tokenAccount: user1Ata,
         tokenMetadata: tokenMetadataPDA,
-        tokenAuthority: tokenAuthorityPDA,
+        mintAuthority: findMintAuthority(mintKeypair.publicKey),
         tokenProgram: TOKEN_PROGRAM_ID,
       } as any).signers([adminKeypair]).rpc();
       ataInfo = await getAccount(provider.connection, user1Ata);
@@ -723,7 +798,7 @@ describe("token-module", () => {
           mint: mintKeypair.publicKey,
           tokenAccount: user1Ata,
           tokenMetadata: tokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
         } as any).signers([unauthorizedUserKeypair]).rpc(),
         "UnauthorizedFreezeAuthority"
@@ -751,7 +826,7 @@ describe("token-module", () => {
           admin: adminKeypair.publicKey,
           mint: escrowMintKeypair.publicKey,
           tokenMetadata: escrowTokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(escrowMintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY
@@ -769,7 +844,7 @@ describe("token-module", () => {
           mint: escrowMintKeypair.publicKey,
           destination: user1EscrowAta,
           tokenMetadata: escrowTokenMetadataPDA,
-          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(escrowMintKeypair.publicKey),
           tokenProgram: TOKEN_PROGRAM_ID
         } as any)
         .signers([adminKeypair]).rpc();
@@ -781,7 +856,7 @@ describe("token-module", () => {
       currentEscrowAuthPDA = findPda([Buffer.from("escrow_authority"), currentEscrowPDA.toBuffer()], program.programId).publicKey;
       currentEscrowTokenATA = getAssociatedTokenAddressSync(escrowMintKeypair.publicKey, currentEscrowAuthPDA, true);
 
-      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey)
+      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey, { releaseAfter: null, refundAfter: null, milestones: [], arbiter: null })
         .accounts({
           sender: user1Keypair.publicKey,
           mint: escrowMintKeypair.publicKey,
@@ -845,7 +920,7 @@ describe("token-module", () => {
       const newEscrowAuthPDA = findPda([Buffer.from("escrow_authority"), newEscrowPDA.toBuffer()], program.programId).publicKey;
       const newEscrowTokenATA = getAssociatedTokenAddressSync(escrowMintKeypair.publicKey, newEscrowAuthPDA, true);
 
-      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey)
+      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey, { releaseAfter: null, refundAfter: null, milestones: [], arbiter: null })
         .accounts({
           sender: user1Keypair.publicKey, mint: escrowMintKeypair.publicKey, senderTokenAccount: user1EscrowAta,
           escrow: newEscrowPDA, escrowAuthority: newEscrowAuthPDA, escrowTokenAccount: newEscrowTokenATA,
@@ -888,7 +963,7 @@ describe("token-module", () => {
       const newEscrowAuthPDA = findPda([Buffer.from("escrow_authority"), newEscrowPDA.toBuffer()], program.programId).publicKey;
       const newEscrowTokenATA = getAssociatedTokenAddressSync(escrowMintKeypair.publicKey, newEscrowAuthPDA, true);
 
-      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey) // Escrow for user2
+      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), user2Keypair.publicKey, { releaseAfter: null, refundAfter: null, milestones: [], arbiter: null }) // Escrow for user2
         .accounts({
           sender: user1Keypair.publicKey, mint: escrowMintKeypair.publicKey, senderTokenAccount: user1EscrowAta,
           escrow: newEscrowPDA, escrowAuthority: newEscrowAuthPDA, escrowTokenAccount: newEscrowTokenATA,
@@ -925,7 +1000,7 @@ describe("token-module", () => {
 
       const senderInitialBalanceUser1Ata = (await getAccount(provider.connection, user1EscrowAta)).amount;
 
-      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), null) // No recipient
+      await program.methods.createEscrow(escrowAmount, Array.from(localEscrowSeed), null, { releaseAfter: null, refundAfter: null, milestones: [], arbiter: null }) // No recipient
         .accounts({
           sender: user1Keypair.publicKey, mint: escrowMintKeypair.publicKey, senderTokenAccount: user1EscrowAta,
           escrow: newEscrowPDA, escrowAuthority: newEscrowAuthPDA, escrowTokenAccount: newEscrowTokenATA,
@@ -1000,4 +1075,844 @@ describe("token-module", () => {
       }
     });
   });
-});
\ No newline at end of file
+
+  describe("9. Admin Handover", () => {
+    const mintKeypair = Keypair.generate();
+    let tokenMetadataPDA: PublicKey;
+
+    const tokenAdminAccounts = (admin: PublicKey) => ({
+      admin,
+      mint: mintKeypair.publicKey,
+      tokenMetadata: tokenMetadataPDA,
+    });
+
+    before(async () => {
+      tokenMetadataPDA = findPda([Buffer.from("token_metadata"), mintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      await program.methods
+        .createToken("Handover Token", "HOT", TOKEN_DECIMALS, null, null)
+        .accounts({
+          admin: adminKeypair.publicKey,
+          mint: mintKeypair.publicKey,
+          tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([adminKeypair, mintKeypair])
+        .rpc({ commitment: "confirmed" });
+    });
+
+    it("Should fail to propose a handover if not the admin", async () => {
+      await expectAnchorError(
+        program.methods
+          .proposeTokenAdmin(unauthorizedUserKeypair.publicKey)
+          .accounts(tokenAdminAccounts(unauthorizedUserKeypair.publicKey) as any)
+          .signers([unauthorizedUserKeypair])
+          .rpc(),
+        "UnauthorizedAdmin"
+      );
+    });
+
+    it("Should hand a mint over in two steps", async () => {
+      await program.methods
+        .proposeTokenAdmin(user1Keypair.publicKey)
+        .accounts(tokenAdminAccounts(adminKeypair.publicKey) as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      let metadata = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
+      assert.isTrue(metadata.admin.equals(adminKeypair.publicKey));
+      assert.isTrue(metadata.pendingAdmin.equals(user1Keypair.publicKey));
+
+      await expectAnchorError(
+        program.methods
+          .acceptTokenAdmin()
+          .accounts({ pendingAdmin: user2Keypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA } as any)
+          .signers([user2Keypair])
+          .rpc(),
+        "UnauthorizedPendingAdmin"
+      );
+
+      await program.methods
+        .acceptTokenAdmin()
+        .accounts({ pendingAdmin: user1Keypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA } as any)
+        .signers([user1Keypair])
+        .rpc({ commitment: "confirmed" });
+
+      metadata = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
+      assert.isTrue(metadata.admin.equals(user1Keypair.publicKey));
+      assert.isNull(metadata.pendingAdmin);
+    });
+
+    it("Should fail to accept when no handover is pending", async () => {
+      await expectAnchorError(
+        program.methods
+          .acceptTokenAdmin()
+          .accounts({ pendingAdmin: user1Keypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA } as any)
+          .signers([user1Keypair])
+          .rpc(),
+        "NoPendingAdmin"
+      );
+    });
+
+    it("Should renounce the mint admin and block further minting", async () => {
+      const setPaused = (paused: boolean) =>
+        (paused ? program.methods.pauseToken() : program.methods.unpauseToken())
+          .accounts({ ...tokenAdminAccounts(user1Keypair.publicKey), role: null } as any)
+          .signers([user1Keypair])
+          .rpc({ commitment: "confirmed" });
+
+      // A paused mint would stay paused for good
+      await setPaused(true);
+      await expectAnchorError(
+        program.methods
+          .renounceTokenAdmin()
+          .accounts(tokenAdminAccounts(user1Keypair.publicKey) as any)
+          .signers([user1Keypair])
+          .rpc(),
+        "Paused"
+      );
+      await setPaused(false);
+
+      await program.methods
+        .renounceTokenAdmin()
+        .accounts(tokenAdminAccounts(user1Keypair.publicKey) as any)
+        .signers([user1Keypair])
+        .rpc({ commitment: "confirmed" });
+
+      const metadata = await program.account.tokenMetadata.fetch(tokenMetadataPDA);
+      assert.isTrue(metadata.admin.equals(PublicKey.default));
+
+      const user1Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user1Keypair.publicKey);
+      await provider.sendAndConfirm(
+        new Transaction().add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user1Ata, user1Keypair.publicKey, mintKeypair.publicKey)),
+        [user1Keypair],
+        { commitment: "confirmed" }
+      );
+
+      await expectAnchorError(
+        program.methods
+          .mintTokens(new BN(1))
+          .accounts({
+            ...tokenAdminAccounts(user1Keypair.publicKey),
+            destination: user1Ata,
+            mintAuthority: findMintAuthority(mintKeypair.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          } as any)
+          .signers([user1Keypair])
+          .rpc(),
+        "UnauthorizedMintAuthority"
+      );
+    });
+
+    it("Should hand the token authority over and back", async () => {
+      const authorityAccounts = (admin: PublicKey) => ({ admin, tokenAuthority: tokenAuthorityPDA });
+      const acceptAccounts = (pendingAdmin: PublicKey) => ({ pendingAdmin, tokenAuthority: tokenAuthorityPDA });
+
+      await program.methods
+        .proposeTokenAuthorityAdmin(user2Keypair.publicKey)
+        .accounts(authorityAccounts(adminKeypair.publicKey) as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .acceptTokenAuthorityAdmin()
+        .accounts(acceptAccounts(user2Keypair.publicKey) as any)
+        .signers([user2Keypair])
+        .rpc({ commitment: "confirmed" });
+
+      let authority = await program.account.tokenAuthority.fetch(tokenAuthorityPDA);
+      assert.isTrue(authority.admin.equals(user2Keypair.publicKey));
+
+      await program.methods
+        .proposeTokenAuthorityAdmin(adminKeypair.publicKey)
+        .accounts(authorityAccounts(user2Keypair.publicKey) as any)
+        .signers([user2Keypair])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .acceptTokenAuthorityAdmin()
+        .accounts(acceptAccounts(adminKeypair.publicKey) as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      authority = await program.account.tokenAuthority.fetch(tokenAuthorityPDA);
+      assert.isTrue(authority.admin.equals(adminKeypair.publicKey));
+      assert.isNull(authority.pendingAdmin);
+    });
+  });
+
+  describe("10. Mint Authority Migration", () => {
+    let legacyMint: PublicKey;
+
+    before(async () => {
+      // Mints created before per-mint authorities used the global PDA for both roles
+      legacyMint = await createMint(provider.connection, adminKeypair, tokenAuthorityPDA, tokenAuthorityPDA, TOKEN_DECIMALS);
+    });
+
+    it("Should move a legacy mint to its own mint authority", async () => {
+      await program.methods
+        .migrateMintAuthority()
+        .accounts({
+          mint: legacyMint,
+          tokenAuthority: tokenAuthorityPDA,
+          mintAuthority: findMintAuthority(legacyMint),
+          tokenMetadata: null,
+          payer: adminKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      const mintInfo = await getMint(provider.connection, legacyMint, "confirmed", TOKEN_PROGRAM_ID);
+      assert.isTrue(mintInfo.mintAuthority?.equals(findMintAuthority(legacyMint)));
+      assert.isTrue(mintInfo.freezeAuthority?.equals(findMintAuthority(legacyMint)));
+
+      const authority = await program.account.tokenAuthority.fetch(tokenAuthorityPDA);
+      assert.isTrue(authority.admin.equals(adminKeypair.publicKey));
+    });
+
+    it("Should fail to migrate a mint twice", async () => {
+      await expectAnchorError(
+        program.methods
+          .migrateMintAuthority()
+          .accounts({
+            mint: legacyMint,
+            tokenAuthority: tokenAuthorityPDA,
+            mintAuthority: findMintAuthority(legacyMint),
+            tokenMetadata: null,
+            payer: adminKeypair.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          } as any)
+          .signers([adminKeypair])
+          .rpc(),
+        "MintAlreadyMigrated"
+      );
+    });
+  });
+
+  describe("11. Mint Roles", () => {
+    const mintKeypair = Keypair.generate();
+    let tokenMetadataPDA: PublicKey;
+    let user1Ata: PublicKey;
+    const quota = new BN(10).mul(new BN(10).pow(new BN(TOKEN_DECIMALS)));
+
+    const ROLE_MINTER = 0;
+    const ROLE_FREEZER = 1;
+    const findRole = (holder: PublicKey, role: number) =>
+      findPda([Buffer.from("mint_role"), mintKeypair.publicKey.toBuffer(), holder.toBuffer(), Buffer.from([role])], program.programId).publicKey;
+
+    const mintAs = (signer: Keypair, amount: BN, role: PublicKey | null) =>
+      program.methods
+        .mintTokens(amount)
+        .accounts({
+          admin: signer.publicKey,
+          mint: mintKeypair.publicKey,
+          destination: user1Ata,
+          tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          role,
+        } as any)
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      tokenMetadataPDA = findPda([Buffer.from("token_metadata"), mintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      await program.methods
+        .createToken("Role Token", "ROLE", TOKEN_DECIMALS, null, null)
+        .accounts({
+          admin: adminKeypair.publicKey,
+          mint: mintKeypair.publicKey,
+          tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([adminKeypair, mintKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      user1Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user1Keypair.publicKey);
+      await provider.sendAndConfirm(
+        new Transaction().add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user1Ata, user1Keypair.publicKey, mintKeypair.publicKey)),
+        [user1Keypair],
+        { commitment: "confirmed" }
+      );
+    });
+
+    const grantRole = (signer: Keypair, holder: PublicKey, role: number, roleKind: any, total: BN | null) =>
+      program.methods
+        .grantRole(holder, roleKind, { total, perPeriod: null, periodSeconds: 0 })
+        .accounts({
+          admin: signer.publicKey,
+          mint: mintKeypair.publicKey,
+          tokenMetadata: tokenMetadataPDA,
+          mintRole: findRole(holder, role),
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+
+    it("Should fail to grant a role if not the admin", async () => {
+      await expectAnchorError(
+        grantRole(unauthorizedUserKeypair, unauthorizedUserKeypair.publicKey, ROLE_MINTER, { minter: {} }, null),
+        "UnauthorizedAdmin"
+      );
+    });
+
+    it("Should let a minter mint within its quota", async () => {
+      await grantRole(adminKeypair, user2Keypair.publicKey, ROLE_MINTER, { minter: {} }, quota);
+
+      await mintAs(user2Keypair, quota.subn(1), findRole(user2Keypair.publicKey, ROLE_MINTER));
+      const role = await program.account.mintRole.fetch(findRole(user2Keypair.publicKey, ROLE_MINTER));
+      assert.isTrue(role.totalMinted.eq(quota.subn(1)));
+
+      await expectAnchorError(
+        mintAs(user2Keypair, new BN(2), findRole(user2Keypair.publicKey, ROLE_MINTER)),
+        "MinterQuotaExceeded"
+      );
+    });
+
+    it("Should fail to mint without the admin key or a role", async () => {
+      await expectAnchorError(mintAs(user2Keypair, new BN(1), null), "UnauthorizedMintAuthority");
+    });
+
+    it("Should let a freezer freeze and thaw", async () => {
+      await grantRole(adminKeypair, user2Keypair.publicKey, ROLE_FREEZER, { freezer: {} }, null);
+      const freezeAccounts = {
+        admin: user2Keypair.publicKey,
+        mint: mintKeypair.publicKey,
+        tokenAccount: user1Ata,
+        tokenMetadata: tokenMetadataPDA,
+        mintAuthority: findMintAuthority(mintKeypair.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        role: findRole(user2Keypair.publicKey, ROLE_FREEZER),
+      };
+
+      await program.methods.freezeTokenAccount().accounts(freezeAccounts as any).signers([user2Keypair]).rpc({ commitment: "confirmed" });
+      assert.isTrue((await getAccount(provider.connection, user1Ata)).isFrozen);
+
+      await program.methods.thawTokenAccount().accounts(freezeAccounts as any).signers([user2Keypair]).rpc({ commitment: "confirmed" });
+      assert.isFalse((await getAccount(provider.connection, user1Ata)).isFrozen);
+    });
+
+    it("Should revoke a role", async () => {
+      const rolePDA = findRole(user2Keypair.publicKey, ROLE_MINTER);
+      await program.methods
+        .revokeRole()
+        .accounts({ admin: adminKeypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA, mintRole: rolePDA } as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      assert.isNull(await provider.connection.getAccountInfo(rolePDA));
+      try {
+        await mintAs(user2Keypair, new BN(1), rolePDA);
+        assert.fail("Minting with a revoked role should fail");
+      } catch (error) {
+        expect(error).to.exist;
+      }
+    });
+  });
+
+  describe("12. Pause", () => {
+    const mintKeypair = Keypair.generate();
+    let tokenMetadataPDA: PublicKey;
+    let user1Ata: PublicKey;
+    let user2Ata: PublicKey;
+
+    const setPaused = (paused: boolean, signer: Keypair = adminKeypair) =>
+      (paused ? program.methods.pauseToken() : program.methods.unpauseToken())
+        .accounts({ admin: signer.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA, role: null } as any)
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+
+    const transfer = () =>
+      program.methods
+        .transferTokens(new BN(1))
+        .accounts({
+          owner: user1Keypair.publicKey,
+          mint: mintKeypair.publicKey,
+          fromAccount: user1Ata,
+          toAccount: user2Ata,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMetadata: tokenMetadataPDA,
+        } as any)
+        .signers([user1Keypair])
+        .rpc({ commitment: "confirmed" });
+
+    before(async () => {
+      tokenMetadataPDA = findPda([Buffer.from("token_metadata"), mintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      await program.methods
+        .createToken("Pause Token", "PSE", TOKEN_DECIMALS, null, null)
+        .accounts({
+          admin: adminKeypair.publicKey,
+          mint: mintKeypair.publicKey,
+          tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([adminKeypair, mintKeypair])
+        .rpc({ commitment: "confirmed" });
+
+      user1Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user1Keypair.publicKey);
+      user2Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey);
+      await provider.sendAndConfirm(
+        new Transaction()
+          .add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user1Ata, user1Keypair.publicKey, mintKeypair.publicKey))
+          .add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user2Ata, user2Keypair.publicKey, mintKeypair.publicKey)),
+        [user1Keypair],
+        { commitment: "confirmed" }
+      );
+      await program.methods
+        .mintTokens(new BN(1000))
+        .accounts({
+          admin: adminKeypair.publicKey,
+          mint: mintKeypair.publicKey,
+          destination: user1Ata,
+          tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          role: null,
+        } as any)
+        .signers([adminKeypair])
+        .rpc({ commitment: "confirmed" });
+    });
+
+    it("Should fail to pause if not the admin or a pauser", async () => {
+      await expectAnchorError(setPaused(true, unauthorizedUserKeypair), "UnauthorizedPauseAuthority");
+    });
+
+    it("Should block transfers and minting while paused", async () => {
+      await setPaused(true);
+      assert.isTrue((await program.account.tokenMetadata.fetch(tokenMetadataPDA)).paused);
+
+      await expectAnchorError(transfer(), "Paused");
+      await expectAnchorError(
+        program.methods
+          .mintTokens(new BN(1))
+          .accounts({
+            admin: adminKeypair.publicKey,
+            mint: mintKeypair.publicKey,
+            destination: user1Ata,
+            tokenMetadata: tokenMetadataPDA,
+            mintAuthority: findMintAuthority(mintKeypair.publicKey),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            role: null,
+          } as any)
+          .signers([adminKeypair])
+          .rpc(),
+        "Paused"
+      );
+    });
+
+    it("Should allow transfers again after unpausing", async () => {
+      await setPaused(false);
+      await transfer();
+      assert.strictEqual((await getAccount(provider.connection, user2Ata)).amount, BigInt(1));
+
+      await expectAnchorError(setPaused(false), "NotPaused");
+    });
+  });
+
+  describe("13. Escrow Schedules", () => {
+    const mintKeypair = Keypair.generate();
+    let tokenMetadataPDA: PublicKey;
+    let user1Ata: PublicKey;
+    const escrowAmount = new BN(5).mul(new BN(10).pow(new BN(TOKEN_DECIMALS)));
+
+    const escrowAccounts = (seed: Buffer) => {
+      const escrow = findPda([Buffer.from("token_escrow"), user1Keypair.publicKey.toBuffer(), mintKeypair.publicKey.toBuffer(), seed], program.programId).publicKey;
+      const escrowAuthority = findPda([Buffer.from("escrow_authority"), escrow.toBuffer()], program.programId).publicKey;
+      return { escrow, escrowAuthority, escrowTokenAccount: getAssociatedTokenAddressSync(mintKeypair.publicKey, escrowAuthority, true) };
+    };
+
+    const createEscrow = (
+      seed: Buffer,
+      releaseAfter: BN | null,
+      refundAfter: BN | null,
+      milestones: { amount: BN; releaseAt: BN | null }[] = [],
+      arbiter: PublicKey | null = null
+    ) =>
+      program.methods.createEscrow(escrowAmount, Array.from(seed), user2Keypair.publicKey, { releaseAfter, refundAfter, milestones, arbiter })
+        .accounts({
+          sender: user1Keypair.publicKey, mint: mintKeypair.publicKey, senderTokenAccount: user1Ata,
+          ...escrowAccounts(seed),
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId, rent: SYSVAR_RENT_PUBKEY,
+        } as any).signers([user1Keypair]).rpc({ commitment: "confirmed" });
+
+    const cancelEscrow = (seed: Buffer, sender: Keypair = user1Keypair) =>
+      program.methods.cancelEscrow().accounts({
+        sender: sender.publicKey, mint: mintKeypair.publicKey,
+        ...escrowAccounts(seed),
+        senderTokenAccount: getAssociatedTokenAddressSync(mintKeypair.publicKey, sender.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any).signers([sender]).rpc({ commitment: "confirmed" });
+
+    const now = () => new BN(Math.floor(Date.now() / 1000));
+
+    before(async () => {
+      tokenMetadataPDA = findPda([Buffer.from("token_metadata"), mintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      await program.methods.createToken("Timelock Token", "TLK", TOKEN_DECIMALS, null, null)
+        .accounts({
+          admin: adminKeypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey), tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId, rent: SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([adminKeypair, mintKeypair]).rpc({ commitment: "confirmed" });
+
+      user1Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user1Keypair.publicKey);
+      await provider.sendAndConfirm(
+        new Transaction().add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user1Ata, user1Keypair.publicKey, mintKeypair.publicKey)),
+        [user1Keypair],
+        { commitment: "confirmed" }
+      );
+      await program.methods.mintTokens(escrowAmount.mul(new BN(5)))
+        .accounts({
+          admin: adminKeypair.publicKey, mint: mintKeypair.publicKey, destination: user1Ata, tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey), tokenProgram: TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([adminKeypair]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Should fail to create an escrow that refunds before it releases", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await expectAnchorError(createEscrow(seed, now().addn(100), now().addn(50)), "InvalidEscrowSchedule");
+    });
+
+    it("Should keep the recipient from claiming before release_after", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createEscrow(seed, now().addn(3600), null);
+
+      await expectAnchorError(
+        program.methods.releaseEscrow().accounts({
+          recipient: user2Keypair.publicKey, mint: mintKeypair.publicKey,
+          ...escrowAccounts(seed),
+          recipientTokenAccount: getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any).signers([user2Keypair]).rpc(),
+        "EscrowLocked"
+      );
+      await expectAnchorError(cancelEscrow(seed), "EscrowNotRefundable");
+    });
+
+    it("Should let the sender cancel after refund_after and close both accounts", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createEscrow(seed, null, now().subn(10));
+      const balanceBefore = (await getAccount(provider.connection, user1Ata)).amount;
+
+      await expectAnchorError(cancelEscrow(seed, user2Keypair), "ConstraintHasOne");
+      await cancelEscrow(seed);
+
+      const balanceAfter = (await getAccount(provider.connection, user1Ata)).amount;
+      assert.strictEqual(balanceAfter - balanceBefore, BigInt(escrowAmount.toString()));
+      const { escrow, escrowTokenAccount } = escrowAccounts(seed);
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await provider.connection.getAccountInfo(escrowTokenAccount));
+    });
+
+    it("Should release milestones one by one and close both accounts when drained", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      const half = escrowAmount.divn(2);
+      await expectAnchorError(
+        createEscrow(seed, null, null, [{ amount: half, releaseAt: null }, { amount: half.subn(1), releaseAt: null }]),
+        "InvalidMilestones"
+      );
+      await createEscrow(seed, null, null, [
+        { amount: half, releaseAt: null },
+        { amount: escrowAmount.sub(half), releaseAt: now().subn(10) },
+      ]);
+
+      const { escrow, escrowAuthority, escrowTokenAccount } = escrowAccounts(seed);
+      const user2Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey);
+      const releaseMilestone = (index: number) =>
+        program.methods.releaseMilestone(index).accounts({
+          recipient: user2Keypair.publicKey, escrow, mint: mintKeypair.publicKey, escrowAuthority, escrowTokenAccount,
+          recipientTokenAccount: user2Ata, tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        } as any).signers([user2Keypair]).rpc({ commitment: "confirmed" });
+
+      await expectAnchorError(
+        program.methods.releaseEscrow().accounts({
+          recipient: user2Keypair.publicKey, escrow, mint: mintKeypair.publicKey, escrowAuthority, escrowTokenAccount,
+          recipientTokenAccount: user2Ata, tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        } as any).signers([user2Keypair]).rpc(),
+        "EscrowHasMilestones"
+      );
+
+      // The second milestone is already due, the first needs the sender's approval
+      await expectAnchorError(releaseMilestone(0), "MilestoneLocked");
+      await releaseMilestone(1);
+      const escrowData = await program.account.escrow.fetch(escrow);
+      assert.isTrue(escrowData.releasedAmount.eq(escrowAmount.sub(half)));
+      await expectAnchorError(releaseMilestone(1), "MilestoneAlreadyReleased");
+
+      await program.methods.approveMilestone(0)
+        .accounts({ sender: user1Keypair.publicKey, escrow } as any)
+        .signers([user1Keypair]).rpc({ commitment: "confirmed" });
+      await releaseMilestone(0);
+
+      assert.strictEqual((await getAccount(provider.connection, user2Ata)).amount, BigInt(escrowAmount.toString()));
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await provider.connection.getAccountInfo(escrowTokenAccount));
+    });
+
+    it("Should lock a disputed escrow until the arbiter splits it", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      const arbiter = adminKeypair;
+      await expectAnchorError(createEscrow(seed, null, null, [], user2Keypair.publicKey), "InvalidArbiter");
+      await createEscrow(seed, null, null, [], arbiter.publicKey);
+
+      const { escrow, escrowAuthority, escrowTokenAccount } = escrowAccounts(seed);
+      const user2Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey);
+      const user2Before = (await getAccount(provider.connection, user2Ata)).amount;
+      const user1Before = (await getAccount(provider.connection, user1Ata)).amount;
+
+      await expectAnchorError(
+        program.methods.raiseDispute().accounts({ party: unauthorizedUserKeypair.publicKey, escrow } as any)
+          .signers([unauthorizedUserKeypair]).rpc(),
+        "UnauthorizedDisputeParty"
+      );
+      await program.methods.raiseDispute().accounts({ party: user1Keypair.publicKey, escrow } as any)
+        .signers([user1Keypair]).rpc({ commitment: "confirmed" });
+      assert.deepEqual((await program.account.escrow.fetch(escrow)).status, { disputed: {} });
+
+      await expectAnchorError(
+        program.methods.releaseEscrow().accounts({
+          recipient: user2Keypair.publicKey, escrow, mint: mintKeypair.publicKey, escrowAuthority, escrowTokenAccount,
+          recipientTokenAccount: user2Ata, tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID, systemProgram: SystemProgram.programId,
+        } as any).signers([user2Keypair]).rpc(),
+        "EscrowDisputed"
+      );
+
+      const recipientShare = escrowAmount.muln(3).divn(10);
+      const resolve = (signer: Keypair) =>
+        program.methods.resolveDispute(recipientShare).accounts({
+          arbiter: signer.publicKey, escrow, mint: mintKeypair.publicKey,
+          sender: user1Keypair.publicKey, recipient: user2Keypair.publicKey,
+          escrowAuthority, escrowTokenAccount, senderTokenAccount: user1Ata, recipientTokenAccount: user2Ata,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any).signers([signer]).rpc({ commitment: "confirmed" });
+
+      await expectAnchorError(resolve(user2Keypair), "UnauthorizedArbiter");
+      await resolve(arbiter);
+
+      const user2After = (await getAccount(provider.connection, user2Ata)).amount;
+      const user1After = (await getAccount(provider.connection, user1Ata)).amount;
+      assert.strictEqual(user2After - user2Before, BigInt(recipientShare.toString()));
+      assert.strictEqual(user1After - user1Before, BigInt(escrowAmount.sub(recipientShare).toString()));
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await provider.connection.getAccountInfo(escrowTokenAccount));
+    });
+  });
+
+  describe("14. Vesting", () => {
+    const mintKeypair = Keypair.generate();
+    let tokenMetadataPDA: PublicKey;
+    let user1Ata: PublicKey;
+    const totalAmount = new BN(100).mul(new BN(10).pow(new BN(TOKEN_DECIMALS)));
+
+    const vestingAccounts = (seed: Buffer) => {
+      const vesting = findPda([Buffer.from("vesting"), user1Keypair.publicKey.toBuffer(), mintKeypair.publicKey.toBuffer(), seed], program.programId).publicKey;
+      const vestingAuthority = findPda([Buffer.from("vesting_authority"), vesting.toBuffer()], program.programId).publicKey;
+      return { vesting, vestingAuthority, vestingTokenAccount: getAssociatedTokenAddressSync(mintKeypair.publicKey, vestingAuthority, true) };
+    };
+
+    const createVesting = (
+      seed: Buffer,
+      schedule: { startTime: BN; cliffSeconds: BN; durationSeconds: BN; releaseIntervalSeconds: BN },
+      revokeAuthority: PublicKey | null = null
+    ) =>
+      program.methods.createVesting(Array.from(seed), totalAmount, user2Keypair.publicKey, schedule, revokeAuthority)
+        .accounts({
+          issuer: user1Keypair.publicKey, mint: mintKeypair.publicKey, issuerTokenAccount: user1Ata,
+          ...vestingAccounts(seed),
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any).signers([user1Keypair]).rpc({ commitment: "confirmed" });
+
+    const claimVested = (seed: Buffer) =>
+      program.methods.claimVested().accounts({
+        beneficiary: user2Keypair.publicKey, mint: mintKeypair.publicKey, issuer: user1Keypair.publicKey,
+        ...vestingAccounts(seed),
+        beneficiaryTokenAccount: getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any).signers([user2Keypair]).rpc({ commitment: "confirmed" });
+
+    const now = () => new BN(Math.floor(Date.now() / 1000));
+
+    before(async () => {
+      tokenMetadataPDA = findPda([Buffer.from("token_metadata"), mintKeypair.publicKey.toBuffer()], program.programId).publicKey;
+      await program.methods.createToken("Vesting Token", "VST", TOKEN_DECIMALS, null, null)
+        .accounts({
+          admin: adminKeypair.publicKey, mint: mintKeypair.publicKey, tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey), tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId, rent: SYSVAR_RENT_PUBKEY,
+        } as any)
+        .signers([adminKeypair, mintKeypair]).rpc({ commitment: "confirmed" });
+
+      user1Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user1Keypair.publicKey);
+      await provider.sendAndConfirm(
+        new Transaction().add(createAssociatedTokenAccountInstruction(user1Keypair.publicKey, user1Ata, user1Keypair.publicKey, mintKeypair.publicKey)),
+        [user1Keypair],
+        { commitment: "confirmed" }
+      );
+      await program.methods.mintTokens(totalAmount.muln(4))
+        .accounts({
+          admin: adminKeypair.publicKey, mint: mintKeypair.publicKey, destination: user1Ata, tokenMetadata: tokenMetadataPDA,
+          mintAuthority: findMintAuthority(mintKeypair.publicKey), tokenProgram: TOKEN_PROGRAM_ID,
+        } as any)
+        .signers([adminKeypair]).rpc({ commitment: "confirmed" });
+    });
+
+    it("Should fail to create a vesting with a cliff past its duration", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await expectAnchorError(
+        createVesting(seed, { startTime: now(), cliffSeconds: new BN(200), durationSeconds: new BN(100), releaseIntervalSeconds: new BN(10) }),
+        "InvalidVestingSchedule"
+      );
+    });
+
+    it("Should have nothing to claim before the cliff", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createVesting(seed, { startTime: now(), cliffSeconds: new BN(3600), durationSeconds: new BN(7200), releaseIntervalSeconds: new BN(60) });
+      const { vestingTokenAccount } = vestingAccounts(seed);
+      assert.strictEqual((await getAccount(provider.connection, vestingTokenAccount)).amount, BigInt(totalAmount.toString()));
+      await expectAnchorError(claimVested(seed), "NothingToClaim");
+    });
+
+    it("Should close a fully claimed vesting to the issuer", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createVesting(seed, { startTime: now().subn(1000), cliffSeconds: new BN(100), durationSeconds: new BN(500), releaseIntervalSeconds: new BN(50) });
+      const { vesting, vestingTokenAccount } = vestingAccounts(seed);
+      const issuerLamports = await provider.connection.getBalance(user1Keypair.publicKey);
+      const rent = (await provider.connection.getAccountInfo(vesting))!.lamports
+        + (await provider.connection.getAccountInfo(vestingTokenAccount))!.lamports;
+      await claimVested(seed);
+
+      const user2Ata = getAssociatedTokenAddressSync(mintKeypair.publicKey, user2Keypair.publicKey);
+      assert.strictEqual((await getAccount(provider.connection, user2Ata)).amount, BigInt(totalAmount.toString()));
+      assert.isNull(await provider.connection.getAccountInfo(vesting));
+      assert.isNull(await provider.connection.getAccountInfo(vestingTokenAccount));
+      assert.strictEqual(await provider.connection.getBalance(user1Keypair.publicKey), issuerLamports + rent);
+    });
+
+    it("Should let the revoke authority return the unvested remainder to the issuer", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createVesting(
+        seed,
+        { startTime: now(), cliffSeconds: new BN(3600), durationSeconds: new BN(7200), releaseIntervalSeconds: new BN(60) },
+        adminKeypair.publicKey
+      );
+      const { vesting, vestingAuthority, vestingTokenAccount } = vestingAccounts(seed);
+      const revoke = (signer: Keypair) =>
+        program.methods.revokeVesting().accounts({
+          revokeAuthority: signer.publicKey, vesting, mint: mintKeypair.publicKey, issuer: user1Keypair.publicKey,
+          vestingAuthority, vestingTokenAccount, issuerTokenAccount: user1Ata,
+          tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        } as any).signers([signer]).rpc({ commitment: "confirmed" });
+
+      await expectAnchorError(revoke(user2Keypair), "UnauthorizedRevokeAuthority");
+      const user1Before = (await getAccount(provider.connection, user1Ata)).amount;
+      await revoke(adminKeypair);
+
+      const user1After = (await getAccount(provider.connection, user1Ata)).amount;
+      assert.strictEqual(user1After - user1Before, BigInt(totalAmount.toString()));
+      // Nothing had vested, so there is nothing left to claim and both accounts close
+      assert.isNull(await provider.connection.getAccountInfo(vesting));
+      assert.isNull(await provider.connection.getAccountInfo(vestingTokenAccount));
+    });
+
+    it("Should keep a revoked vesting open until the vested part is claimed", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createVesting(
+        seed,
+        { startTime: now().subn(500), cliffSeconds: new BN(0), durationSeconds: new BN(1000), releaseIntervalSeconds: new BN(100) },
+        adminKeypair.publicKey
+      );
+      const { vesting, vestingAuthority, vestingTokenAccount } = vestingAccounts(seed);
+      await program.methods.revokeVesting().accounts({
+        revokeAuthority: adminKeypair.publicKey, vesting, mint: mintKeypair.publicKey, issuer: user1Keypair.publicKey,
+        vestingAuthority, vestingTokenAccount, issuerTokenAccount: user1Ata,
+        tokenProgram: TOKEN_PROGRAM_ID, associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      } as any).signers([adminKeypair]).rpc({ commitment: "confirmed" });
+
+      const state = await program.account.vesting.fetch(vesting);
+      assert.isTrue(state.revoked);
+      assert.isFalse(state.totalAmount.isZero());
+
+      await claimVested(seed);
+      assert.isNull(await provider.connection.getAccountInfo(vesting));
+      assert.isNull(await provider.connection.getAccountInfo(vestingTokenAccount));
+    });
+  });
+
+  describe("15. SOL Escrow", () => {
+    const escrowAmount = new BN(LAMPORTS_PER_SOL / 10);
+
+    const findSolEscrow = (seed: Buffer) =>
+      findPda([Buffer.from("sol_escrow"), user1Keypair.publicKey.toBuffer(), seed], program.programId).publicKey;
+
+    const createSolEscrow = (seed: Buffer, releaseAfter: BN | null, refundAfter: BN | null) =>
+      program.methods.createSolEscrow(escrowAmount, Array.from(seed), user2Keypair.publicKey, releaseAfter, refundAfter)
+        .accounts({
+          sender: user1Keypair.publicKey, escrow: findSolEscrow(seed), systemProgram: SystemProgram.programId,
+        } as any).signers([user1Keypair]).rpc({ commitment: "confirmed" });
+
+    const releaseSolEscrow = (seed: Buffer, recipient: Keypair = user2Keypair) =>
+      program.methods.releaseSolEscrow().accounts({ recipient: recipient.publicKey, escrow: findSolEscrow(seed) } as any)
+        .signers([recipient]).rpc({ commitment: "confirmed" });
+
+    const cancelSolEscrow = (seed: Buffer) =>
+      program.methods.cancelSolEscrow().accounts({ sender: user1Keypair.publicKey, escrow: findSolEscrow(seed) } as any)
+        .signers([user1Keypair]).rpc({ commitment: "confirmed" });
+
+    const now = () => new BN(Math.floor(Date.now() / 1000));
+
+    it("Should hold the SOL in the escrow PDA and release it to the recipient", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createSolEscrow(seed, null, null);
+      const escrow = findSolEscrow(seed);
+      const rent = await provider.connection.getMinimumBalanceForRentExemption((await provider.connection.getAccountInfo(escrow))!.data.length);
+      assert.strictEqual(await provider.connection.getBalance(escrow), rent + escrowAmount.toNumber());
+
+      await expectAnchorError(releaseSolEscrow(seed, unauthorizedUserKeypair), "UnauthorizedRecipient");
+      const before = await provider.connection.getBalance(user2Keypair.publicKey);
+      await releaseSolEscrow(seed);
+      const after = await provider.connection.getBalance(user2Keypair.publicKey);
+      assert.isAtLeast(after - before, escrowAmount.toNumber());
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+    });
+
+    it("Should keep the recipient from claiming before release_after", async () => {
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createSolEscrow(seed, now().addn(3600), null);
+      await expectAnchorError(releaseSolEscrow(seed), "EscrowLocked");
+    });
+
+    it("Should only refund the sender once refund_after has passed", async () => {
+      const lockedSeed = Keypair.generate().publicKey.toBuffer();
+      await createSolEscrow(lockedSeed, null, now().addn(3600));
+      await expectAnchorError(cancelSolEscrow(lockedSeed), "EscrowLocked");
+
+      const openSeed = Keypair.generate().publicKey.toBuffer();
+      await createSolEscrow(openSeed, null, null);
+      await expectAnchorError(cancelSolEscrow(openSeed), "EscrowNotRefundable");
+
+      const seed = Keypair.generate().publicKey.toBuffer();
+      await createSolEscrow(seed, null, now().subn(1));
+      await cancelSolEscrow(seed);
+      assert.isNull(await provider.connection.getAccountInfo(findSolEscrow(seed)));
+    });
+  });
+});